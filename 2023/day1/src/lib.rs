@@ -1,5 +1,42 @@
 mod part1;
 mod part2;
+mod scanner;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+pub use scanner::{DigitScanner, Token};
+
+use anyhow::Result;
+use aoc_core::Solution;
+use std::fmt::Display;
+
+/// The lines of the calibration document, which each part scans for its
+/// own kind of digits.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Vec<&'static str>> {
+    let _frame = aoc_telemetry::frame!("parse");
+
+    Ok(input.lines().collect())
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 1;
+
+    type Input = Vec<&'static str>;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+}
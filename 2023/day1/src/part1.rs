@@ -1,30 +1,23 @@
+use crate::{parse, scanner::DigitScanner};
 use anyhow::{Context, Result};
+use aoc_core::SolveError;
 
-#[tracing::instrument]
-fn process(input: &str) -> Result<String> {
-    let output = input
-        .lines()
-        .map(|line| {
-            let mut it = line.chars().filter_map(|ch| ch.to_digit(10));
-
-            let first = it.next().expect("should be a number");
-            match it.last() {
-                Some(last) => first * 10 + last,
-                None => first * 10 + first,
-            }
-        })
-        .sum::<u32>();
+#[tracing::instrument(skip(lines))]
+fn process(lines: &[&str]) -> Result<String, SolveError> {
+    let output = DigitScanner::digits().calibrate(lines)?;
 
     Ok(output.to_string())
 }
 
-#[tracing::instrument]
-pub fn part1() -> Result<()> {
-    let file = include_str!("../input1.txt");
-    let result = process(file).context("process part 1")?;
-    println!("Part 1: {}", result);
+#[tracing::instrument(skip(lines))]
+pub fn solve_part1(lines: &[&str]) -> Result<String> {
+    let _frame = aoc_telemetry::frame!("part 1");
+
+    process(lines).context("process part 1")
+}
 
-    Ok(())
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -38,7 +31,7 @@ pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "142");
     }
 }
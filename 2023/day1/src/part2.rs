@@ -1,56 +1,23 @@
+use crate::{parse, scanner::DigitScanner};
 use anyhow::{Context, Result};
+use aoc_core::SolveError;
 
-#[tracing::instrument]
-fn process_line(input: &str) -> u32 {
-    let mut it = (0..input.len()).filter_map(|index| {
-        let trimmed_line = &input[index..];
-        let result = if trimmed_line.starts_with("one") {
-            '1'
-        } else if trimmed_line.starts_with("two") {
-            '2'
-        } else if trimmed_line.starts_with("three") {
-            '3'
-        } else if trimmed_line.starts_with("four") {
-            '4'
-        } else if trimmed_line.starts_with("five") {
-            '5'
-        } else if trimmed_line.starts_with("six") {
-            '6'
-        } else if trimmed_line.starts_with("seven") {
-            '7'
-        } else if trimmed_line.starts_with("eight") {
-            '8'
-        } else if trimmed_line.starts_with("nine") {
-            '9'
-        } else {
-            trimmed_line.chars().next().unwrap()
-        };
+#[tracing::instrument(skip(lines))]
+fn process(lines: &[&str]) -> Result<String, SolveError> {
+    let output = DigitScanner::english().calibrate(lines)?;
 
-        result.to_digit(10)
-    });
-
-    let first = it.next().expect("should be a number");
-
-    match it.last() {
-        Some(last) => first * 10 + last,
-        None => first * 10 + first,
-    }
+    Ok(output.to_string())
 }
 
-#[tracing::instrument]
-fn process(input: &str) -> Result<String> {
-    let output = input.lines().map(process_line).sum::<u32>();
+#[tracing::instrument(skip(lines))]
+pub fn solve_part2(lines: &[&str]) -> Result<String> {
+    let _frame = aoc_telemetry::frame!("part 2");
 
-    Ok(output.to_string())
+    process(lines).context("process part 2")
 }
 
-#[tracing::instrument]
-pub fn part2() -> Result<()> {
-    let file = include_str!("../input2.txt");
-    let result = process(file).context("process part 2")?;
-    println!("Part 2: {}", result);
-
-    Ok(())
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -67,7 +34,7 @@ xtwone3four
 zoneight234
 7pqrstsixteen
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "281");
     }
 }
@@ -0,0 +1,187 @@
+use aoc_core::SolveError;
+use std::{cmp::Reverse, collections::VecDeque};
+
+const ROOT: usize = 0;
+
+#[derive(Debug, Clone)]
+struct Node {
+    next: [usize; 256],
+    /// `(value, length)` of every word ending at this node, including the
+    /// ones inherited through failure links.
+    words: Vec<(u32, usize)>,
+}
+
+impl Node {
+    fn new() -> Self {
+        Self {
+            next: [ROOT; 256],
+            words: Vec::new(),
+        }
+    }
+}
+
+/// Aho–Corasick automaton over a vocabulary of digit tokens. All matches are
+/// reported, so overlapping words such as `eightwo` yield both `8` and `2`.
+#[derive(Debug, Clone)]
+pub struct DigitScanner {
+    nodes: Vec<Node>,
+}
+
+/// A digit token found in a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub start: usize,
+    pub len: usize,
+    pub value: u32,
+}
+
+impl DigitScanner {
+    const DIGITS: [&'static str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+    const ENGLISH: [&'static str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    /// Builds a scanner for `words`, each mapped to the digit it stands for.
+    pub fn new<'a>(words: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        let mut nodes = vec![Node::new()];
+        let mut is_edge = vec![[false; 256]];
+
+        for (word, value) in words {
+            if word.is_empty() {
+                continue;
+            }
+
+            let mut state = ROOT;
+            for &b in word.as_bytes() {
+                if !is_edge[state][b as usize] {
+                    nodes.push(Node::new());
+                    is_edge.push([false; 256]);
+                    nodes[state].next[b as usize] = nodes.len() - 1;
+                    is_edge[state][b as usize] = true;
+                }
+                state = nodes[state].next[b as usize];
+            }
+            nodes[state].words.push((value, word.len()));
+        }
+
+        // Breadth-first over the trie, turning it into a DFA by filling the
+        // missing transitions from each node's failure state.
+        let mut fail = vec![ROOT; nodes.len()];
+        let mut queue = (0..256)
+            .filter(|&b| is_edge[ROOT][b])
+            .map(|b| nodes[ROOT].next[b])
+            .collect::<VecDeque<_>>();
+
+        while let Some(state) = queue.pop_front() {
+            let inherited = nodes[fail[state]].words.clone();
+            nodes[state].words.extend(inherited);
+
+            let edges = is_edge[state];
+            for (b, edge) in edges.into_iter().enumerate() {
+                let fallback = nodes[fail[state]].next[b];
+
+                if edge {
+                    let child = nodes[state].next[b];
+                    fail[child] = fallback;
+                    queue.push_back(child);
+                } else {
+                    nodes[state].next[b] = fallback;
+                }
+            }
+        }
+
+        Self { nodes }
+    }
+
+    /// Only the ASCII digits `0`–`9`.
+    pub fn digits() -> Self {
+        Self::new(Self::DIGITS.iter().zip(0..).map(|(w, v)| (*w, v)))
+    }
+
+    /// ASCII digits plus the English words `one` through `nine`.
+    pub fn english() -> Self {
+        Self::new(
+            Self::DIGITS
+                .iter()
+                .zip(0..)
+                .chain(Self::ENGLISH.iter().zip(1..))
+                .map(|(w, v)| (*w, v)),
+        )
+    }
+
+    /// Every token in `line`, ordered by where it ends.
+    pub fn tokens<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Token> + 'a {
+        line.bytes()
+            .enumerate()
+            .scan(ROOT, move |state, (i, b)| {
+                *state = self.nodes[*state].next[b as usize];
+                Some((i, *state))
+            })
+            .flat_map(move |(i, state)| {
+                self.nodes[state]
+                    .words
+                    .iter()
+                    .map(move |&(value, len)| Token {
+                        start: i + 1 - len,
+                        len,
+                        value,
+                    })
+            })
+    }
+
+    /// The first and last tokens of `line` by starting position. When two
+    /// tokens start at the same place the longer one wins.
+    pub fn first_and_last(&self, line: &str) -> Option<(Token, Token)> {
+        let first = self
+            .tokens(line)
+            .min_by_key(|t| (t.start, Reverse(t.len)))?;
+        let last = self.tokens(line).max_by_key(|t| (t.start, t.len))?;
+
+        Some((first, last))
+    }
+
+    /// The two digit number made of the first and last digit in `line`.
+    pub fn calibration_value(&self, line: &str) -> Result<u32, SolveError> {
+        let Some((first, last)) = self.first_and_last(line) else {
+            return Err(SolveError::malformed(format!("no digit in line {line:?}")));
+        };
+
+        Ok(first.value * 10 + last.value)
+    }
+
+    /// Sum of the calibration values of `lines`.
+    pub fn calibrate(&self, lines: &[&str]) -> Result<u32, SolveError> {
+        lines.iter().map(|line| self.calibration_value(line)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_words() {
+        let scanner = DigitScanner::english();
+
+        assert_eq!(scanner.calibration_value("eightwo").unwrap(), 82);
+        assert_eq!(scanner.calibration_value("xtwone3four").unwrap(), 24);
+        assert_eq!(scanner.calibration_value("oneight").unwrap(), 18);
+        assert_eq!(scanner.calibration_value("7pqrstsixteen").unwrap(), 76);
+    }
+
+    #[test]
+    fn custom_vocabulary() {
+        let scanner = DigitScanner::new([("zero", 0), ("eins", 1), ("zwei", 2), ("drei", 3)]);
+
+        assert_eq!(scanner.calibration_value("zweinsxdreizero").unwrap(), 20);
+        assert_eq!(scanner.calibration_value("einsz").unwrap(), 11);
+    }
+
+    #[test]
+    fn lines_without_digits_are_errors() {
+        assert!(DigitScanner::digits().calibration_value("one").is_err());
+        assert!(DigitScanner::english()
+            .calibrate(&["1abc2", "nothing"])
+            .is_err());
+    }
+}
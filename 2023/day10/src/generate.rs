@@ -0,0 +1,121 @@
+use aoc_core::Rng;
+
+/// A maze whose loop goes round a random region of `size` by `size` cells,
+/// with junk pipes everywhere else.
+///
+/// The region is a run of columns, each a single span of rows overlapping
+/// the span before it, so its outline never touches itself. Every cell
+/// becomes two by two tiles, with the outline on the even rows and columns
+/// between them.
+///
+/// Returns the maze with the answers it was built to have: how far the
+/// farthest pipe is, then how many tiles the loop encloses.
+pub(crate) fn maze(rng: &mut Rng, size: usize) -> (String, usize, usize) {
+    let size = size.max(1);
+
+    let mut spans: Vec<(usize, usize)> = Vec::with_capacity(size);
+    for _ in 0..size {
+        // Keep one row of the previous column, so the two share an edge.
+        let row = match spans.last() {
+            Some(&(top, bottom)) => rng.usize(top..bottom),
+            None => rng.usize(0..size),
+        };
+        spans.push((rng.usize(0..=row), rng.usize(row + 1..=size)));
+    }
+    let inside = |row: isize, column: isize| {
+        usize::try_from(column)
+            .ok()
+            .and_then(|column| spans.get(column))
+            .is_some_and(|&(top, bottom)| (top as isize..bottom as isize).contains(&row))
+    };
+
+    let tiles = 2 * size + 1;
+    let mut grid = (0..tiles)
+        .map(|_| {
+            (0..tiles)
+                .map(|_| rng.choice(*b"..|-LJ7F").unwrap())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut pipes = Vec::new();
+    for row in 0..=size as isize {
+        for column in 0..=size as isize {
+            let north = inside(row - 1, column - 1) != inside(row - 1, column);
+            let south = inside(row, column - 1) != inside(row, column);
+            let west = inside(row - 1, column - 1) != inside(row, column - 1);
+            let east = inside(row - 1, column) != inside(row, column);
+
+            let corner = match (north, south, west, east) {
+                (true, true, false, false) => b'|',
+                (false, false, true, true) => b'-',
+                (true, false, false, true) => b'L',
+                (true, false, true, false) => b'J',
+                (false, true, true, false) => b'7',
+                (false, true, false, true) => b'F',
+                _ => continue,
+            };
+            let (y, x) = (2 * row as usize, 2 * column as usize);
+            grid[y][x] = corner;
+            pipes.push((y, x));
+            if south {
+                grid[y + 1][x] = b'|';
+                pipes.push((y + 1, x));
+            }
+            if east {
+                grid[y][x + 1] = b'-';
+                pipes.push((y, x + 1));
+            }
+        }
+    }
+
+    // Part 2 counts the start as a pipe crossing its row, so it has to
+    // stand in for one that does.
+    let starts = pipes
+        .iter()
+        .filter(|&&(y, x)| matches!(grid[y][x], b'|' | b'7' | b'F'))
+        .collect::<Vec<_>>();
+    let &(y, x) = rng.choice(starts).unwrap();
+    grid[y][x] = b'S';
+    // Only the loop may lead into the start.
+    for (dy, dx) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+        let (Some(ny), Some(nx)) = (y.checked_add_signed(dy), x.checked_add_signed(dx)) else {
+            continue;
+        };
+        if ny < tiles && nx < tiles && !pipes.contains(&(ny, nx)) {
+            grid[ny][nx] = b'.';
+        }
+    }
+
+    let area = spans
+        .iter()
+        .map(|(top, bottom)| bottom - top)
+        .sum::<usize>();
+    let input = grid
+        .into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect();
+
+    let farthest = pipes.len() / 2;
+    // Pick's theorem, with each cell four tiles of area.
+    let enclosed = 4 * area + 1 - pipes.len() / 2;
+
+    (input, farthest, enclosed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mazes_have_the_answers_they_were_built_with() {
+        for seed in 0..20 {
+            let (input, farthest, enclosed) =
+                maze(&mut Rng::with_seed(seed), 1 + seed as usize % 8);
+            let input = &*input.leak();
+
+            assert_eq!(crate::part1(input).unwrap(), farthest.to_string());
+            assert_eq!(crate::part2(input).unwrap(), enclosed.to_string());
+        }
+    }
+}
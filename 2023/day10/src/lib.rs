@@ -1,5 +1,48 @@
+mod generate;
 mod part1;
 mod part2;
+mod sketch;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+pub use sketch::sketch;
+
+use anyhow::{Context, Result};
+use aoc_core::{Rng, Solution};
+use aoc_parse::parse_all_nom7;
+use pathfinding::matrix::Matrix;
+use std::fmt::Display;
+
+/// The sketch of the field both parts follow the loop through.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Matrix<char>> {
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all_nom7(sketch, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 10;
+
+    type Input = Matrix<char>;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate::maze(rng, size).0)
+    }
+}
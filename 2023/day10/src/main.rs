@@ -1,5 +1,6 @@
 use anyhow::Result;
-use day10::{part1, part2};
+use aoc_telemetry::{init_telemetry, Telemetry};
+use day10::{parse, solve_part1, solve_part2};
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
@@ -7,16 +8,18 @@ static ALLOC: dhat::Alloc = dhat::Alloc;
 
 #[tracing::instrument]
 fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
+    let _telemetry = init_telemetry(&Telemetry::default())?;
 
     #[cfg(feature = "dhat-heap")]
     let _profiler = dhat::Profiler::new_heap();
 
     let input = include_str!("../input.txt");
-    let result = part1(input)?;
+    let parsed = parse(input)?;
+
+    let result = solve_part1(&parsed)?;
     println!("Part 1: {result}");
 
-    let result = part2(input)?;
+    let result = solve_part2(&parsed)?;
     println!("Part 2: {result}");
 
     Ok(())
@@ -1,8 +1,11 @@
+use crate::parse;
 use anyhow::{Context, Result};
+use aoc_core::SolveError;
+use pathfinding::matrix::Matrix;
 use petgraph::{algo::dijkstra, prelude::*};
 use tracing::info;
 
-#[derive(Debug, Hash, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Hash, Copy, Clone, PartialEq, Eq)]
 enum Tile {
     NorthSouth,
     EastWest,
@@ -10,38 +13,34 @@ enum Tile {
     NorthWest,
     SouthWest,
     SouthEast,
+    #[default]
     Ground,
     Start,
 }
 
-impl Default for Tile {
-    fn default() -> Self {
-        Self::Ground
-    }
-}
-
-#[tracing::instrument(skip(input))]
-fn maze(input: &str) -> Vec<Tile> {
-    input
-        .lines()
-        .flat_map(|line| {
-            line.chars().map(|c| match c {
-                '|' => Tile::NorthSouth,
-                '-' => Tile::EastWest,
-                'L' => Tile::NorthEast,
-                'J' => Tile::NorthWest,
-                '7' => Tile::SouthWest,
-                'F' => Tile::SouthEast,
-                '.' => Tile::Ground,
-                'S' => Tile::Start,
-                _ => unreachable!(),
-            })
+#[tracing::instrument(skip(grid))]
+fn maze(grid: &Matrix<char>) -> Result<Vec<Tile>, SolveError> {
+    grid.values()
+        .map(|&c| match c {
+            '|' => Ok(Tile::NorthSouth),
+            '-' => Ok(Tile::EastWest),
+            'L' => Ok(Tile::NorthEast),
+            'J' => Ok(Tile::NorthWest),
+            '7' => Ok(Tile::SouthWest),
+            'F' => Ok(Tile::SouthEast),
+            '.' => Ok(Tile::Ground),
+            'S' => Ok(Tile::Start),
+            _ => Err(SolveError::malformed(format!("unknown tile {c:?}"))),
         })
-        .collect::<Vec<_>>()
+        .collect()
 }
 
+type Maze = (Graph<(), (), Directed>, (Tile, NodeIndex));
+
+/// Links each pipe to the tiles it opens onto, where `maze` holds rows of
+/// `length` tiles one after another.
 #[tracing::instrument(skip(maze))]
-fn build_graph(maze: &[Tile]) -> (Graph<(), (), Directed>, (Tile, NodeIndex)) {
+fn build_graph(maze: &[Tile], length: usize) -> Result<Maze, SolveError> {
     let mut graph: Graph<(), (), Directed> = Graph::new();
     let nodes = maze
         .iter()
@@ -54,63 +53,73 @@ fn build_graph(maze: &[Tile]) -> (Graph<(), (), Directed>, (Tile, NodeIndex)) {
     let starting_node = nodes
         .iter()
         .find(|(tile, _)| tile == &Tile::Start)
-        .expect("must have a starting node");
-
-    let length = (maze.len() as f32).sqrt() as usize;
+        .ok_or_else(|| SolveError::malformed("no start tile"))?;
 
     for (i, (tile, node)) in nodes.iter().enumerate() {
         match tile {
             Tile::NorthSouth => {
                 nodes
-                    .get(i + length).map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
+                    .get(i + length)
+                    .map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
                 if i >= length {
                     nodes
-                        .get(i - length).map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
+                        .get(i - length)
+                        .map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
                 }
             }
             Tile::EastWest => {
                 nodes
-                    .get(i + 1).map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
+                    .get(i + 1)
+                    .map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
                 if i >= 1 {
                     nodes
-                        .get(i - 1).map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
+                        .get(i - 1)
+                        .map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
                 }
             }
 
             Tile::NorthEast => {
                 if i >= length {
                     nodes
-                        .get(i - length).map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
+                        .get(i - length)
+                        .map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
                 }
                 nodes
-                    .get(i + 1).map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
+                    .get(i + 1)
+                    .map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
             }
 
             Tile::NorthWest => {
                 if i >= length {
                     nodes
-                        .get(i - length).map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
+                        .get(i - length)
+                        .map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
                 }
                 if i >= 1 {
                     nodes
-                        .get(i - 1).map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
+                        .get(i - 1)
+                        .map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
                 }
             }
 
             Tile::SouthWest => {
                 nodes
-                    .get(i + length).map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
+                    .get(i + length)
+                    .map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
                 if i >= 1 {
                     nodes
-                        .get(i - 1).map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
+                        .get(i - 1)
+                        .map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
                 }
             }
 
             Tile::SouthEast => {
                 nodes
-                    .get(i + 1).map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
+                    .get(i + 1)
+                    .map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
                 nodes
-                    .get(i + length).map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
+                    .get(i + length)
+                    .map(|neighbor| graph.add_edge(*node, neighbor.1, ()));
             }
 
             Tile::Start => {
@@ -164,26 +173,34 @@ fn build_graph(maze: &[Tile]) -> (Graph<(), (), Directed>, (Tile, NodeIndex)) {
         }
     }
 
-    (graph, *starting_node)
+    Ok((graph, *starting_node))
 }
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+fn process(grid: &Matrix<char>) -> Result<String, SolveError> {
     info!("processing input");
 
-    let maze = maze(input);
-    let (graph, start) = build_graph(&maze);
+    let maze = maze(grid)?;
+    let (graph, start) = build_graph(&maze, grid.columns)?;
     let res = dijkstra(&graph, start.1, None, |_| 1);
-    let max = res.values().max().unwrap();
+    let max = res
+        .values()
+        .max()
+        .ok_or_else(|| SolveError::no_solution("start tile is not in the graph"))?;
 
     Ok(max.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+pub fn solve_part1(grid: &Matrix<char>) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
+
+    process(grid).context("process part 1")
+}
 
-    process(input).context("process part 1")
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -198,7 +215,7 @@ mod tests {
 .L-J.
 .....
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "4");
     }
 
@@ -210,7 +227,7 @@ SJ.L7
 |F--J
 LJ...
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "8");
     }
 }
@@ -1,88 +1,45 @@
+use crate::parse;
 use anyhow::{Context, Result};
-use pathfinding::matrix::{directions, Matrix};
+use aoc_core::SolveError;
+use aoc_grid::Direction4;
+use pathfinding::matrix::Matrix;
 use tracing::info;
 
-#[tracing::instrument(skip(input))]
-fn points(input: &str) -> Vec<(usize, usize)> {
-    let grid = input
-        .lines()
-        .map(|line| line.chars())
-        .collect::<Matrix<char>>();
-
+#[tracing::instrument(skip(grid))]
+fn points(grid: &Matrix<char>) -> Result<Vec<(usize, usize)>, SolveError> {
     let start_coord = grid
         .items()
         .find(|(_, c)| *c == &'S')
         .map(|((row, col), _)| (row, col))
-        .expect("should have a start");
+        .ok_or_else(|| SolveError::malformed("no start tile"))?;
 
     info!(?start_coord);
 
     let mut points = vec![start_coord];
 
-    // find all valid directions from the S node
-    let valid_neighbors = directions::DIRECTIONS_4
+    // Pipes that lead back into the start tile from each side.
+    let connects = |direction: Direction4, c: char| match direction {
+        Direction4::North => matches!(c, '|' | 'F' | '7'),
+        Direction4::South => matches!(c, '|' | 'L' | 'J'),
+        Direction4::East => matches!(c, '-' | 'J' | '7'),
+        Direction4::West => matches!(c, '-' | 'L' | 'F'),
+    };
+
+    // Take the first and follow the loop from there
+    let mut travel_direction = Direction4::ALL
         .into_iter()
-        .filter_map(|direction| {
-            grid.move_in_direction((start_coord.0, start_coord.1), direction)
-                .filter(|neighbor_position| match direction {
-                    directions::N => {
-                        if let Some(c) = grid.get(*neighbor_position) {
-                            match *c {
-                                '|' | 'F' | '7' => true,
-                                _ => false,
-                            }
-                        } else {
-                            false
-                        }
-                    }
-                    directions::S => {
-                        if let Some(c) = grid.get(*neighbor_position) {
-                            match *c {
-                                '|' | 'L' | 'J' => true,
-                                _ => false,
-                            }
-                        } else {
-                            false
-                        }
-                    }
-                    directions::E => {
-                        if let Some(c) = grid.get(*neighbor_position) {
-                            match *c {
-                                '-' | 'J' | '7' => true,
-                                _ => false,
-                            }
-                        } else {
-                            false
-                        }
-                    }
-                    directions::W => {
-                        if let Some(c) = grid.get(*neighbor_position) {
-                            match *c {
-                                '-' | 'L' | 'F' => true,
-                                _ => false,
-                            }
-                        } else {
-                            false
-                        }
-                    }
-                    _ => false,
-                })
+        .find(|&direction| {
+            grid.move_in_direction(start_coord, direction.row_col())
+                .and_then(|neighbor| grid.get(neighbor))
+                .is_some_and(|&c| connects(direction, c))
         })
-        .collect::<Vec<_>>();
-
-    // Take the first and find a matching corner
-    let first_neighbor = valid_neighbors
-        .first()
-        .expect("should have a first element");
+        .ok_or_else(|| SolveError::no_solution("no pipe connects to the start tile"))?;
 
     let mut next_point = start_coord;
 
-    let mut travel_direction = (
-        first_neighbor.0 as isize - start_coord.0 as isize,
-        first_neighbor.1 as isize - start_coord.1 as isize,
-    );
+    while let Some(p) = grid.move_in_direction(next_point, travel_direction.row_col()) {
+        aoc_core::step()?;
 
-    while let Some(p) = grid.move_in_direction(next_point, travel_direction) {
         if let Some(c) = grid.get(p) {
             if *c == 'S' {
                 break;
@@ -91,54 +48,30 @@ fn points(input: &str) -> Vec<(usize, usize)> {
             next_point = p;
             points.push(p);
 
-            match travel_direction {
-                directions::E if *c == 'J' => {
-                    travel_direction = directions::N;
-                }
-                directions::E if *c == '7' => {
-                    travel_direction = directions::S;
-                }
-
-                directions::W if *c == 'L' => {
-                    travel_direction = directions::N;
-                }
-                directions::W if *c == 'F' => {
-                    travel_direction = directions::S;
-                }
-
-                directions::N if *c == 'F' => {
-                    travel_direction = directions::E;
-                }
-                directions::N if *c == '7' => {
-                    travel_direction = directions::W;
-                }
-
-                directions::S if *c == 'L' => {
-                    travel_direction = directions::E;
-                }
-                directions::S if *c == 'J' => {
-                    travel_direction = directions::W;
-                }
-
-                _ => {}
-            }
+            travel_direction = match (travel_direction, *c) {
+                (Direction4::East, 'J') | (Direction4::West, 'L') => Direction4::North,
+                (Direction4::East, '7') | (Direction4::West, 'F') => Direction4::South,
+                (Direction4::North, 'F') | (Direction4::South, 'L') => Direction4::East,
+                (Direction4::North, '7') | (Direction4::South, 'J') => Direction4::West,
+                (direction, _) => direction,
+            };
         }
     }
 
-    points
+    Ok(points)
 }
 
 #[allow(unused)]
-fn print_area(input: &str, points: &Vec<(usize, usize)>) {
-    let area = input
-        .lines()
+fn print_area(grid: &Matrix<char>, points: &[(usize, usize)]) {
+    let area = grid
+        .iter()
         .enumerate()
         .map(|(row, line)| {
             let mut inside = false;
 
-            line.chars()
+            line.iter()
                 .enumerate()
-                .map(|(col, c)| {
+                .map(|(col, &c)| {
                     if points.contains(&(row, col)) {
                         if ['S', '|', '7', 'F'].contains(&c) {
                             inside = !inside;
@@ -160,37 +93,31 @@ fn print_area(input: &str, points: &Vec<(usize, usize)>) {
     println!("{}", area.join("\n"));
 }
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+fn process(grid: &Matrix<char>) -> Result<String, SolveError> {
     info!("processing input");
 
-    let points = points(input);
+    let points = points(grid)?;
 
-    let filtered = input
-        .lines()
+    let filtered = grid
+        .iter()
         .enumerate()
         .map(|(y, line)| {
-            line.chars()
+            line.iter()
                 .enumerate()
-                .filter_map(|(x, c)| {
-                    if points.contains(&(y, x)) {
-                        Some(c)
-                    } else {
-                        Some('.')
-                    }
-                })
+                .map(|(x, &c)| if points.contains(&(y, x)) { c } else { '.' })
                 .collect::<Vec<_>>()
         })
         .collect::<Vec<_>>();
 
-    // print_area(input, &points);
+    // print_area(grid, &points);
 
     let area = filtered.iter().enumerate().fold(0, |acc, (row, line)| {
         let mut inside = false;
 
         acc + line.iter().enumerate().fold(0, |inner_acc, (col, c)| {
             if points.contains(&(row, col)) {
-                if ['S', '|', '7', 'F'].contains(&c) {
+                if ['S', '|', '7', 'F'].contains(c) {
                     inside = !inside;
                 }
 
@@ -211,11 +138,16 @@ fn process(input: &'static str) -> Result<String> {
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+pub fn solve_part2(grid: &Matrix<char>) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
+
+    process(grid).context("process part 2")
+}
 
-    process(input).context("process part 2")
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -234,7 +166,7 @@ mod tests {
 .L--J.L--J.
 ...........
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "4");
     }
 
@@ -251,7 +183,7 @@ L--J.L7...LJS7F-7L7.
 ....FJL-7.||.||||...
 ....L---J.LJ.LJLJ...
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "8");
     }
 
@@ -268,7 +200,7 @@ L---JF-JLJ.||-FJLJJ7
 L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "10");
     }
 }
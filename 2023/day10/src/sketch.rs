@@ -0,0 +1,31 @@
+use nom::{
+    character::complete::{line_ending, one_of},
+    combinator::map_res,
+    multi::{many1, separated_list1},
+    IResult,
+};
+use pathfinding::matrix::Matrix;
+
+/// The sketch of the field, one pipe or patch of ground per cell. Both
+/// parts step between neighbouring cells, so the rows must line up.
+#[tracing::instrument(skip(input))]
+pub fn sketch(input: &str) -> IResult<&str, Matrix<char>> {
+    map_res(
+        separated_list1(line_ending, many1(one_of("|-LJ7F.S"))),
+        Matrix::from_rows,
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_must_line_up() {
+        let (_, grid) = sketch(".S-7.\n.L-J.\n").unwrap();
+        assert_eq!((grid.rows, grid.columns), (2, 5));
+        assert_eq!(grid[(0, 1)], 'S');
+
+        assert!(sketch(".S-7.\n.L-J\n").is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use glam::IVec2;
+use nom::{
+    character::complete::{line_ending, one_of},
+    combinator::{map, verify},
+    multi::{many1, separated_list1},
+    IResult,
+};
+use std::fmt;
+
+/// The telescope image: where its galaxies are on a grid of `width` by
+/// `height` pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    /// Galaxy positions in reading order.
+    pub galaxies: Vec<IVec2>,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Image {
+    /// Rows without a galaxy, top to bottom.
+    pub fn empty_rows(&self) -> Vec<i32> {
+        (0..self.height)
+            .filter(|&y| self.galaxies.iter().all(|galaxy| galaxy.y != y))
+            .collect()
+    }
+
+    /// Columns without a galaxy, left to right.
+    pub fn empty_columns(&self) -> Vec<i32> {
+        (0..self.width)
+            .filter(|&x| self.galaxies.iter().all(|galaxy| galaxy.x != x))
+            .collect()
+    }
+}
+
+/// Written as the input grid, `#` for a galaxy and `.` for empty space.
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            let row = (0..self.width)
+                .map(|x| {
+                    if self.galaxies.contains(&IVec2::new(x, y)) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>();
+            writeln!(f, "{row}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(input))]
+pub fn image(input: &str) -> IResult<&str, Image> {
+    // Columns are read across every row, so they must all be as long.
+    let rectangular = |rows: &Vec<Vec<char>>| rows.iter().all(|row| row.len() == rows[0].len());
+
+    map(
+        verify(
+            separated_list1(line_ending, many1(one_of(".#"))),
+            rectangular,
+        ),
+        |rows| Image {
+            galaxies: rows
+                .iter()
+                .enumerate()
+                .flat_map(|(y, row)| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|(_, &pixel)| pixel == '#')
+                        .map(move |(x, _)| IVec2::new(x as i32, y as i32))
+                })
+                .collect(),
+            width: rows[0].len() as i32,
+            height: rows.len() as i32,
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use proptest::prelude::*;
+
+    fn any_image() -> impl Strategy<Value = Image> {
+        (1..20_i32, 1..20_i32)
+            .prop_flat_map(|(width, height)| {
+                let pixels = prop::collection::vec(any::<bool>(), (width * height) as usize);
+                (Just(width), Just(height), pixels)
+            })
+            .prop_map(|(width, height, pixels)| Image {
+                galaxies: pixels
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &galaxy)| galaxy)
+                    .map(|(i, _)| IVec2::new(i as i32 % width, i as i32 / width))
+                    .collect(),
+                width,
+                height,
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in any_image()) {
+            prop_assert_eq!(parse_all_nom7(image, &expected.to_string()), Ok(expected));
+        }
+
+        #[test]
+        fn crlf_round_trip(expected in any_image()) {
+            let input = expected.to_string().replace('\n', "\r\n");
+            prop_assert_eq!(parse_all_nom7(image, &input), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn empty_lines() {
+        let (_, image) = image("#..\n...\n..#\n").unwrap();
+
+        assert_eq!(image.galaxies, vec![IVec2::new(0, 0), IVec2::new(2, 2)]);
+        assert_eq!(image.empty_rows(), vec![1]);
+        assert_eq!(image.empty_columns(), vec![1]);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        assert!(image("#..\n.#\n").is_err());
+    }
+}
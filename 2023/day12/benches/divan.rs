@@ -1,4 +1,4 @@
-use day12::{part1, part2};
+use day12::{parse, part1, part2, solve_part1, solve_part2};
 
 fn main() {
     divan::main();
@@ -13,3 +13,20 @@ fn part1_benchmark() {
 fn part2_benchmark() {
     part2(divan::black_box(include_str!("../input.txt",))).unwrap();
 }
+
+#[divan::bench]
+fn parse_benchmark() {
+    parse(divan::black_box(include_str!("../input.txt",))).unwrap();
+}
+
+#[divan::bench]
+fn solve_part1_benchmark(bencher: divan::Bencher) {
+    let parsed = parse(include_str!("../input.txt")).unwrap();
+    bencher.bench(|| solve_part1(divan::black_box(&parsed)).unwrap());
+}
+
+#[divan::bench]
+fn solve_part2_benchmark(bencher: divan::Bencher) {
+    let parsed = parse(include_str!("../input.txt")).unwrap();
+    bencher.bench(|| solve_part2(divan::black_box(&parsed)).unwrap());
+}
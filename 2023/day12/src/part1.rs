@@ -1,34 +1,14 @@
+use crate::{parse, Condition, Row};
 use anyhow::{Context, Result};
+use aoc_core::SolveError;
 use itertools::{repeat_n, Itertools};
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending, one_of, space1},
-    combinator::map,
-    multi::{many1, separated_list1},
-    sequence::separated_pair,
-    IResult,
-};
-use rayon::prelude::*;
 use tracing::info;
 
-#[derive(Debug, PartialEq, Clone)]
-enum Condition {
-    Operational,
-    Damaged,
-    Unknown,
-}
-
-#[derive(Debug)]
-struct Row {
-    conditions: Vec<Condition>,
-    groups: Vec<u32>,
-}
-
 impl Row {
     fn permute(&self) -> impl Iterator<Item = Vec<Condition>> {
         let unknowns = self
             .conditions
-            .par_iter()
+            .iter()
             .filter(|condition| *condition == &Condition::Unknown)
             .count();
         repeat_n([Condition::Operational, Condition::Damaged], unknowns).multi_cartesian_product()
@@ -36,13 +16,21 @@ impl Row {
 
     fn is_valid(&self, permutation: &[Condition]) -> bool {
         let mut it = permutation.iter();
-
-        self.conditions
+        let Some(conditions) = self
+            .conditions
             .iter()
             .map(|condition| match condition {
-                Condition::Unknown => it.next().expect("should have a valid permutation"),
-                c => c,
+                Condition::Unknown => it.next(),
+                c => Some(c),
             })
+            .collect::<Option<Vec<_>>>()
+        else {
+            // Too few conditions to fill in every unknown.
+            return false;
+        };
+
+        conditions
+            .into_iter()
             .group_by(|condition| *condition == &Condition::Damaged)
             .into_iter()
             .filter_map(|(is_damaged, group)| is_damaged.then_some(group.count() as u32))
@@ -50,57 +38,33 @@ impl Row {
     }
 }
 
-#[tracing::instrument(skip(input))]
-fn conditions(input: &str) -> IResult<&str, Vec<Condition>> {
-    many1(map(one_of("?.#"), |c| match c {
-        '?' => Condition::Unknown,
-        '.' => Condition::Operational,
-        '#' => Condition::Damaged,
-        _ => unreachable!(),
-    }))(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn groups(input: &str) -> IResult<&str, Vec<u32>> {
-    separated_list1(tag(","), complete::u32)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn row(input: &str) -> IResult<&str, Row> {
-    map(
-        separated_pair(conditions, space1, groups),
-        |(conditions, groups)| Row { conditions, groups },
-    )(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn records(input: &str) -> IResult<&str, Vec<Row>> {
-    separated_list1(line_ending, row)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(records))]
+fn process(records: &[Row]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, records) = records(input)?;
-
-    let result = records
-        .par_iter()
-        .map(|row| {
-            row.permute()
-                .filter(|permutation| row.is_valid(permutation))
-                .count()
+    // Every row tries all 2^unknowns ways to fill in its unknowns, so a
+    // long enough row never finishes. The rows stay on this thread, where
+    // each try counts against the step budget.
+    let result = records.iter().try_fold(0_usize, |total, row| {
+        row.permute().try_fold(total, |total, permutation| {
+            aoc_core::step()?;
+            Ok::<_, SolveError>(total + usize::from(row.is_valid(&permutation)))
         })
-        .sum::<usize>();
+    })?;
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(records))]
+pub fn solve_part1(records: &[Row]) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
 
-    process(input).context("process part 1")
+    process(records).context("process part 1")
+}
+
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -110,7 +74,7 @@ mod tests {
     #[test_log::test]
     fn one_arrangement() {
         let input = "???.### 1,1,3\n";
-        let result = process(input);
+        let result = process(&parse(input).unwrap());
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "1");
     }
@@ -118,7 +82,7 @@ mod tests {
     #[test_log::test]
     fn four_arrangements() {
         let input = ".??..??...?##. 1,1,3\n";
-        let result = process(input);
+        let result = process(&parse(input).unwrap());
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "4");
     }
@@ -126,11 +90,18 @@ mod tests {
     #[test_log::test]
     fn ten_arrangements() {
         let input = "?###???????? 3,2,1\n";
-        let result = process(input);
+        let result = process(&parse(input).unwrap());
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "10");
     }
 
+    #[test_log::test]
+    fn long_rows_run_out_of_steps() {
+        let input = format!("{} 1\n", "?".repeat(40)).leak();
+        let result = aoc_core::with_step_budget(1_000, || process(&parse(input).unwrap()));
+        assert_eq!(result, Err(SolveError::OutOfSteps));
+    }
+
     #[test_log::test]
     fn twenty_one_arrangements() {
         let input = r##"???.### 1,1,3
@@ -140,7 +111,7 @@ mod tests {
 ????.######..#####. 1,6,5
 ?###???????? 3,2,1
 "##;
-        let result = process(input);
+        let result = process(&parse(input).unwrap());
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "21");
     }
@@ -1,33 +1,15 @@
+use crate::{parse, Condition, Row};
 use anyhow::{Context, Result};
-use itertools::{repeat_n, Itertools};
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending, one_of, space1},
-    combinator::map,
-    multi::{many1, separated_list1},
-    sequence::separated_pair,
-    IResult,
-};
+use aoc_core::SolveError;
+use itertools::repeat_n;
 use tracing::info;
 
-#[derive(Debug, PartialEq, Clone)]
-enum Condition {
-    Operational,
-    Damaged,
-    Unknown,
-}
-
-#[derive(Debug)]
-struct Row {
-    conditions: Vec<Condition>,
-    groups: Vec<u32>,
-}
-
 impl Row {
+    /// Five copies of the row, with an unknown condition between each.
     fn unfold(&self) -> Row {
         let conditions = repeat_n(self.conditions.clone(), 5)
-            .flatten()
-            .collect::<Vec<_>>();
+            .collect::<Vec<_>>()
+            .join(&Condition::Unknown);
 
         let groups = repeat_n(self.groups.clone(), 5)
             .flatten()
@@ -36,154 +18,170 @@ impl Row {
         Row { conditions, groups }
     }
 
-    fn permute(&self) -> Vec<Vec<Condition>> {
-        let unknowns = self
-            .conditions
-            .iter()
-            .filter(|condition| *condition == &Condition::Unknown)
-            .count();
-        repeat_n([Condition::Operational, Condition::Damaged], unknowns)
-            .multi_cartesian_product()
-            .collect::<Vec<_>>()
+    /// How many ways the unknown conditions can be filled in to match the
+    /// groups, counted from the end of the row backwards rather than by
+    /// trying every way.
+    fn arrangements(&self) -> u64 {
+        let (conditions, groups) = (self.conditions.len(), self.groups.len());
+
+        // `ways[i][g]` is how many ways `conditions[i..]` match `groups[g..]`.
+        let mut ways = vec![vec![0_u64; groups + 1]; conditions + 1];
+        ways[conditions][groups] = 1;
+
+        for i in (0..conditions).rev() {
+            for g in 0..=groups {
+                let mut count = 0;
+
+                if self.conditions[i] != Condition::Damaged {
+                    count += ways[i + 1][g];
+                }
+
+                // Start the next group here, if it fits before an
+                // operational spring or the end of the row.
+                if self.conditions[i] != Condition::Operational && g < groups {
+                    let end = i + self.groups[g] as usize;
+                    let fits = end > i
+                        && end <= conditions
+                        && !self.conditions[i..end].contains(&Condition::Operational)
+                        && self.conditions.get(end) != Some(&Condition::Damaged);
+
+                    if fits {
+                        count += ways[(end + 1).min(conditions)][g + 1];
+                    }
+                }
+
+                ways[i][g] = count;
+            }
+        }
+
+        ways[0][0]
     }
-
-    fn is_valid(&self, permutation: &Vec<Condition>) -> bool {
-        let mut it = permutation.iter();
-        let groups = self
-            .conditions
-            .iter()
-            .map(|condition| match condition {
-                Condition::Unknown => it.next().expect("should have a valid permutation"),
-                c => c,
-            })
-            .group_by(|condition| *condition == &Condition::Damaged)
-            .into_iter()
-            .filter_map(|(is_damaged, group)| {
-                is_damaged.then_some(group.into_iter().count() as u32)
-            })
-            .collect::<Vec<u32>>();
-
-        &self.groups[..] == &groups[..]
-    }
-}
-
-#[tracing::instrument(skip(input))]
-fn conditions(input: &str) -> IResult<&str, Vec<Condition>> {
-    many1(map(one_of("?.#"), |c| match c {
-        '?' => Condition::Unknown,
-        '.' => Condition::Operational,
-        '#' => Condition::Damaged,
-        _ => unreachable!(),
-    }))(input)
 }
 
-#[tracing::instrument(skip(input))]
-fn groups(input: &str) -> IResult<&str, Vec<u32>> {
-    separated_list1(tag(","), complete::u32)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn row(input: &str) -> IResult<&str, Row> {
-    map(
-        separated_pair(conditions, space1, groups),
-        |(conditions, groups)| Row { conditions, groups },
-    )(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn records(input: &str) -> IResult<&str, Vec<Row>> {
-    separated_list1(line_ending, row)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(records))]
+fn process(records: &[Row]) -> Result<String, SolveError> {
     info!("processing input");
 
-    // let (_, records) = records(input)?;
+    let result = records
+        .iter()
+        .map(|row| row.unfold().arrangements())
+        .sum::<u64>();
 
-    // info!(?records);
+    Ok(result.to_string())
+}
 
-    // let result = records
-    //     .iter()
-    //     .map(|row| {
-    //         row.unfold()
-    //             .permute()
-    //             .iter()
-    //             .filter(|permutation| row.is_valid(permutation))
-    //             .count()
-    //     })
-    //     .sum::<usize>();
+#[tracing::instrument(skip(records))]
+pub fn solve_part2(records: &[Row]) -> Result<String> {
+    info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
 
-    let result = 0;
-    Ok(result.to_string())
+    process(records).context("process part 2")
 }
 
-#[tracing::instrument(skip(input))]
 pub fn part2(input: &'static str) -> Result<String> {
-    info!("part s");
-
-    process(input).context("process part 2")
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    /// Tries every way of filling in the unknowns, which is slow but hard
+    /// to get wrong.
+    fn brute_force(row: &Row) -> u64 {
+        let unknowns = row
+            .conditions
+            .iter()
+            .positions(|condition| *condition == Condition::Unknown)
+            .collect::<Vec<_>>();
+
+        (0..1_u32 << unknowns.len())
+            .filter(|damaged| {
+                let mut filled = row.conditions.clone();
+                for (bit, &i) in unknowns.iter().enumerate() {
+                    filled[i] = if damaged >> bit & 1 == 1 {
+                        Condition::Damaged
+                    } else {
+                        Condition::Operational
+                    };
+                }
+
+                let groups = filled
+                    .split(|condition| *condition != Condition::Damaged)
+                    .filter(|group| !group.is_empty())
+                    .map(|group| group.len() as u32)
+                    .collect::<Vec<_>>();
+                groups == row.groups
+            })
+            .count() as u64
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let condition = prop_oneof![
+            Just(Condition::Operational),
+            Just(Condition::Damaged),
+            Just(Condition::Unknown),
+        ];
+        let rows = (
+            prop::collection::vec(condition, 1..=12),
+            prop::collection::vec(0..5_u32, 1..=4),
+        )
+            .prop_map(|(conditions, groups)| Row { conditions, groups });
+
+        aoc_differential::assert_agree(rows, brute_force, Row::arrangements);
+    }
 
-    #[ignore]
     #[test_log::test]
     fn line1() {
         let input = "???.### 1,1,3\n";
-        let result = process(input);
+        let result = process(&parse(input).unwrap());
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "1");
     }
 
-    #[ignore]
     #[test_log::test]
     fn line2() {
         let input = ".??..??...?##. 1,1,3\n";
-        let result = process(input);
+        let result = process(&parse(input).unwrap());
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "16384");
     }
 
-    #[ignore]
     #[test_log::test]
     fn line3() {
         let input = "?#?#?#?#?#?#?#? 1,3,1,6\n";
-        let result = process(input);
+        let result = process(&parse(input).unwrap());
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "1");
     }
 
-    #[ignore]
     #[test_log::test]
     fn line4() {
         let input = "????.#...#... 4,1,1\n";
-        let result = process(input);
+        let result = process(&parse(input).unwrap());
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "16");
     }
 
-    #[ignore]
     #[test_log::test]
     fn line5() {
         let input = "????.######..#####. 1,6,5\n";
-        let result = process(input);
+        let result = process(&parse(input).unwrap());
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "2500");
     }
 
-    #[ignore]
     #[test_log::test]
     fn line6() {
         let input = "?###???????? 3,2,1\n";
-        let result = process(input);
+        let result = process(&parse(input).unwrap());
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "506250");
     }
 
-    #[ignore]
     #[test_log::test]
     fn all_lines() {
         let input = r##"???.### 1,1,3
@@ -193,7 +191,7 @@ mod tests {
 ????.######..#####. 1,6,5
 ?###???????? 3,2,1
 "##;
-        let result = process(input);
+        let result = process(&parse(input).unwrap());
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "525152");
     }
@@ -0,0 +1,126 @@
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, line_ending, one_of, space1},
+    combinator::map,
+    multi::{many1, separated_list1},
+    sequence::separated_pair,
+    IResult,
+};
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Condition {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let condition = match self {
+            Condition::Unknown => '?',
+            Condition::Operational => '.',
+            Condition::Damaged => '#',
+        };
+
+        write!(f, "{condition}")
+    }
+}
+
+/// A row of springs and the sizes of its damaged groups, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub conditions: Vec<Condition>,
+    pub groups: Vec<u32>,
+}
+
+/// Written as a line of the input, like `???.### 1,1,3`.
+impl fmt::Display for Row {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.conditions.iter().join(""),
+            self.groups.iter().join(",")
+        )
+    }
+}
+
+#[tracing::instrument(skip(input))]
+fn conditions(input: &str) -> IResult<&str, Vec<Condition>> {
+    many1(map(one_of("?.#"), |c| match c {
+        '?' => Condition::Unknown,
+        '.' => Condition::Operational,
+        '#' => Condition::Damaged,
+        _ => unreachable!(),
+    }))(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn groups(input: &str) -> IResult<&str, Vec<u32>> {
+    separated_list1(tag(","), complete::u32)(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn row(input: &str) -> IResult<&str, Row> {
+    map(
+        separated_pair(conditions, space1, groups),
+        |(conditions, groups)| Row { conditions, groups },
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn records(input: &str) -> IResult<&str, Vec<Row>> {
+    separated_list1(line_ending, row)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use proptest::prelude::*;
+
+    fn any_row() -> impl Strategy<Value = Row> {
+        let condition = prop_oneof![
+            Just(Condition::Operational),
+            Just(Condition::Damaged),
+            Just(Condition::Unknown),
+        ];
+
+        (
+            prop::collection::vec(condition, 1..30),
+            prop::collection::vec(1..10_u32, 1..8),
+        )
+            .prop_map(|(conditions, groups)| Row { conditions, groups })
+    }
+
+    /// A row written with any run of spaces and tabs before its groups and
+    /// either line ending, paired with the row it reads as.
+    fn any_spaced_row() -> impl Strategy<Value = (Row, String)> {
+        (
+            any_row(),
+            "[ \t]{1,3}",
+            prop_oneof![Just("\n"), Just("\r\n")],
+        )
+            .prop_map(|(row, gap, end)| {
+                let line = row.to_string().replacen(' ', &gap, 1) + end;
+                (row, line)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in prop::collection::vec(any_row(), 1..20)) {
+            let input = expected.iter().map(|row| format!("{row}\n")).collect::<String>();
+            prop_assert_eq!(parse_all_nom7(records, &input), Ok(expected));
+        }
+
+        #[test]
+        fn spaced_round_trip(rows in prop::collection::vec(any_spaced_row(), 1..20)) {
+            let input = rows.iter().map(|(_, line)| line.as_str()).collect::<String>();
+            let expected = rows.into_iter().map(|(row, _)| row).collect::<Vec<_>>();
+            prop_assert_eq!(parse_all_nom7(records, &input), Ok(expected));
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use crate::{parse, Pattern};
 use anyhow::{Context, Result};
+use aoc_core::SolveError;
 use rayon::prelude::*;
 use tracing::info;
 
@@ -34,42 +36,32 @@ fn find_reflection_line(pattern: Vec<Vec<char>>) -> usize {
 }
 
 #[tracing::instrument(skip(pattern))]
-fn reflection(pattern: &str) -> usize {
-    let lines = pattern
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let vertical = find_reflection_line(transpose(lines.clone()));
-    let horizontal = find_reflection_line(lines);
+fn reflection(pattern: &Pattern) -> usize {
+    let vertical = find_reflection_line(transpose(pattern.clone()));
+    let horizontal = find_reflection_line(pattern.clone());
 
     horizontal * 100 + vertical
 }
 
-#[tracing::instrument(skip(input))]
-fn patterns(input: &str) -> Vec<&str> {
-    input.split("\n\n").collect::<Vec<_>>()
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(patterns))]
+fn process(patterns: &[Pattern]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let patterns = patterns(input);
-
-    let result = patterns
-        .par_iter()
-        .map(|pattern| reflection(pattern))
-        .sum::<usize>();
+    let result = patterns.par_iter().map(reflection).sum::<usize>();
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(patterns))]
+pub fn solve_part1(patterns: &[Pattern]) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
+
+    process(patterns).context("process part 1")
+}
 
-    process(input).context("process part 1")
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -94,7 +86,7 @@ mod tests {
 ..##..###
 #....#..#
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "405");
     }
 }
@@ -1,4 +1,6 @@
+use crate::{parse, Pattern};
 use anyhow::{Context, Result};
+use aoc_core::SolveError;
 use rayon::prelude::*;
 use tracing::info;
 
@@ -40,41 +42,31 @@ fn find_reflection_line(pattern: Vec<Vec<char>>) -> usize {
 }
 
 #[tracing::instrument(skip(pattern))]
-fn reflection(pattern: &str) -> usize {
-    let lines = pattern
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let vertical = find_reflection_line(transpose(lines.clone()));
-    let horizontal = find_reflection_line(lines) * 100;
+fn reflection(pattern: &Pattern) -> usize {
+    let vertical = find_reflection_line(transpose(pattern.clone()));
+    let horizontal = find_reflection_line(pattern.clone()) * 100;
     horizontal + vertical
 }
 
-#[tracing::instrument(skip(input))]
-fn patterns(input: &str) -> Vec<&str> {
-    input.split("\n\n").collect::<Vec<_>>()
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(patterns))]
+fn process(patterns: &[Pattern]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let patterns = patterns(input);
-
-    let result = patterns
-        .par_iter()
-        .map(|pattern| reflection(pattern))
-        .sum::<usize>();
+    let result = patterns.par_iter().map(reflection).sum::<usize>();
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(patterns))]
+pub fn solve_part2(patterns: &[Pattern]) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
 
-    process(input).context("process part 2")
+    process(patterns).context("process part 2")
+}
+
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -99,7 +91,7 @@ mod tests {
 ..##..###
 #....#..#
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "400");
     }
 }
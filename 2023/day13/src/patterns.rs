@@ -0,0 +1,44 @@
+use nom::{
+    character::complete::{line_ending, one_of},
+    combinator::verify,
+    multi::{many1, separated_list1},
+    sequence::pair,
+    IResult,
+};
+
+/// A pattern of ash and rocks, row by row.
+pub type Pattern = Vec<Vec<char>>;
+
+#[tracing::instrument(skip(input))]
+fn pattern(input: &str) -> IResult<&str, Pattern> {
+    // Reflections are checked column by column too, so rows line up.
+    let rectangular = |rows: &Pattern| rows.iter().all(|row| row.len() == rows[0].len());
+
+    verify(
+        separated_list1(line_ending, many1(one_of(".#"))),
+        rectangular,
+    )(input)
+}
+
+/// The patterns, separated by blank lines.
+#[tracing::instrument(skip(input))]
+pub fn patterns(input: &str) -> IResult<&str, Vec<Pattern>> {
+    separated_list1(pair(line_ending, line_ending), pattern)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_lines_split_patterns() {
+        let (rest, parsed) = patterns("#.\n.#\n\n##\n").unwrap();
+
+        assert_eq!(rest, "\n");
+        assert_eq!(
+            parsed,
+            vec![vec![vec!['#', '.'], vec!['.', '#']], vec![vec!['#', '#']]]
+        );
+        assert!(patterns("#.\n#\n").is_err());
+    }
+}
@@ -1,66 +1,11 @@
+use crate::{parse, Reflector, Rock};
 use anyhow::{Context, Result};
-use nom::{
-    character::complete::{line_ending, one_of},
-    combinator::map,
-    multi::{many1, separated_list1},
-    IResult,
-};
-use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use aoc_core::SolveError;
+use aoc_grid::Direction4;
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator};
 use tracing::info;
 
-#[derive(Debug, Hash, PartialEq, Clone)]
-enum Rock {
-    Round,
-    Cube,
-    Empty,
-}
-
-#[derive(Debug, Hash, PartialEq, Clone)]
-struct Reflector {
-    rocks: Vec<Vec<Rock>>,
-}
-
-#[derive(Debug)]
-enum Direction {
-    North,
-    West,
-    South,
-    East,
-}
-
 impl Reflector {
-    fn tilt_north(mut self) -> Self {
-        let mut rocks_move = true;
-
-        let rows = self.rocks.len();
-        let columns = self.rocks[0].len();
-
-        while rocks_move {
-            // Always stop moving unless we know rocks will move.
-            rocks_move = false;
-
-            for i in 1..rows {
-                for j in 0..columns {
-                    let rock = &self.rocks[i][j];
-
-                    if rock != &Rock::Round {
-                        continue;
-                    }
-
-                    // If the rock above us is an empty space, move this rock there
-                    if &self.rocks[i - 1][j] == &Rock::Empty {
-                        rocks_move = true;
-
-                        self.rocks[i - 1][j] = Rock::Round;
-                        self.rocks[i][j] = Rock::Empty;
-                    }
-                }
-            }
-        }
-
-        Reflector { rocks: self.rocks }
-    }
-
     fn tilt_west(mut self) -> Self {
         let mut rocks_move = true;
 
@@ -79,7 +24,7 @@ impl Reflector {
                         continue;
                     }
 
-                    if &self.rocks[i][j - 1] == &Rock::Empty {
+                    if self.rocks[i][j - 1] == Rock::Empty {
                         rocks_move = true;
 
                         self.rocks[i][j - 1] = Rock::Round;
@@ -110,7 +55,7 @@ impl Reflector {
                         continue;
                     }
 
-                    if &self.rocks[i + 1][j] == &Rock::Empty {
+                    if self.rocks[i + 1][j] == Rock::Empty {
                         rocks_move = true;
 
                         self.rocks[i + 1][j] = Rock::Round;
@@ -141,7 +86,7 @@ impl Reflector {
                         continue;
                     }
 
-                    if &self.rocks[i][j + 1] == &Rock::Empty {
+                    if self.rocks[i][j + 1] == Rock::Empty {
                         rocks_move = true;
 
                         self.rocks[i][j + 1] = Rock::Round;
@@ -154,83 +99,67 @@ impl Reflector {
         Reflector { rocks: self.rocks }
     }
 
-    fn tilt(self, dir: Direction) -> Self {
+    fn tilt(self, dir: Direction4) -> Self {
         match dir {
-            Direction::North => self.tilt_north(),
-            Direction::West => self.tilt_west(),
-            Direction::South => self.tilt_south(),
-            Direction::East => self.tilt_east(),
+            Direction4::North => self.tilt_north(),
+            Direction4::West => self.tilt_west(),
+            Direction4::South => self.tilt_south(),
+            Direction4::East => self.tilt_east(),
         }
     }
-
-    fn compute_load(self) -> usize {
-        self.rocks
-            .par_iter()
-            .rev()
-            .enumerate()
-            .map(|(i, row)| (i + 1) * row.iter().filter(|rock| *rock == &Rock::Round).count())
-            .sum::<usize>()
-    }
-}
-
-#[tracing::instrument(skip(input))]
-fn rock(input: &str) -> IResult<&str, Rock> {
-    map(one_of("O#."), |c| match c {
-        'O' => Rock::Round,
-        '#' => Rock::Cube,
-        '.' => Rock::Empty,
-        _ => unreachable!(),
-    })(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn rocks(input: &str) -> IResult<&str, Vec<Rock>> {
-    many1(rock)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn reflector(input: &str) -> IResult<&str, Reflector> {
-    map(separated_list1(line_ending, rocks), |rocks| Reflector {
-        rocks,
-    })(input)
 }
 
 fn cycle(reflector: Reflector) -> Reflector {
     reflector
-        .tilt(Direction::North)
-        .tilt(Direction::West)
-        .tilt(Direction::South)
-        .tilt(Direction::East)
+        .tilt(Direction4::North)
+        .tilt(Direction4::West)
+        .tilt(Direction4::South)
+        .tilt(Direction4::East)
 }
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(reflector))]
+fn process(reflector: &Reflector) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, mut reflector) = reflector(input)?;
+    let mut reflector = reflector.clone();
 
     let mut history = vec![];
 
-    let Some(cycle_start) = (0..1_000_000_000).find_map(|_| {
-        history.push(reflector.clone());
-        reflector = cycle(reflector.clone());
-        history.par_iter().position_any(|prev| *prev == reflector)
-    }) else {
+    let Some(cycle_start) = (0..1_000_000_000)
+        .find_map(|_| {
+            if let Err(e) = aoc_core::step() {
+                return Some(Err(e));
+            }
+
+            history.push(reflector.clone());
+            reflector = cycle(reflector.clone());
+            history
+                .par_iter()
+                .position_any(|prev| *prev == reflector)
+                .map(Ok)
+        })
+        .transpose()?
+    else {
         return Ok(reflector.compute_load().to_string());
     };
 
     let cycle_length = history.len() - cycle_start;
     let solution_index = cycle_start + ((1_000_000_000 - cycle_start) % cycle_length);
-    let result = history[solution_index].clone().compute_load();
+    let result = history[solution_index].compute_load();
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(reflector))]
+pub fn solve_part2(reflector: &Reflector) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
 
-    process(input).context("process part 2")
+    process(reflector).context("process part 2")
+}
+
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -250,7 +179,7 @@ O.#..O.#.#
 #....###..
 #OO..#....
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "64");
     }
 }
@@ -0,0 +1,141 @@
+use nom::{
+    character::complete::{line_ending, one_of},
+    combinator::{map, verify},
+    multi::{many1, separated_list1},
+    IResult,
+};
+use std::fmt;
+
+#[derive(Debug, Hash, PartialEq, Clone)]
+pub enum Rock {
+    Round,
+    Cube,
+    Empty,
+}
+
+/// The platform, row by row from north to south.
+#[derive(Debug, Hash, PartialEq, Clone)]
+pub struct Reflector {
+    pub rocks: Vec<Vec<Rock>>,
+}
+
+impl Reflector {
+    /// Rolls every round rock as far north as it will go.
+    pub fn tilt_north(mut self) -> Self {
+        let mut rocks_move = true;
+
+        let rows = self.rocks.len();
+        let columns = self.rocks[0].len();
+
+        while rocks_move {
+            // Always stop moving unless we know rocks will move.
+            rocks_move = false;
+
+            for i in 1..rows {
+                for j in 0..columns {
+                    let rock = &self.rocks[i][j];
+
+                    if rock != &Rock::Round {
+                        continue;
+                    }
+
+                    // If the rock above us is an empty space, move this rock there
+                    if self.rocks[i - 1][j] == Rock::Empty {
+                        rocks_move = true;
+
+                        self.rocks[i - 1][j] = Rock::Round;
+                        self.rocks[i][j] = Rock::Empty;
+                    }
+                }
+            }
+        }
+
+        Reflector { rocks: self.rocks }
+    }
+
+    /// The load on the north support beams.
+    pub fn compute_load(&self) -> usize {
+        self.rocks
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, row)| (i + 1) * row.iter().filter(|rock| *rock == &Rock::Round).count())
+            .sum::<usize>()
+    }
+}
+
+/// Drawn the way the input does, one row per line.
+impl fmt::Display for Reflector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.rocks {
+            for rock in row {
+                let rock = match rock {
+                    Rock::Round => 'O',
+                    Rock::Cube => '#',
+                    Rock::Empty => '.',
+                };
+                write!(f, "{rock}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(input))]
+fn rock(input: &str) -> IResult<&str, Rock> {
+    map(one_of("O#."), |c| match c {
+        'O' => Rock::Round,
+        '#' => Rock::Cube,
+        '.' => Rock::Empty,
+        _ => unreachable!(),
+    })(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn rocks(input: &str) -> IResult<&str, Vec<Rock>> {
+    many1(rock)(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn reflector(input: &str) -> IResult<&str, Reflector> {
+    // Tilting walks every row as far as the first one goes.
+    let rectangular = |rows: &Vec<Vec<Rock>>| rows.iter().all(|row| row.len() == rows[0].len());
+
+    map(
+        verify(separated_list1(line_ending, rocks), rectangular),
+        |rocks| Reflector { rocks },
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use proptest::prelude::*;
+
+    fn any_reflector() -> impl Strategy<Value = Reflector> {
+        let rock = prop_oneof![Just(Rock::Round), Just(Rock::Cube), Just(Rock::Empty)];
+
+        (1..20_usize, 1..20_usize)
+            .prop_flat_map(move |(rows, columns)| {
+                prop::collection::vec(prop::collection::vec(rock.clone(), columns), rows)
+            })
+            .prop_map(|rocks| Reflector { rocks })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in any_reflector()) {
+            let input = expected.to_string();
+            prop_assert_eq!(parse_all_nom7(reflector, &input), Ok(expected));
+        }
+
+        #[test]
+        fn crlf_round_trip(expected in any_reflector()) {
+            let input = expected.to_string().replace('\n', "\r\n");
+            prop_assert_eq!(parse_all_nom7(reflector, &input), Ok(expected));
+        }
+    }
+}
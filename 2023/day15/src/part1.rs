@@ -1,27 +1,30 @@
+use crate::{hash, parse, Instruction};
 use anyhow::{Context, Result};
+use aoc_core::SolveError;
 use tracing::info;
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(instructions))]
+fn process(instructions: &[Instruction]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let result = input
-        .split(',')
-        .map(|inst| {
-            inst.chars()
-                .filter(|c| c != &'\n')
-                .fold(0, |acc, c| ((acc + c as usize) * 17) % 256)
-        })
+    let result = instructions
+        .iter()
+        .map(|instruction| hash(instruction.step) as usize)
         .sum::<usize>();
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(instructions))]
+pub fn solve_part1(instructions: &[Instruction]) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
+
+    process(instructions).context("process part 1")
+}
 
-    process(input).context("process part 1")
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -31,14 +34,14 @@ mod tests {
     #[test_log::test]
     fn test_rn1() {
         let input = r##"rn=1"##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "30");
     }
 
     #[test_log::test]
     fn it_works() {
         let input = r##"rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7"##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "1320");
     }
 }
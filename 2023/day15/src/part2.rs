@@ -1,78 +1,19 @@
+use crate::{parse, Instruction, Operation};
 use anyhow::{Context, Result};
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, alpha1},
-    combinator::map,
-    multi::separated_list1,
-    sequence::pair,
-    IResult,
-};
+use aoc_core::SolveError;
 use std::collections::BTreeMap;
 use tracing::info;
 
-#[derive(Debug)]
-enum Operation {
-    Pop,
-    Push(u32),
-}
-
-#[derive(Debug)]
-struct Instruction<'a> {
-    label: &'a str,
-    hash: u8,
-    operation: Operation,
-}
-
 #[derive(Debug, PartialEq, Clone)]
 struct Lens<'a> {
     label: &'a str,
     focal_length: u32,
 }
 
-#[tracing::instrument(skip(input))]
-fn label(input: &str) -> IResult<&str, &str> {
-    alpha1(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn operation(input: &str) -> IResult<&str, Operation> {
-    alt((
-        map(tag("-"), |_| Operation::Pop),
-        map(pair(tag("="), complete::u32), |(_, value)| {
-            Operation::Push(value)
-        }),
-    ))(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn instruction(input: &str) -> IResult<&str, Instruction> {
-    map(pair(label, operation), |(label, operation)| {
-        let hash: u8 = label
-            .chars()
-            .fold(0, |acc, c| ((acc + c as usize) * 17) % 256)
-            .try_into()
-            .expect("should fit into a u8");
-
-        Instruction {
-            label,
-            hash,
-            operation,
-        }
-    })(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
-    separated_list1(tag(","), instruction)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(instructions))]
+fn process(instructions: &[Instruction]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, instructions) = instructions(input)?;
-
     let boxes = instructions.iter().fold(
         BTreeMap::<u8, Vec<Lens>>::new(),
         |mut boxes, instruction| {
@@ -127,11 +68,16 @@ fn process(input: &'static str) -> Result<String> {
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(instructions))]
+pub fn solve_part2(instructions: &[Instruction]) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
+
+    process(instructions).context("process part 2")
+}
 
-    process(input).context("process part 2")
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -141,7 +87,7 @@ mod tests {
     #[test_log::test]
     fn it_works() {
         let input = r##"rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7"##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "145");
     }
 }
@@ -0,0 +1,121 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{self, alpha1},
+    combinator::{consumed, map},
+    multi::separated_list1,
+    sequence::pair,
+    IResult,
+};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    Pop,
+    Push(u32),
+}
+
+/// A step of the initialization sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction<'a> {
+    /// The step as written, which part 1 hashes whole.
+    pub step: &'a str,
+    pub label: &'a str,
+    /// The box the label goes in.
+    pub hash: u8,
+    pub operation: Operation,
+}
+
+/// Written as a step of the input, like `rn=1` or `cm-`.
+impl fmt::Display for Instruction<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.operation {
+            Operation::Pop => write!(f, "{}-", self.label),
+            Operation::Push(focal_length) => write!(f, "{}={focal_length}", self.label),
+        }
+    }
+}
+
+/// The HASH algorithm.
+pub fn hash(s: &str) -> u8 {
+    s.bytes()
+        .fold(0, |acc, b| acc.wrapping_add(b).wrapping_mul(17))
+}
+
+#[tracing::instrument(skip(input))]
+fn label(input: &str) -> IResult<&str, &str> {
+    alpha1(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn operation(input: &str) -> IResult<&str, Operation> {
+    alt((
+        map(tag("-"), |_| Operation::Pop),
+        map(pair(tag("="), complete::u32), |(_, value)| {
+            Operation::Push(value)
+        }),
+    ))(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn instruction(input: &str) -> IResult<&str, Instruction<'_>> {
+    map(
+        consumed(pair(label, operation)),
+        |(step, (label, operation))| Instruction {
+            step,
+            label,
+            hash: hash(label),
+            operation,
+        },
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn instructions(input: &str) -> IResult<&str, Vec<Instruction<'_>>> {
+    separated_list1(tag(","), instruction)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    fn any_instruction() -> impl Strategy<Value = Instruction<'static>> {
+        // Instructions borrow from the input, so generated ones are leaked
+        // to live as long as the test.
+        let label = "[a-z]{1,8}".prop_map(|label| &*label.leak());
+        let operation = prop_oneof![Just(Operation::Pop), (1..10_u32).prop_map(Operation::Push),];
+
+        (label, operation).prop_map(|(label, operation)| {
+            let mut instruction = Instruction {
+                step: "",
+                label,
+                hash: hash(label),
+                operation,
+            };
+            instruction.step = instruction.to_string().leak();
+
+            instruction
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in prop::collection::vec(any_instruction(), 1..50)) {
+            let input = format!("{}\n", expected.iter().join(","));
+            prop_assert_eq!(parse_all_nom7(instructions, &input), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn steps_hash_as_written() {
+        assert_eq!(hash("HASH"), 52);
+
+        let (_, parsed) = instructions("rn=01,cm-").unwrap();
+        assert_eq!(parsed[0].step, "rn=01");
+        assert_eq!(parsed[0].operation, Operation::Push(1));
+        assert_eq!(parsed[1].step, "cm-");
+    }
+}
@@ -1,4 +1,5 @@
-use day16::{part1, part2};
+use aoc_core::{Rng, Solution};
+use day16::{parse, part1, part2, solve_part1, solve_part2, Puzzle};
 
 fn main() {
     divan::main();
@@ -13,3 +14,39 @@ fn part1_benchmark() {
 fn part2_benchmark() {
     part2(divan::black_box(include_str!("../input.txt",))).unwrap();
 }
+
+#[divan::bench]
+fn parse_benchmark() {
+    parse(divan::black_box(include_str!("../input.txt",))).unwrap();
+}
+
+#[divan::bench]
+fn solve_part1_benchmark(bencher: divan::Bencher) {
+    let parsed = parse(include_str!("../input.txt")).unwrap();
+    bencher.bench(|| solve_part1(divan::black_box(&parsed)).unwrap());
+}
+
+#[divan::bench]
+fn solve_part2_benchmark(bencher: divan::Bencher) {
+    let parsed = parse(include_str!("../input.txt")).unwrap();
+    bencher.bench(|| solve_part2(divan::black_box(&parsed)).unwrap());
+}
+
+/// A generated input of `size`, the same on every run.
+fn generated(size: usize) -> &'static str {
+    Puzzle::generate(&mut Rng::with_seed(2023), size)
+        .unwrap()
+        .leak()
+}
+
+#[divan::bench(args = [10, 50, 110])]
+fn part1_scaling(bencher: divan::Bencher, size: usize) {
+    let input = generated(size);
+    bencher.bench(|| part1(divan::black_box(input)).unwrap());
+}
+
+#[divan::bench(args = [10, 50, 110])]
+fn part2_scaling(bencher: divan::Bencher, size: usize) {
+    let input = generated(size);
+    bencher.bench(|| part2(divan::black_box(input)).unwrap());
+}
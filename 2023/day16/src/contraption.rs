@@ -0,0 +1,32 @@
+use nom::{
+    character::complete::{line_ending, one_of},
+    combinator::{map, verify},
+    multi::{many1, separated_list1},
+    IResult,
+};
+
+/// The tiles of the contraption, row by row.
+#[tracing::instrument(skip(input))]
+pub(crate) fn contraption(input: &str) -> IResult<&str, Vec<Vec<u8>>> {
+    // Beams walk the whole rectangle, so every row must be as wide.
+    let rectangular = |rows: &Vec<Vec<u8>>| rows.iter().all(|row| row.len() == rows[0].len());
+
+    verify(
+        separated_list1(line_ending, many1(map(one_of(r".|-/\"), |c| c as u8))),
+        rectangular,
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_must_line_up() {
+        assert_eq!(
+            contraption(".|\n-/\n"),
+            Ok(("\n", vec![b".|".to_vec(), b"-/".to_vec()]))
+        );
+        assert!(contraption(".|.\n-\n").is_err());
+    }
+}
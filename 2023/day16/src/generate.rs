@@ -0,0 +1,39 @@
+use aoc_core::Rng;
+
+/// A `size` by `size` contraption, about one tile in ten a mirror or a
+/// splitter, as in the puzzle's input.
+pub(crate) fn contraption(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+
+    (0..size)
+        .map(|_| {
+            let mut row = (0..size)
+                .map(|_| match rng.u8(0..40) {
+                    0 => '/',
+                    1 => '\\',
+                    2 => '|',
+                    3 => '-',
+                    _ => '.',
+                })
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn beams_cross_generated_contraptions() {
+        for seed in 0..10 {
+            let input = &*contraption(&mut Rng::with_seed(seed), 1 + seed as usize * 3).leak();
+
+            let from_the_corner = crate::part1(input).unwrap().parse::<usize>().unwrap();
+            let best = crate::part2(input).unwrap().parse::<usize>().unwrap();
+            assert!(from_the_corner <= best, "{input}");
+        }
+    }
+}
@@ -1,5 +1,47 @@
+mod contraption;
+mod generate;
 mod part1;
 mod part2;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+
+use anyhow::{Context, Result};
+use aoc_core::{Rng, Solution};
+use aoc_parse::parse_all_nom7;
+use contraption::contraption;
+use std::fmt::Display;
+
+/// The tiles of the contraption both parts shine beams through.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Vec<Vec<u8>>> {
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all_nom7(contraption, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 16;
+
+    type Input = Vec<Vec<u8>>;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate::contraption(rng, size))
+    }
+}
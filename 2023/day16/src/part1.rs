@@ -1,29 +1,24 @@
+use crate::parse;
 use anyhow::{Context, Result};
+use aoc_core::SolveError;
+use aoc_grid::Direction4;
 use glam::IVec2;
 use std::collections::{HashSet, VecDeque};
 use tracing::info;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(Debug)]
 struct Ray {
-    direction: Direction,
+    direction: Direction4,
     position: IVec2,
-    next_position: VecDeque<(IVec2, Direction)>,
-    path: HashSet<(IVec2, Direction)>,
+    next_position: VecDeque<(IVec2, Direction4)>,
+    path: HashSet<(IVec2, Direction4)>,
     energized: HashSet<IVec2>,
 }
 
 impl Ray {
     fn new() -> Self {
         let position = IVec2::new(0, 0);
-        let direction = Direction::Right;
+        let direction = Direction4::East;
         let next_position = (position, direction);
 
         let mut ray = Ray {
@@ -39,16 +34,20 @@ impl Ray {
         ray
     }
 
-    fn calculate_next_positions(&mut self, grid: &[u8], width: usize, height: usize) {
-        let (position, direction) = self
-            .next_position
-            .pop_back()
-            .expect("should have next position");
+    fn calculate_next_positions(&mut self, grid: &[Vec<u8>], width: usize, height: usize) {
+        let Some((position, direction)) = self.next_position.pop_back() else {
+            return;
+        };
 
         self.position = position;
         self.direction = direction;
 
-        let tile = grid[self.grid_position(width)];
+        let Some(&tile) = grid
+            .get(self.position.y as usize)
+            .and_then(|row| row.get(self.position.x as usize))
+        else {
+            return;
+        };
 
         // energize current tile
         self.energized.insert(self.position);
@@ -75,7 +74,7 @@ impl Ray {
     fn append_next_position(
         &mut self,
         position: IVec2,
-        direction: Direction,
+        direction: Direction4,
         width: isize,
         height: isize,
     ) {
@@ -86,130 +85,32 @@ impl Ray {
         }
     }
 
+    fn step(&mut self, direction: Direction4, width: isize, height: isize) {
+        self.append_next_position(
+            self.position + direction.offset::<IVec2>(),
+            direction,
+            width,
+            height,
+        );
+    }
+
     fn follow_empty(&mut self, width: isize, height: isize) {
-        match self.direction {
-            Direction::Up => self.append_next_position(
-                self.position + IVec2::new(0, -1),
-                Direction::Up,
-                width,
-                height,
-            ),
-            Direction::Down => self.append_next_position(
-                self.position + IVec2::new(0, 1),
-                Direction::Down,
-                width,
-                height,
-            ),
-            Direction::Left => self.append_next_position(
-                self.position + IVec2::new(-1, 0),
-                Direction::Left,
-                width,
-                height,
-            ),
-            Direction::Right => self.append_next_position(
-                self.position + IVec2::new(1, 0),
-                Direction::Right,
-                width,
-                height,
-            ),
-        }
+        self.step(self.direction, width, height);
     }
 
     fn follow_splitter(&mut self, tile: u8, width: isize, height: isize) {
-        match tile {
-            b'|' => match self.direction {
-                Direction::Left | Direction::Right => {
-                    self.append_next_position(
-                        self.position + IVec2::new(0, -1),
-                        Direction::Up,
-                        width,
-                        height,
-                    );
-                    self.append_next_position(
-                        self.position + IVec2::new(0, 1),
-                        Direction::Down,
-                        width,
-                        height,
-                    );
-                }
-                _ => self.follow_empty(width, height),
-            },
-            b'-' => match self.direction {
-                Direction::Up | Direction::Down => {
-                    self.append_next_position(
-                        self.position + IVec2::new(-1, 0),
-                        Direction::Left,
-                        width,
-                        height,
-                    );
-                    self.append_next_position(
-                        self.position + IVec2::new(1, 0),
-                        Direction::Right,
-                        width,
-                        height,
-                    );
-                }
-                _ => self.follow_empty(width, height),
-            },
-            _ => unreachable!(),
+        match (tile, self.direction.is_horizontal()) {
+            (b'|', true) | (b'-', false) => {
+                self.step(self.direction.turn_left(), width, height);
+                self.step(self.direction.turn_right(), width, height);
+            }
+            _ => self.follow_empty(width, height),
         }
     }
 
     fn follow_mirror(&mut self, tile: u8, width: isize, height: isize) {
-        match tile {
-            b'/' => match self.direction {
-                Direction::Up => self.append_next_position(
-                    self.position + IVec2::new(1, 0),
-                    Direction::Right,
-                    width,
-                    height,
-                ),
-                Direction::Down => self.append_next_position(
-                    self.position + IVec2::new(-1, 0),
-                    Direction::Left,
-                    width,
-                    height,
-                ),
-                Direction::Left => self.append_next_position(
-                    self.position + IVec2::new(0, 1),
-                    Direction::Down,
-                    width,
-                    height,
-                ),
-                Direction::Right => self.append_next_position(
-                    self.position + IVec2::new(0, -1),
-                    Direction::Up,
-                    width,
-                    height,
-                ),
-            },
-            b'\\' => match self.direction {
-                Direction::Up => self.append_next_position(
-                    self.position + IVec2::new(-1, 0),
-                    Direction::Left,
-                    width,
-                    height,
-                ),
-                Direction::Down => self.append_next_position(
-                    self.position + IVec2::new(1, 0),
-                    Direction::Right,
-                    width,
-                    height,
-                ),
-                Direction::Left => self.append_next_position(
-                    self.position + IVec2::new(0, -1),
-                    Direction::Up,
-                    width,
-                    height,
-                ),
-                Direction::Right => self.append_next_position(
-                    self.position + IVec2::new(0, 1),
-                    Direction::Down,
-                    width,
-                    height,
-                ),
-            },
-            _ => unreachable!(),
+        if let Some(direction) = self.direction.reflect(tile as char) {
+            self.step(direction, width, height);
         }
     }
 
@@ -217,25 +118,19 @@ impl Ray {
         !self.next_position.is_empty()
     }
 
-    fn grid_position(&self, width: usize) -> usize {
-        (self.position.y as usize) * width + self.position.x as usize
-    }
-
     fn is_within_bounds(&self, position: IVec2, width: isize, height: isize) -> bool {
         position.x >= 0
-            && position.x < (width as i32) - 1 // ignore \n at the end of each line
+            && position.x < (width as i32)
             && position.y >= 0
             && position.y < (height as i32)
     }
 }
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+fn process(grid: &[Vec<u8>]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let width = input.find('\n').expect("should have a newline") + 1;
-    let height = input.len() / width;
-    let grid = input.as_bytes();
+    let (width, height) = (grid[0].len(), grid.len());
     let mut ray = Ray::new();
 
     while ray.keep_following() {
@@ -246,11 +141,16 @@ fn process(input: &'static str) -> Result<String> {
     Ok(energized_count.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+pub fn solve_part1(grid: &[Vec<u8>]) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
+
+    process(grid).context("process part 1")
+}
 
-    process(input).context("process part 1")
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -270,7 +170,7 @@ mod tests {
 .|....-|.\
 ..//.|....
 ";
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "46");
     }
 }
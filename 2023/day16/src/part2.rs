@@ -1,28 +1,23 @@
+use crate::parse;
 use anyhow::{Context, Result};
+use aoc_core::SolveError;
+use aoc_grid::Direction4;
 use glam::IVec2;
 use rayon::prelude::*;
 use std::collections::{HashSet, VecDeque};
 use tracing::info;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(Debug)]
 struct Ray {
-    direction: Direction,
+    direction: Direction4,
     position: IVec2,
-    next_position: VecDeque<(IVec2, Direction)>,
-    path: HashSet<(IVec2, Direction)>,
+    next_position: VecDeque<(IVec2, Direction4)>,
+    path: HashSet<(IVec2, Direction4)>,
     energized: HashSet<IVec2>,
 }
 
 impl Ray {
-    fn new(position: IVec2, direction: Direction) -> Self {
+    fn new(position: IVec2, direction: Direction4) -> Self {
         let next_position = (position, direction);
 
         let mut ray = Ray {
@@ -38,16 +33,20 @@ impl Ray {
         ray
     }
 
-    fn calculate_next_positions(&mut self, grid: &[u8], width: usize, height: usize) {
-        let (position, direction) = self
-            .next_position
-            .pop_back()
-            .expect("should have next position");
+    fn calculate_next_positions(&mut self, grid: &[Vec<u8>], width: usize, height: usize) {
+        let Some((position, direction)) = self.next_position.pop_back() else {
+            return;
+        };
 
         self.position = position;
         self.direction = direction;
 
-        let tile = grid[self.grid_position(width)];
+        let Some(&tile) = grid
+            .get(self.position.y as usize)
+            .and_then(|row| row.get(self.position.x as usize))
+        else {
+            return;
+        };
 
         // energize current tile
         self.energized.insert(self.position);
@@ -74,7 +73,7 @@ impl Ray {
     fn append_next_position(
         &mut self,
         position: IVec2,
-        direction: Direction,
+        direction: Direction4,
         width: isize,
         height: isize,
     ) {
@@ -85,130 +84,32 @@ impl Ray {
         }
     }
 
+    fn step(&mut self, direction: Direction4, width: isize, height: isize) {
+        self.append_next_position(
+            self.position + direction.offset::<IVec2>(),
+            direction,
+            width,
+            height,
+        );
+    }
+
     fn follow_empty(&mut self, width: isize, height: isize) {
-        match self.direction {
-            Direction::Up => self.append_next_position(
-                self.position + IVec2::new(0, -1),
-                Direction::Up,
-                width,
-                height,
-            ),
-            Direction::Down => self.append_next_position(
-                self.position + IVec2::new(0, 1),
-                Direction::Down,
-                width,
-                height,
-            ),
-            Direction::Left => self.append_next_position(
-                self.position + IVec2::new(-1, 0),
-                Direction::Left,
-                width,
-                height,
-            ),
-            Direction::Right => self.append_next_position(
-                self.position + IVec2::new(1, 0),
-                Direction::Right,
-                width,
-                height,
-            ),
-        }
+        self.step(self.direction, width, height);
     }
 
     fn follow_splitter(&mut self, tile: u8, width: isize, height: isize) {
-        match tile {
-            b'|' => match self.direction {
-                Direction::Left | Direction::Right => {
-                    self.append_next_position(
-                        self.position + IVec2::new(0, -1),
-                        Direction::Up,
-                        width,
-                        height,
-                    );
-                    self.append_next_position(
-                        self.position + IVec2::new(0, 1),
-                        Direction::Down,
-                        width,
-                        height,
-                    );
-                }
-                _ => self.follow_empty(width, height),
-            },
-            b'-' => match self.direction {
-                Direction::Up | Direction::Down => {
-                    self.append_next_position(
-                        self.position + IVec2::new(-1, 0),
-                        Direction::Left,
-                        width,
-                        height,
-                    );
-                    self.append_next_position(
-                        self.position + IVec2::new(1, 0),
-                        Direction::Right,
-                        width,
-                        height,
-                    );
-                }
-                _ => self.follow_empty(width, height),
-            },
-            _ => unreachable!(),
+        match (tile, self.direction.is_horizontal()) {
+            (b'|', true) | (b'-', false) => {
+                self.step(self.direction.turn_left(), width, height);
+                self.step(self.direction.turn_right(), width, height);
+            }
+            _ => self.follow_empty(width, height),
         }
     }
 
     fn follow_mirror(&mut self, tile: u8, width: isize, height: isize) {
-        match tile {
-            b'/' => match self.direction {
-                Direction::Up => self.append_next_position(
-                    self.position + IVec2::new(1, 0),
-                    Direction::Right,
-                    width,
-                    height,
-                ),
-                Direction::Down => self.append_next_position(
-                    self.position + IVec2::new(-1, 0),
-                    Direction::Left,
-                    width,
-                    height,
-                ),
-                Direction::Left => self.append_next_position(
-                    self.position + IVec2::new(0, 1),
-                    Direction::Down,
-                    width,
-                    height,
-                ),
-                Direction::Right => self.append_next_position(
-                    self.position + IVec2::new(0, -1),
-                    Direction::Up,
-                    width,
-                    height,
-                ),
-            },
-            b'\\' => match self.direction {
-                Direction::Up => self.append_next_position(
-                    self.position + IVec2::new(-1, 0),
-                    Direction::Left,
-                    width,
-                    height,
-                ),
-                Direction::Down => self.append_next_position(
-                    self.position + IVec2::new(1, 0),
-                    Direction::Right,
-                    width,
-                    height,
-                ),
-                Direction::Left => self.append_next_position(
-                    self.position + IVec2::new(0, -1),
-                    Direction::Up,
-                    width,
-                    height,
-                ),
-                Direction::Right => self.append_next_position(
-                    self.position + IVec2::new(0, 1),
-                    Direction::Down,
-                    width,
-                    height,
-                ),
-            },
-            _ => unreachable!(),
+        if let Some(direction) = self.direction.reflect(tile as char) {
+            self.step(direction, width, height);
         }
     }
 
@@ -216,13 +117,9 @@ impl Ray {
         !self.next_position.is_empty()
     }
 
-    fn grid_position(&self, width: usize) -> usize {
-        (self.position.y as usize) * width + self.position.x as usize
-    }
-
     fn is_within_bounds(&self, position: IVec2, width: isize, height: isize) -> bool {
         position.x >= 0
-            && position.x < (width as i32) - 1 // ignore \n at the end of each line
+            && position.x < (width as i32)
             && position.y >= 0
             && position.y < (height as i32)
     }
@@ -232,16 +129,16 @@ impl Ray {
 fn starting_rays(width: usize, height: usize) -> impl ParallelIterator<Item = Ray> {
     let top_row = (0..width)
         .into_par_iter()
-        .map(move |x| Ray::new(IVec2::new(x as i32, 0), Direction::Down));
+        .map(move |x| Ray::new(IVec2::new(x as i32, 0), Direction4::South));
     let bottom_row = (0..width)
         .into_par_iter()
-        .map(move |x| Ray::new(IVec2::new(x as i32, (height - 1) as i32), Direction::Up));
+        .map(move |x| Ray::new(IVec2::new(x as i32, (height - 1) as i32), Direction4::North));
     let left_column = (0..height)
         .into_par_iter()
-        .map(move |y| Ray::new(IVec2::new(0, y as i32), Direction::Right));
+        .map(move |y| Ray::new(IVec2::new(0, y as i32), Direction4::East));
     let right_column = (0..height)
         .into_par_iter()
-        .map(move |y| Ray::new(IVec2::new((width - 1) as i32, y as i32), Direction::Left));
+        .map(move |y| Ray::new(IVec2::new((width - 1) as i32, y as i32), Direction4::West));
 
     top_row
         .chain(bottom_row)
@@ -249,15 +146,13 @@ fn starting_rays(width: usize, height: usize) -> impl ParallelIterator<Item = Ra
         .chain(right_column)
 }
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+fn process(grid: &[Vec<u8>]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let width = input.find('\n').expect("should have a newline") + 1;
-    let height = input.len() / width;
-    let grid = input.as_bytes();
+    let (width, height) = (grid[0].len(), grid.len());
 
-    let result = starting_rays(width - 1, height)
+    let result = starting_rays(width, height)
         .map(|mut ray| {
             while ray.keep_following() {
                 ray.calculate_next_positions(grid, width, height);
@@ -266,16 +161,21 @@ fn process(input: &'static str) -> Result<String> {
             ray.energized.len()
         })
         .max()
-        .expect("should have a maximum");
+        .ok_or_else(|| SolveError::malformed("contraption is empty"))?;
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+pub fn solve_part2(grid: &[Vec<u8>]) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
+
+    process(grid).context("process part 2")
+}
 
-    process(input).context("process part 2")
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -295,7 +195,7 @@ mod tests {
 .|....-|.\
 ..//.|....
 ";
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "51");
     }
 }
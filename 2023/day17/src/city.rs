@@ -0,0 +1,34 @@
+use nom::{
+    character::complete::{line_ending, satisfy},
+    combinator::{map_opt, map_res},
+    multi::{many1, separated_list1},
+    IResult,
+};
+use pathfinding::matrix::Matrix;
+
+/// The heat loss of each city block. The crucible moves between
+/// neighbouring blocks, so the rows must line up.
+#[tracing::instrument(skip(input))]
+pub fn city(input: &str) -> IResult<&str, Matrix<u32>> {
+    map_res(
+        separated_list1(
+            line_ending,
+            many1(map_opt(satisfy(|c| c.is_ascii_digit()), |c| c.to_digit(10))),
+        ),
+        Matrix::from_rows,
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_cities() {
+        let (_, grid) = city("241\n321\n").unwrap();
+        assert_eq!(grid[(1, 0)], 3);
+
+        assert!(city("").is_err());
+        assert!(city("123\n45\n").is_err());
+    }
+}
@@ -1,54 +1,51 @@
+use crate::parse;
 use anyhow::{Context, Result};
-use pathfinding::{
-    directed::dijkstra::dijkstra,
-    matrix::{directions, Matrix},
-};
+use aoc_core::SolveError;
+use aoc_grid::Direction4;
+use pathfinding::{directed::dijkstra::dijkstra, matrix::Matrix};
 use tracing::info;
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+fn process(grid: &Matrix<u32>) -> Result<String, SolveError> {
     info!("processing input");
 
-    let grid = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).expect("should be a valid number"))
-        })
-        .collect::<Matrix<u32>>();
+    if grid.rows == 0 || grid.columns == 0 {
+        return Err(SolveError::malformed("the city is empty"));
+    }
 
     let path = dijkstra(
-        &((0, 0), directions::E, 0),
+        &((0, 0), Direction4::East, 0),
         |&(pos, step_direction, same_direction_count)| {
-            directions::DIRECTIONS_4
+            Direction4::ALL
                 .into_iter()
                 .filter_map(|direction| {
-                    grid.move_in_direction(pos, direction)
-                        .map(|neighbor_position| {
+                    grid.move_in_direction(pos, direction.row_col())
+                        .and_then(|neighbor_position| {
+                            let heat_loss = *grid.get(neighbor_position)? as usize;
+
                             // add a large penalty if we attempt to travel less than 4 steps in the
                             // same direction
                             let cost = if same_direction_count < 4 && direction != step_direction {
                                 420
                             } else {
-                                *grid.get(neighbor_position).unwrap() as usize
+                                heat_loss
                             };
 
                             // info!(?direction, ?step_direction, ?same_direction_count);
 
-                            if direction != (step_direction.0 * -1, step_direction.1 * -1)
-                                && direction != step_direction
+                            if direction != step_direction.reverse() && direction != step_direction
                             {
                                 // changing direction, reset same direction count to 1
-                                ((neighbor_position, direction, 1), cost)
+                                Some(((neighbor_position, direction, 1), cost))
                             } else if same_direction_count < 10 && direction == step_direction {
                                 // same direction, increment same direction count
-                                (
+                                Some((
                                     (neighbor_position, direction, same_direction_count + 1),
                                     cost,
-                                )
+                                ))
                             } else {
                                 // Add a node that we will never follow
-                                ((neighbor_position, direction, 3), 420)
+                                Some(((neighbor_position, direction, 3), 420))
                             }
                         })
                 })
@@ -56,7 +53,7 @@ fn process(input: &'static str) -> Result<String> {
         },
         |&(pos, _, dist)| pos == (grid.rows - 1, grid.columns - 1) && dist >= 4,
     )
-    .expect("should have found a path");
+    .ok_or_else(|| SolveError::no_solution("no path reaches the factory"))?;
 
     info!(?path);
 
@@ -64,11 +61,16 @@ fn process(input: &'static str) -> Result<String> {
     Ok(total_cost.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+pub fn solve_part2(grid: &Matrix<u32>) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
 
-    process(input).context("process part 2")
+    process(grid).context("process part 2")
+}
+
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -83,7 +85,7 @@ mod tests {
 999999999991
 999999999991
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "71");
     }
 
@@ -102,7 +104,7 @@ mod tests {
 1224686865563
 2546548887735
 4322674655533"##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "94");
     }
 }
@@ -0,0 +1,188 @@
+use aoc_grid::Direction4;
+use nom::{
+    bytes::complete::{tag, take_while_m_n},
+    character::complete::{self, line_ending, one_of, space1},
+    combinator::{map, map_res},
+    multi::separated_list1,
+    sequence::{delimited, tuple},
+    IResult,
+};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub direction: Direction4,
+    pub amount: i64,
+    /// The colour code, which part 2 reads as the real instruction.
+    pub color: u32,
+}
+
+impl Instruction {
+    /// The instruction hidden in the colour: five hex digits of distance,
+    /// then one for the direction. `None` if that digit is not 0 to 3.
+    pub fn decoded(&self) -> Option<(Direction4, i64)> {
+        let direction = match self.color & 0xf {
+            0 => Direction4::East,
+            1 => Direction4::South,
+            2 => Direction4::West,
+            3 => Direction4::North,
+            _ => return None,
+        };
+
+        Some((direction, i64::from(self.color >> 4)))
+    }
+}
+
+/// Cubic metres the lagoon holds once the trench along `plan` is dug and
+/// its inside hollowed out: the shoelace area of the loop plus the half of
+/// the trench outside it. `None` if that does not fit in an `i64`.
+pub fn lagoon_area(plan: &[Instruction]) -> Option<i64> {
+    let (mut area, mut perimeter, mut y) = (0_i64, 1_i64, 0_i64);
+
+    for instruction in plan {
+        let (dx, dy) = instruction.direction.delta();
+        let dx = (dx as i64).checked_mul(instruction.amount)?;
+        let dy = (dy as i64).checked_mul(instruction.amount)?;
+
+        area = area.checked_add(dx.checked_mul(y)?)?;
+        perimeter = perimeter
+            .checked_add(dx.checked_abs()?)?
+            .checked_add(dy.checked_abs()?)?;
+        y = y.checked_add(dy)?;
+    }
+
+    area.checked_abs()?.checked_add(perimeter / 2 + 1)
+}
+
+/// Written as a line of the input, like `R 6 (#70c710)`.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            Direction4::North => 'U',
+            Direction4::South => 'D',
+            Direction4::West => 'L',
+            Direction4::East => 'R',
+        };
+
+        write!(f, "{direction} {} (#{:06x})", self.amount, self.color)
+    }
+}
+
+#[tracing::instrument(skip(input))]
+fn color(input: &str) -> IResult<&str, u32> {
+    map_res(
+        take_while_m_n(6, 6, |c: char| c.is_ascii_hexdigit()),
+        |hex| u32::from_str_radix(hex, 16),
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn amount(input: &str) -> IResult<&str, i64> {
+    complete::i64(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn direction(input: &str) -> IResult<&str, Direction4> {
+    map_res(one_of("UDLR"), Direction4::try_from)(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn instruction(input: &str) -> IResult<&str, Instruction> {
+    map(
+        tuple((
+            direction,
+            space1,
+            amount,
+            space1,
+            delimited(tag("(#"), color, tag(")")),
+        )),
+        |(direction, _, amount, _, color)| Instruction {
+            direction,
+            amount,
+            color,
+        },
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn dig_plan(input: &str) -> IResult<&str, Vec<Instruction>> {
+    separated_list1(line_ending, instruction)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use proptest::prelude::*;
+
+    fn any_instruction() -> impl Strategy<Value = Instruction> {
+        (
+            prop::sample::select(Direction4::ALL.to_vec()),
+            1..100_i64,
+            0..0x1000000_u32,
+        )
+            .prop_map(|(direction, amount, color)| Instruction {
+                direction,
+                amount,
+                color,
+            })
+    }
+
+    /// An instruction written with any run of spaces and tabs between its
+    /// fields and either line ending, paired with the instruction it reads
+    /// as.
+    fn any_spaced_instruction() -> impl Strategy<Value = (Instruction, String)> {
+        let gap = || "[ \t]{1,3}";
+
+        (
+            any_instruction(),
+            gap(),
+            gap(),
+            prop_oneof![Just("\n"), Just("\r\n")],
+        )
+            .prop_map(|(instruction, before_amount, before_color, end)| {
+                let written = instruction.to_string();
+                let fields = written.split(' ').collect::<Vec<_>>();
+                let line = format!(
+                    "{}{before_amount}{}{before_color}{}{end}",
+                    fields[0], fields[1], fields[2]
+                );
+                (instruction, line)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in prop::collection::vec(any_instruction(), 1..50)) {
+            let input = expected.iter().map(|instruction| format!("{instruction}\n")).collect::<String>();
+            prop_assert_eq!(parse_all_nom7(dig_plan, &input), Ok(expected));
+        }
+
+        #[test]
+        fn spaced_round_trip(
+            instructions in prop::collection::vec(any_spaced_instruction(), 1..50),
+        ) {
+            let input = instructions.iter().map(|(_, line)| line.as_str()).collect::<String>();
+            let expected = instructions.into_iter().map(|(instruction, _)| instruction).collect::<Vec<_>>();
+            prop_assert_eq!(parse_all_nom7(dig_plan, &input), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn lagoon_area_overflows() {
+        let (_, plan) =
+            dig_plan("R 2 (#000000)\nD 2 (#000000)\nL 2 (#000000)\nU 2 (#000000)\n").unwrap();
+        assert_eq!(lagoon_area(&plan), Some(9));
+
+        let (_, plan) = dig_plan("L 9223372036854775807 (#088ba0)\n").unwrap();
+        assert_eq!(lagoon_area(&plan), None);
+    }
+
+    #[test]
+    fn colors_hide_instructions() {
+        let (_, plan) = dig_plan("R 6 (#70c710)\nD 5 (#0dc574)\n").unwrap();
+
+        assert_eq!(plan[0].decoded(), Some((Direction4::East, 461937)));
+        assert_eq!(plan[1].decoded(), None);
+    }
+}
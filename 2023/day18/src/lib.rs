@@ -1,5 +1,42 @@
+mod dig_plan;
 mod part1;
 mod part2;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use dig_plan::{dig_plan, lagoon_area, Instruction};
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+
+use anyhow::{Context, Result};
+use aoc_core::Solution;
+use aoc_parse::parse_all_nom7;
+use std::fmt::Display;
+
+/// The dig plan, which each part reads its own way.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Vec<Instruction>> {
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all_nom7(dig_plan, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 18;
+
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+}
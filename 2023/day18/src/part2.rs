@@ -1,99 +1,43 @@
+use crate::{lagoon_area, parse, Instruction};
 use anyhow::{Context, Result};
-use glam::I64Vec2;
-use nom::{
-    bytes::complete::{tag, take_until, take_while_m_n},
-    character::complete::{line_ending, one_of},
-    combinator::{map, map_res},
-    multi::separated_list1,
-    sequence::tuple,
-    IResult,
-};
+use aoc_core::SolveError;
 use tracing::info;
 
-#[derive(Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug)]
-struct Instruction {
-    direction: Direction,
-    amount: i64,
-}
-
-#[tracing::instrument(skip(c))]
-fn is_hex_digit(c: char) -> bool {
-    c.is_digit(16)
-}
-
-#[tracing::instrument(skip(input))]
-fn amount(input: &str) -> IResult<&str, i64> {
-    map_res(take_while_m_n(5, 5, is_hex_digit), |input| {
-        i64::from_str_radix(input, 16)
-    })(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn direction(input: &str) -> IResult<&str, Direction> {
-    map(one_of("0123"), |c| match c {
-        '0' => Direction::Right,
-        '1' => Direction::Down,
-        '2' => Direction::Left,
-        '3' => Direction::Up,
-        _ => unreachable!(),
-    })(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn instruction(input: &str) -> IResult<&str, Instruction> {
-    map(
-        tuple((take_until("#"), tag("#"), amount, direction, tag(")"))),
-        |(_, _, amount, direction, _)| Instruction { direction, amount },
-    )(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn dig_plan(input: &str) -> IResult<&str, Vec<Instruction>> {
-    separated_list1(line_ending, instruction)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(dig_plan))]
+fn process(dig_plan: &[Instruction]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, dig_plan) = dig_plan(input)?;
-
-    let (inner_area, perimeter, _) = dig_plan.iter().fold(
-        (0, 1, I64Vec2::splat(0)),
-        |(area, perimeter, current), inst| {
-            let delta = match inst.direction {
-                Direction::Up => I64Vec2::new(0, -inst.amount),
-                Direction::Down => I64Vec2::new(0, inst.amount),
-                Direction::Left => I64Vec2::new(-inst.amount, 0),
-                Direction::Right => I64Vec2::new(inst.amount, 0),
-            };
+    let dig_plan = dig_plan
+        .iter()
+        .map(|instruction| {
+            let (direction, amount) = instruction.decoded().ok_or_else(|| {
+                SolveError::malformed(format!("{instruction} hides no direction"))
+            })?;
 
-            (
-                area + delta.x * current.y,
-                perimeter + delta.x.abs() + delta.y.abs(),
-                current + delta,
-            )
-        },
-    );
+            Ok(Instruction {
+                direction,
+                amount,
+                ..*instruction
+            })
+        })
+        .collect::<Result<Vec<_>, SolveError>>()?;
 
-    let area = inner_area.abs() + perimeter / 2 + 1;
+    let area = lagoon_area(&dig_plan)
+        .ok_or_else(|| SolveError::malformed("the lagoon is too big to measure"))?;
 
     Ok(area.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(dig_plan))]
+pub fn solve_part2(dig_plan: &[Instruction]) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
+
+    process(dig_plan).context("process part 2")
+}
 
-    process(input).context("process part 2")
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -117,7 +61,7 @@ U 3 (#a77fa3)
 L 2 (#015232)
 U 2 (#7a21e3)
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "952408144115");
     }
 }
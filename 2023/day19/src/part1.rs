@@ -1,209 +1,75 @@
+use crate::{parse, Part, Rule, System, Target};
 use anyhow::{Context, Result};
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, alpha1, line_ending, one_of},
-    combinator::map,
-    multi::{many1, separated_list1},
-    sequence::{delimited, separated_pair, tuple},
-    IResult,
-};
-use std::{cmp::Ordering, collections::BTreeMap};
+use aoc_core::SolveError;
+use std::collections::BTreeMap;
 use tracing::info;
 
-#[derive(Debug)]
-enum Target<'a> {
-    Accept,
-    Reject,
-    Workflow(&'a str),
-}
-
-#[derive(Debug)]
-enum Rule<'a> {
-    Test {
-        category: char,
-        ordering: Ordering,
-        number: i32,
-        target: Target<'a>,
-    },
-    Target(Target<'a>),
-}
-
-impl<'a> Rule<'a> {
-    fn apply(&self, part: &Part) -> Option<&Target> {
+impl Rule<'_> {
+    fn apply(&self, part: &Part) -> Option<&Target<'_>> {
         match self {
             Rule::Test {
                 category,
                 ordering,
                 number,
                 target,
-            } => {
-                let test_value = match *category {
-                    'x' => part.x,
-                    'm' => part.m,
-                    'a' => part.a,
-                    's' => part.s,
-                    _ => unreachable!(),
-                };
-                (test_value.cmp(number) == *ordering).then_some(target)
-            }
+            } => (part.rating(*category).cmp(number) == *ordering).then_some(target),
             Rule::Target(target) => Some(target),
         }
     }
 }
 
-#[derive(Debug)]
-struct Part {
-    x: i32,
-    m: i32,
-    a: i32,
-    s: i32,
-}
-
-#[tracing::instrument(skip(input))]
-fn target<'a>(input: &'a str) -> IResult<&str, Target<'a>> {
-    alt((
-        map(complete::char('A'), |_| Target::Accept),
-        map(complete::char('R'), |_| Target::Reject),
-        map(alpha1, |name| Target::Workflow(name)),
-    ))(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn rule(input: &str) -> IResult<&str, Rule> {
-    alt((
-        map(
-            tuple((
-                one_of("xmas"),
-                one_of("<>"),
-                complete::i32,
-                tag(":"),
-                target,
-            )),
-            |(category, ordering, number, _, target)| {
-                let ordering = match ordering {
-                    '<' => Ordering::Less,
-                    '>' => Ordering::Greater,
-                    _ => unreachable!(),
-                };
-
-                Rule::Test {
-                    category,
-                    ordering,
-                    number,
-                    target,
-                }
-            },
-        ),
-        map(target, |target| Rule::Target(target)),
-    ))(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn rules(input: &str) -> IResult<&str, Vec<Rule>> {
-    separated_list1(tag(","), rule)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn workflow(input: &str) -> IResult<&str, (&str, Vec<Rule>)> {
-    tuple((alpha1, delimited(tag("{"), rules, tag("}"))))(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn workflows(input: &str) -> IResult<&str, BTreeMap<&str, Vec<Rule>>> {
-    map(separated_list1(line_ending, workflow), |workflows| {
-        BTreeMap::from_iter(workflows)
-    })(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn xmas(input: &str) -> IResult<&str, (i32, i32, i32, i32)> {
-    map(
-        tuple((
-            tag("x="),
-            complete::i32,
-            tag(",m="),
-            complete::i32,
-            tag(",a="),
-            complete::i32,
-            tag(",s="),
-            complete::i32,
-        )),
-        |(_, x, _, m, _, a, _, s)| (x, m, a, s),
-    )(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn part(input: &str) -> IResult<&str, Part> {
-    map(delimited(tag("{"), xmas, tag("}")), |(x, m, a, s)| Part {
-        x,
-        m,
-        a,
-        s,
-    })(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn parts(input: &str) -> IResult<&str, Vec<Part>> {
-    separated_list1(line_ending, part)(input)
-}
+#[tracing::instrument(skip(workflows, part))]
+fn accepts(workflows: &BTreeMap<&str, Vec<Rule>>, part: &Part) -> Result<bool, SolveError> {
+    let mut current_workflow = "in";
+
+    // Without a loop in the rules a part visits each workflow at most once.
+    for _ in 0..=workflows.len() {
+        let active_workflow = workflows
+            .get(current_workflow)
+            .ok_or_else(|| SolveError::malformed(format!("unknown workflow {current_workflow}")))?;
+
+        match active_workflow.iter().find_map(|rule| rule.apply(part)) {
+            Some(Target::Accept) => return Ok(true),
+            Some(Target::Reject) => return Ok(false),
+            Some(Target::Workflow(next_workflow)) => current_workflow = next_workflow,
+            None => {
+                return Err(SolveError::malformed(format!(
+                    "workflow {current_workflow} has no fallback rule"
+                )))
+            }
+        }
+    }
 
-#[tracing::instrument(skip(input))]
-fn workflows_and_parts(input: &str) -> IResult<&str, (BTreeMap<&str, Vec<Rule>>, Vec<Part>)> {
-    separated_pair(workflows, many1(line_ending), parts)(input)
+    Err(SolveError::unsupported(
+        "workflows send parts round in a loop",
+    ))
 }
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(system))]
+fn process(system: &System) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, (workflows, parts)) = workflows_and_parts(input)?;
-
-    let result = parts
-        .iter()
-        .filter_map(|part| {
-            let mut current_workflow = "in";
+    let mut result = 0;
 
-            let last_target: Target = 'workflow_loop: loop {
-                let active_workflow = workflows
-                    .get(current_workflow)
-                    .expect("should only fetch valid workflows");
-
-                'rule_loop: for rule in active_workflow.iter() {
-                    match rule.apply(part) {
-                        Some(Target::Accept) => {
-                            break 'workflow_loop Target::Accept;
-                        }
-                        Some(Target::Reject) => {
-                            break 'workflow_loop Target::Reject;
-                        }
-                        Some(Target::Workflow(next_workflow)) => {
-                            current_workflow = next_workflow;
-                            break 'rule_loop;
-                        }
-                        None => {}
-                    }
-                }
-            };
-
-            match last_target {
-                Target::Workflow(_) => {
-                    unreachable!("shouldn't end on a workflow")
-                }
-                Target::Accept => Some(part.x + part.m + part.a + part.s),
-                Target::Reject => None,
-            }
-        })
-        .sum::<i32>();
+    for part in system.parts.iter() {
+        if accepts(&system.workflows, part)? {
+            result += part.x + part.m + part.a + part.s;
+        }
+    }
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(system))]
+pub fn solve_part1(system: &System) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
+
+    process(system).context("process part 1")
+}
 
-    process(input).context("process part 1")
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -229,7 +95,7 @@ hdj{m>838:A,pv}
 {x=2036,m=264,a=79,s=2244}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}"##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "19114");
     }
 }
@@ -1,23 +1,9 @@
+use crate::{parse, Category, Rule, System, Target};
 use anyhow::{Context, Result};
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, alpha1, line_ending, one_of},
-    combinator::map,
-    multi::separated_list1,
-    sequence::{delimited, tuple},
-    IResult,
-};
+use aoc_core::SolveError;
 use std::{cmp::Ordering, collections::BTreeMap, ops::RangeInclusive};
 use tracing::info;
 
-#[derive(Debug)]
-enum Target<'a> {
-    Accept,
-    Reject,
-    Workflow(&'a str),
-}
-
 #[derive(Debug)]
 enum ApplyResult<'a> {
     SplitRange {
@@ -28,19 +14,10 @@ enum ApplyResult<'a> {
     Fail,
 }
 
-#[derive(Debug)]
-enum Rule<'a> {
-    Test {
-        category: char,
-        ordering: Ordering,
-        number: usize,
-        target: Target<'a>,
-    },
-    Target(Target<'a>),
-}
-
-impl<'a> Rule<'a> {
-    fn apply(&self, part: &Part) -> ApplyResult {
+impl Rule<'_> {
+    /// Splits `part` into the ratings this rule sends on and the ratings it
+    /// passes to the next rule.
+    fn split(&self, part: &Part) -> ApplyResult<'_> {
         match self {
             Rule::Target(target) => ApplyResult::Pass(target),
             Rule::Test {
@@ -49,13 +26,8 @@ impl<'a> Rule<'a> {
                 number,
                 target,
             } => {
-                let part_range = match *category {
-                    'x' => &part.x,
-                    'm' => &part.m,
-                    'a' => &part.a,
-                    's' => &part.s,
-                    _ => unreachable!(),
-                };
+                let part_range = part.range(*category);
+                let number = &i64::from(*number);
 
                 if part_range.contains(number) {
                     // If the range contains the number, then we need
@@ -65,76 +37,32 @@ impl<'a> Rule<'a> {
                     // 2. number..end()
                     //
                     // The resulting ranges are a function of the
-                    // ordering, so let's match
+                    // ordering. Rules only ever test with `<` or `>`.
 
-                    match ordering {
-                        &Ordering::Less => {
-                            let range_low = *part_range.start()..=(*number - 1);
-                            let range_high = *number..=*part_range.end();
+                    let split = if *ordering == Ordering::Less {
+                        *number
+                    } else {
+                        *number + 1
+                    };
+                    let range_low = *part_range.start()..=(split - 1);
+                    let range_high = split..=*part_range.end();
 
-                            let mut part_low = part.clone();
-                            let mut part_high = part.clone();
+                    let mut part_low = part.clone();
+                    let mut part_high = part.clone();
 
-                            match *category {
-                                'x' => {
-                                    part_low.x = range_low;
-                                    part_high.x = range_high;
-                                }
-                                'm' => {
-                                    part_low.m = range_low;
-                                    part_high.m = range_high;
-                                }
-                                'a' => {
-                                    part_low.a = range_low;
-                                    part_high.a = range_high;
-                                }
-                                's' => {
-                                    part_low.s = range_low;
-                                    part_high.s = range_high;
-                                }
-                                _ => unreachable!(),
-                            };
+                    *part_low.range_mut(*category) = range_low;
+                    *part_high.range_mut(*category) = range_high;
 
-                            ApplyResult::SplitRange {
-                                pass: (part_low, target),
-                                fail: part_high,
-                            }
+                    if *ordering == Ordering::Less {
+                        ApplyResult::SplitRange {
+                            pass: (part_low, target),
+                            fail: part_high,
                         }
-                        &Ordering::Greater => {
-                            let range_low = *part_range.start()..=*number;
-                            let range_high = (*number + 1)..=*part_range.end();
-
-                            let mut part_low = part.clone();
-                            let mut part_high = part.clone();
-
-                            match *category {
-                                'x' => {
-                                    part_low.x = range_low;
-                                    part_high.x = range_high;
-                                }
-                                'm' => {
-                                    part_low.m = range_low;
-                                    part_high.m = range_high;
-                                }
-                                'a' => {
-                                    part_low.a = range_low;
-                                    part_high.a = range_high;
-                                }
-                                's' => {
-                                    part_low.s = range_low;
-                                    part_high.s = range_high;
-                                }
-                                _ => unreachable!(),
-                            };
-
-                            // info!(?part_low, ?part_high);
-
-                            ApplyResult::SplitRange {
-                                pass: (part_high, target),
-                                fail: part_low,
-                            }
+                    } else {
+                        ApplyResult::SplitRange {
+                            pass: (part_high, target),
+                            fail: part_low,
                         }
-                        _ => unreachable!(),
                     }
                 } else {
                     if (part_range.end() < number && ordering == &Ordering::Less)
@@ -152,10 +80,30 @@ impl<'a> Rule<'a> {
 
 #[derive(Debug, Clone)]
 struct Part {
-    x: RangeInclusive<usize>,
-    m: RangeInclusive<usize>,
-    a: RangeInclusive<usize>,
-    s: RangeInclusive<usize>,
+    x: RangeInclusive<i64>,
+    m: RangeInclusive<i64>,
+    a: RangeInclusive<i64>,
+    s: RangeInclusive<i64>,
+}
+
+impl Part {
+    fn range(&self, category: Category) -> &RangeInclusive<i64> {
+        match category {
+            Category::X => &self.x,
+            Category::M => &self.m,
+            Category::A => &self.a,
+            Category::S => &self.s,
+        }
+    }
+
+    fn range_mut(&mut self, category: Category) -> &mut RangeInclusive<i64> {
+        match category {
+            Category::X => &mut self.x,
+            Category::M => &mut self.m,
+            Category::A => &mut self.a,
+            Category::S => &mut self.s,
+        }
+    }
 }
 
 impl Default for Part {
@@ -169,126 +117,84 @@ impl Default for Part {
     }
 }
 
-#[tracing::instrument(skip(input))]
-fn target<'a>(input: &'a str) -> IResult<&str, Target<'a>> {
-    alt((
-        map(complete::char('A'), |_| Target::Accept),
-        map(complete::char('R'), |_| Target::Reject),
-        map(alpha1, |name| Target::Workflow(name)),
-    ))(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn rule(input: &str) -> IResult<&str, Rule> {
-    alt((
-        map(
-            tuple((
-                one_of("xmas"),
-                one_of("<>"),
-                complete::i32,
-                tag(":"),
-                target,
-            )),
-            |(category, ordering, number, _, target)| {
-                let ordering = match ordering {
-                    '<' => Ordering::Less,
-                    '>' => Ordering::Greater,
-                    _ => unreachable!(),
-                };
-
-                Rule::Test {
-                    category,
-                    ordering,
-                    number: number as usize,
-                    target,
-                }
-            },
-        ),
-        map(target, |target| Rule::Target(target)),
-    ))(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn rules(input: &str) -> IResult<&str, Vec<Rule>> {
-    separated_list1(tag(","), rule)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn workflow(input: &str) -> IResult<&str, (&str, Vec<Rule>)> {
-    tuple((alpha1, delimited(tag("{"), rules, tag("}"))))(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn workflows(input: &str) -> IResult<&str, BTreeMap<&str, Vec<Rule>>> {
-    map(separated_list1(line_ending, workflow), |workflows| {
-        BTreeMap::from_iter(workflows)
-    })(input)
-}
-
 #[tracing::instrument(skip(part, workflows, next_target))]
 fn propagate_parts(
     part: Part,
     workflows: &BTreeMap<&str, Vec<Rule>>,
     next_target: &Target,
-) -> usize {
+    depth: usize,
+) -> Result<i64, SolveError> {
+    // Without a loop in the rules no chain of workflows is longer than
+    // the number of workflows.
+    if depth > workflows.len() {
+        return Err(SolveError::unsupported(
+            "workflows send parts round in a loop",
+        ));
+    }
+
     match next_target {
         // easy one: if a part if rejected, it counts as 0 for the
         // total sum
-        Target::Reject => 0,
+        Target::Reject => Ok(0),
 
         // also easy: if a part is accepted we map it to the product
         // of the sizes of its ranges
         Target::Accept => {
             info!(?part);
 
-            (part.x.end() - part.x.start() + 1)
+            Ok((part.x.end() - part.x.start() + 1)
                 * (part.m.end() - part.m.start() + 1)
                 * (part.a.end() - part.a.start() + 1)
-                * (part.s.end() - part.s.start() + 1)
+                * (part.s.end() - part.s.start() + 1))
         }
 
         // if we have a target workflow, we recursively apply the part
         // to each of the rules in the workflow, accumulating the sum.
         Target::Workflow(id) => {
-            let current_workflow = workflows.get(id).expect("should have valid workflow");
+            let current_workflow = workflows
+                .get(id)
+                .ok_or_else(|| SolveError::malformed(format!("unknown workflow {id}")))?;
             let mut current_part = part;
             let mut sum = 0;
 
             for rule in current_workflow.iter() {
-                match rule.apply(&current_part) {
+                match rule.split(&current_part) {
                     ApplyResult::SplitRange { pass, fail } => {
-                        sum += propagate_parts(pass.0, workflows, pass.1);
+                        sum += propagate_parts(pass.0, workflows, pass.1, depth + 1)?;
                         current_part = fail;
                     }
                     ApplyResult::Pass(target) => {
-                        sum += propagate_parts(current_part.clone(), workflows, target);
+                        sum += propagate_parts(current_part.clone(), workflows, target, depth + 1)?;
                     }
                     ApplyResult::Fail => {}
                 }
             }
 
-            sum
+            Ok(sum)
         }
     }
 }
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(system))]
+fn process(system: &System) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, workflows) = workflows(input)?;
-
     let part = Part::default();
-    let result = propagate_parts(part, &workflows, &Target::Workflow("in"));
+    let result = propagate_parts(part, &system.workflows, &Target::Workflow("in"), 0)?;
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(system))]
+pub fn solve_part2(system: &System) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
 
-    process(input).context("process part 2")
+    process(system).context("process part 2")
+}
+
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -314,7 +220,7 @@ hdj{m>838:A,pv}
 {x=2036,m=264,a=79,s=2244}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}"##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "167409079868000");
     }
 }
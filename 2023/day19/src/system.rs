@@ -0,0 +1,303 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{self, alpha1, line_ending},
+    combinator::{map, value},
+    multi::{many1, separated_list1},
+    sequence::{delimited, separated_pair, tuple},
+    IResult,
+};
+use std::{cmp::Ordering, collections::BTreeMap, fmt};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target<'a> {
+    Accept,
+    Reject,
+    Workflow(&'a str),
+}
+
+impl fmt::Display for Target<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Accept => write!(f, "A"),
+            Target::Reject => write!(f, "R"),
+            Target::Workflow(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let category = match self {
+            Category::X => 'x',
+            Category::M => 'm',
+            Category::A => 'a',
+            Category::S => 's',
+        };
+
+        write!(f, "{category}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule<'a> {
+    Test {
+        category: Category,
+        ordering: Ordering,
+        number: i32,
+        target: Target<'a>,
+    },
+    Target(Target<'a>),
+}
+
+/// Written as a rule of a workflow, like `a<2006:qkq` or `rfg`.
+impl fmt::Display for Rule<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Test {
+                category,
+                ordering,
+                number,
+                target,
+            } => {
+                let ordering = if *ordering == Ordering::Less {
+                    '<'
+                } else {
+                    '>'
+                };
+                write!(f, "{category}{ordering}{number}:{target}")
+            }
+            Rule::Target(target) => write!(f, "{target}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub x: i32,
+    pub m: i32,
+    pub a: i32,
+    pub s: i32,
+}
+
+/// Written as a line of the input, like `{x=787,m=2655,a=1222,s=2876}`.
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Part { x, m, a, s } = self;
+        write!(f, "{{x={x},m={m},a={a},s={s}}}")
+    }
+}
+
+impl Part {
+    pub fn rating(&self, category: Category) -> i32 {
+        match category {
+            Category::X => self.x,
+            Category::M => self.m,
+            Category::A => self.a,
+            Category::S => self.s,
+        }
+    }
+}
+
+/// The workflows by name and the parts to send through them. Part 2 only
+/// reads the workflows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct System<'a> {
+    pub workflows: BTreeMap<&'a str, Vec<Rule<'a>>>,
+    pub parts: Vec<Part>,
+}
+
+/// Written as the input: one workflow per line, a blank line, then one
+/// part per line.
+impl fmt::Display for System<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, rules) in &self.workflows {
+            write!(f, "{name}{{")?;
+            for (i, rule) in rules.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "{rule}")?;
+            }
+            writeln!(f, "}}")?;
+        }
+
+        writeln!(f)?;
+        self.parts.iter().try_for_each(|part| writeln!(f, "{part}"))
+    }
+}
+
+#[tracing::instrument(skip(input))]
+fn category(input: &str) -> IResult<&str, Category> {
+    alt((
+        value(Category::X, complete::char('x')),
+        value(Category::M, complete::char('m')),
+        value(Category::A, complete::char('a')),
+        value(Category::S, complete::char('s')),
+    ))(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn ordering(input: &str) -> IResult<&str, Ordering> {
+    alt((
+        value(Ordering::Less, complete::char('<')),
+        value(Ordering::Greater, complete::char('>')),
+    ))(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn target(input: &str) -> IResult<&str, Target<'_>> {
+    alt((
+        map(complete::char('A'), |_| Target::Accept),
+        map(complete::char('R'), |_| Target::Reject),
+        map(alpha1, Target::Workflow),
+    ))(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn rule(input: &str) -> IResult<&str, Rule<'_>> {
+    alt((
+        map(
+            tuple((category, ordering, complete::i32, tag(":"), target)),
+            |(category, ordering, number, _, target)| Rule::Test {
+                category,
+                ordering,
+                number,
+                target,
+            },
+        ),
+        map(target, Rule::Target),
+    ))(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn rules(input: &str) -> IResult<&str, Vec<Rule<'_>>> {
+    separated_list1(tag(","), rule)(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn workflow(input: &str) -> IResult<&str, (&str, Vec<Rule<'_>>)> {
+    tuple((alpha1, delimited(tag("{"), rules, tag("}"))))(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn workflows(input: &str) -> IResult<&str, BTreeMap<&str, Vec<Rule<'_>>>> {
+    map(separated_list1(line_ending, workflow), |workflows| {
+        BTreeMap::from_iter(workflows)
+    })(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn xmas(input: &str) -> IResult<&str, (i32, i32, i32, i32)> {
+    map(
+        tuple((
+            tag("x="),
+            complete::i32,
+            tag(",m="),
+            complete::i32,
+            tag(",a="),
+            complete::i32,
+            tag(",s="),
+            complete::i32,
+        )),
+        |(_, x, _, m, _, a, _, s)| (x, m, a, s),
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn part(input: &str) -> IResult<&str, Part> {
+    map(delimited(tag("{"), xmas, tag("}")), |(x, m, a, s)| Part {
+        x,
+        m,
+        a,
+        s,
+    })(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn parts(input: &str) -> IResult<&str, Vec<Part>> {
+    separated_list1(line_ending, part)(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn system(input: &str) -> IResult<&str, System<'_>> {
+    map(
+        separated_pair(workflows, many1(line_ending), parts),
+        |(workflows, parts)| System { workflows, parts },
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use proptest::prelude::*;
+
+    /// Workflows borrow their names from the input, so generated names are
+    /// leaked to live as long as the test. Capitals are left out so that no
+    /// name reads as `A` or `R`.
+    fn any_name() -> impl Strategy<Value = &'static str> {
+        "[a-z]{2,3}".prop_map(|name| &*name.leak())
+    }
+
+    fn any_target() -> impl Strategy<Value = Target<'static>> {
+        prop_oneof![
+            Just(Target::Accept),
+            Just(Target::Reject),
+            any_name().prop_map(Target::Workflow),
+        ]
+    }
+
+    fn any_rules() -> impl Strategy<Value = Vec<Rule<'static>>> {
+        let category =
+            prop::sample::select(vec![Category::X, Category::M, Category::A, Category::S]);
+        let ordering = prop::sample::select(vec![Ordering::Less, Ordering::Greater]);
+        let test = (category, ordering, 1..=4000_i32, any_target()).prop_map(
+            |(category, ordering, number, target)| Rule::Test {
+                category,
+                ordering,
+                number,
+                target,
+            },
+        );
+
+        (prop::collection::vec(test, 0..4), any_target()).prop_map(|(mut rules, fallback)| {
+            rules.push(Rule::Target(fallback));
+            rules
+        })
+    }
+
+    fn any_part() -> impl Strategy<Value = Part> {
+        (1..=4000, 1..=4000, 1..=4000, 1..=4000).prop_map(|(x, m, a, s)| Part { x, m, a, s })
+    }
+
+    fn any_system() -> impl Strategy<Value = System<'static>> {
+        (
+            prop::collection::btree_map(any_name(), any_rules(), 1..20),
+            prop::collection::vec(any_part(), 1..20),
+        )
+            .prop_map(|(workflows, parts)| System { workflows, parts })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in any_system()) {
+            let input = expected.to_string();
+            prop_assert_eq!(parse_all_nom7(system, &input), Ok(expected));
+        }
+
+        #[test]
+        fn crlf_round_trip(expected in any_system()) {
+            let input = expected.to_string().replace('\n', "\r\n");
+            prop_assert_eq!(parse_all_nom7(system, &input), Ok(expected));
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use aoc_parse::{Diagnose, ParseError};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, alpha1, multispace0, newline, space1},
+    combinator::{all_consuming, map, map_opt},
+    multi::separated_list1,
+    sequence::{delimited, pair, separated_pair, terminated, tuple},
+    IResult,
+};
+use std::{collections::BTreeMap, fmt};
+
+/// Cube counts keyed by colour name. Used both for a single handful drawn
+/// from the bag and for the bag's contents.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Cubes<'a>(BTreeMap<&'a str, u32>);
+
+impl<'a> Cubes<'a> {
+    /// Parses a comma separated list such as `12 red, 13 green, 14 blue`.
+    pub fn parse(input: &'a str) -> Result<Self, ParseError> {
+        let (_, cubes) =
+            all_consuming(delimited(multispace0, cubes, multispace0))(input).diagnose(input)?;
+
+        Ok(cubes)
+    }
+
+    pub fn get(&self, colour: &str) -> u32 {
+        self.0.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.0.keys().copied()
+    }
+
+    /// Whether every colour in `self` fits within the amount in `bag`.
+    /// Colours missing from the bag have a limit of zero.
+    pub fn fits_in(&self, bag: &Cubes) -> bool {
+        self.0.iter().all(|(colour, n)| *n <= bag.get(colour))
+    }
+
+    /// Product of the counts of `colours`, treating missing colours as
+    /// zero. `None` if the product does not fit in a `u32`.
+    pub fn power_of(&self, colours: &[&str]) -> Option<u32> {
+        colours
+            .iter()
+            .try_fold(1_u32, |power, colour| power.checked_mul(self.get(colour)))
+    }
+}
+
+impl<'a> FromIterator<(&'a str, u32)> for Cubes<'a> {
+    fn from_iter<T: IntoIterator<Item = (&'a str, u32)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// Written the way the input lists a draw, like `3 blue, 4 red`.
+impl fmt::Display for Cubes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (colour, n)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{n} {colour}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game<'a> {
+    pub id: u32,
+    pub draws: Vec<Cubes<'a>>,
+}
+
+impl<'a> Game<'a> {
+    /// Whether every draw could have come out of `bag`.
+    pub fn possible_with(&self, bag: &Cubes) -> bool {
+        self.draws.iter().all(|draw| draw.fits_in(bag))
+    }
+
+    /// The smallest bag that makes this game possible, covering every colour
+    /// seen in any draw.
+    pub fn minimum_bag(&self) -> Cubes<'a> {
+        let mut bag = BTreeMap::new();
+
+        for (colour, n) in self.draws.iter().flat_map(|draw| draw.0.iter()) {
+            let max = bag.entry(*colour).or_insert(0);
+            *max = (*max).max(*n);
+        }
+
+        Cubes(bag)
+    }
+}
+
+/// Written as a line of the input.
+impl fmt::Display for Game<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+
+        for (i, draw) in self.draws.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{draw}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[tracing::instrument]
+fn cube(input: &str) -> IResult<&str, (&str, u32)> {
+    map(
+        separated_pair(complete::u32, space1, alpha1),
+        |(quantity, colour)| (colour, quantity),
+    )(input)
+}
+
+#[tracing::instrument]
+fn cubes(input: &str) -> IResult<&str, Cubes<'_>> {
+    // Repeated colours within one draw are added together, and a draw
+    // whose total for a colour does not fit in a `u32` is rejected.
+    map_opt(
+        separated_list1(terminated(tag(","), space1), cube),
+        |cubes| {
+            let mut draw = BTreeMap::new();
+
+            for (colour, n) in cubes {
+                let total = draw.entry(colour).or_insert(0_u32);
+                *total = total.checked_add(n)?;
+            }
+
+            Some(Cubes(draw))
+        },
+    )(input)
+}
+
+#[tracing::instrument]
+fn game(input: &str) -> IResult<&str, Game<'_>> {
+    map(
+        pair(
+            map(
+                tuple((tag("Game "), complete::u32, tag(": "))),
+                |(_, id, _)| id,
+            ),
+            separated_list1(tag("; "), cubes),
+        ),
+        |(id, draws)| Game { id, draws },
+    )(input)
+}
+
+#[tracing::instrument]
+fn games(input: &str) -> IResult<&str, Vec<Game<'_>>> {
+    separated_list1(newline, game)(input)
+}
+
+/// Parses a whole puzzle input, rejecting anything that is not a game.
+#[tracing::instrument]
+pub fn parse_games(input: &str) -> Result<Vec<Game<'_>>, ParseError> {
+    let (_, games) = all_consuming(terminated(games, multispace0))(input).diagnose(input)?;
+
+    Ok(games)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn any_cubes() -> impl Strategy<Value = Cubes<'static>> {
+        let colour = prop::sample::select(vec!["red", "green", "blue", "teal", "purple"]);
+        prop::collection::btree_map(colour, 0..100_u32, 1..5).prop_map(Cubes)
+    }
+
+    fn any_game() -> impl Strategy<Value = Game<'static>> {
+        (1..1000_u32, prop::collection::vec(any_cubes(), 1..6))
+            .prop_map(|(id, draws)| Game { id, draws })
+    }
+
+    /// A game written with any run of spaces and tabs where `space1` takes
+    /// one, paired with the game it reads as.
+    fn any_spaced_game() -> impl Strategy<Value = (Game<'static>, String)> {
+        let colour = prop::sample::select(vec!["red", "green", "blue", "teal", "purple"]);
+        let gap = || "[ \t]{1,3}";
+        let draw = prop::collection::btree_map(colour, (0..100_u32, gap(), gap()), 1..5);
+
+        (1..1000_u32, prop::collection::vec(draw, 1..6)).prop_map(|(id, draws)| {
+            let written = draws
+                .iter()
+                .map(|draw| {
+                    draw.iter()
+                        .enumerate()
+                        .map(|(i, (colour, (n, before, after)))| {
+                            let comma = if i > 0 {
+                                format!(",{after}")
+                            } else {
+                                String::new()
+                            };
+                            format!("{comma}{n}{before}{colour}")
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+            let game = Game {
+                id,
+                draws: draws
+                    .into_iter()
+                    .map(|draw| {
+                        draw.into_iter()
+                            .map(|(colour, (n, ..))| (colour, n))
+                            .collect()
+                    })
+                    .collect(),
+            };
+
+            (game, format!("Game {id}: {}", written.join("; ")))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn games_round_trip(games in prop::collection::vec(any_game(), 1..10)) {
+            let input = games.iter().map(|game| format!("{game}\n")).collect::<String>();
+            prop_assert_eq!(parse_games(&input).unwrap(), games);
+        }
+
+        #[test]
+        fn spaced_games_round_trip(games in prop::collection::vec(any_spaced_game(), 1..10)) {
+            let input = games.iter().map(|(_, line)| format!("{line}\n")).collect::<String>();
+            let expected = games.into_iter().map(|(game, _)| game).collect::<Vec<_>>();
+            prop_assert_eq!(parse_games(&input).unwrap(), expected);
+        }
+
+        #[test]
+        fn cubes_round_trip(cubes in any_cubes()) {
+            let input = cubes.to_string();
+            prop_assert_eq!(Cubes::parse(&input).unwrap(), cubes);
+        }
+    }
+
+    #[test]
+    fn arbitrary_colours() {
+        let games = parse_games("Game 7: 2 purple, 1 red; 3 purple, 4 teal\n").unwrap();
+        let bag = Cubes::parse("3 purple, 1 red, 4 teal").unwrap();
+
+        assert!(games[0].possible_with(&bag));
+        assert!(!games[0].possible_with(&Cubes::parse("3 purple, 1 red").unwrap()));
+        assert_eq!(
+            games[0].minimum_bag(),
+            Cubes::from_iter([("purple", 3), ("red", 1), ("teal", 4)])
+        );
+        assert_eq!(
+            games[0].minimum_bag().power_of(&["purple", "teal"]),
+            Some(12)
+        );
+    }
+
+    #[test]
+    fn repeated_colours_overflow() {
+        let input = "Game 1: 4000000000 red, 4000000000 red\n";
+
+        assert!(parse_games(input).is_err());
+        assert_eq!(
+            parse_games("Game 1: 4000000000 red, 1 red\n").unwrap()[0].draws[0].get("red"),
+            4000000001
+        );
+    }
+
+    #[test]
+    fn power_overflows() {
+        let cubes = Cubes::from_iter([("red", u32::MAX), ("blue", 2)]);
+
+        assert_eq!(cubes.power_of(&["red"]), Some(u32::MAX));
+        assert_eq!(cubes.power_of(&["red", "blue"]), None);
+    }
+
+    #[test]
+    fn unknown_tokens_are_errors() {
+        assert!(parse_games("Game 1: 3 blue, 4 red; ??? green").is_err());
+        assert!(parse_games("Game 1: 3 blue\nnot a game").is_err());
+        assert!(Cubes::parse("12 red, lots of green").is_err());
+    }
+}
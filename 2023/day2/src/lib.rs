@@ -1,5 +1,41 @@
+mod game;
 mod part1;
 mod part2;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use game::{parse_games, Cubes, Game};
+pub use part1::{part1, process_with, solve_part1};
+pub use part2::{part2, solve_part2};
+
+use anyhow::{Context, Result};
+use aoc_core::Solution;
+use std::fmt::Display;
+
+/// The games both parts look at.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Vec<Game<'static>>> {
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_games(input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 2;
+
+    type Input = Vec<Game<'static>>;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+}
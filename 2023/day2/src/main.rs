@@ -1,12 +1,19 @@
 use anyhow::Result;
-use day2::{part1, part2};
+use aoc_telemetry::{init_telemetry, Telemetry};
+use day2::{parse, solve_part1, solve_part2};
 
 #[tracing::instrument]
 fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
+    let _telemetry = init_telemetry(&Telemetry::default())?;
 
-    part1()?;
-    part2()?;
+    let input = include_str!("../input.txt");
+    let parsed = parse(input)?;
+
+    let result = solve_part1(&parsed)?;
+    println!("Part 1: {result}");
+
+    let result = solve_part2(&parsed)?;
+    println!("Part 2: {result}");
 
     Ok(())
 }
@@ -0,0 +1,281 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending},
+    combinator::map,
+    multi::separated_list1,
+    sequence::tuple,
+    IResult,
+};
+use std::{collections::BTreeMap, fmt};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    On,
+    Off,
+}
+
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub enum Signal {
+    High,
+    Low,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModuleType<'a> {
+    Broadcaster,
+    Conjunction { inputs: BTreeMap<&'a str, Signal> },
+    FlipFlop { status: Status },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module<'a> {
+    pub name: &'a str,
+    pub outputs: Vec<&'a str>,
+    pub module_type: ModuleType<'a>,
+}
+
+/// Written as a line of the input, like `%a -> inv, con`. A module's
+/// state is not part of it.
+impl fmt::Display for Module<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self.module_type {
+            ModuleType::Broadcaster => "",
+            ModuleType::Conjunction { .. } => "&",
+            ModuleType::FlipFlop { .. } => "%",
+        };
+
+        write!(f, "{prefix}{} -> {}", self.name, self.outputs.join(", "))
+    }
+}
+
+impl Module<'_> {
+    pub fn process_signal(
+        &mut self,
+        src: String,
+        signal: &Signal,
+    ) -> Vec<(String, String, Signal)> {
+        match &mut self.module_type {
+            ModuleType::Broadcaster => self
+                .outputs
+                .iter()
+                .map(|dst| (self.name.to_string(), dst.to_string(), *signal))
+                .collect::<Vec<(String, String, Signal)>>(),
+            ModuleType::Conjunction { ref mut inputs } => {
+                // if it remembers high pulses for all inputs, it sends
+                // a low pulse; otherwise, it sends a high pulse.
+                if let Some(memory) = inputs.get_mut(src.as_str()) {
+                    *memory = *signal;
+                }
+
+                let output_signal = if inputs.values().all(|signal| signal == &Signal::High) {
+                    Signal::Low
+                } else {
+                    Signal::High
+                };
+
+                self.outputs
+                    .iter()
+                    .map(|dst| (self.name.to_string(), dst.to_string(), output_signal))
+                    .collect::<Vec<(String, String, Signal)>>()
+            }
+            ModuleType::FlipFlop { ref mut status } => match (&status, signal) {
+                (_, &Signal::High) => vec![],
+                (&Status::On, &Signal::Low) => {
+                    *status = Status::Off;
+
+                    self.outputs
+                        .iter()
+                        .map(|dst| (self.name.to_string(), dst.to_string(), Signal::Low))
+                        .collect::<Vec<(String, String, Signal)>>()
+                }
+                (&Status::Off, &Signal::Low) => {
+                    *status = Status::On;
+
+                    self.outputs
+                        .iter()
+                        .map(|dst| (self.name.to_string(), dst.to_string(), Signal::High))
+                        .collect::<Vec<(String, String, Signal)>>()
+                }
+            },
+        }
+    }
+}
+
+#[tracing::instrument(skip(input))]
+fn outputs(input: &str) -> IResult<&str, Vec<&str>> {
+    separated_list1(tag(", "), alpha1)(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn broadcaster(input: &str) -> IResult<&str, (&str, Module<'_>)> {
+    map(
+        tuple((tag("broadcaster"), tag(" -> "), outputs)),
+        |(name, _, outputs)| {
+            (
+                name,
+                Module {
+                    name,
+                    outputs,
+                    module_type: ModuleType::Broadcaster,
+                },
+            )
+        },
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn conjunction(input: &str) -> IResult<&str, (&str, Module<'_>)> {
+    map(
+        tuple((tag("&"), alpha1, tag(" -> "), outputs)),
+        |(_, name, _, outputs)| {
+            (
+                name,
+                Module {
+                    name,
+                    outputs,
+                    module_type: ModuleType::Conjunction {
+                        inputs: BTreeMap::new(),
+                    },
+                },
+            )
+        },
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn flipflop(input: &str) -> IResult<&str, (&str, Module<'_>)> {
+    map(
+        tuple((tag("%"), alpha1, tag(" -> "), outputs)),
+        |(_, name, _, outputs)| {
+            (
+                name,
+                Module {
+                    name,
+                    outputs,
+                    module_type: ModuleType::FlipFlop {
+                        status: Status::Off,
+                    },
+                },
+            )
+        },
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn module(input: &str) -> IResult<&str, (&str, Module<'_>)> {
+    alt((broadcaster, conjunction, flipflop))(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn modules(input: &str) -> IResult<&str, BTreeMap<&str, Module<'_>>> {
+    map(separated_list1(line_ending, module), |modules| {
+        BTreeMap::from_iter(modules)
+    })(input)
+}
+
+/// The modules as they are before the first button press: every
+/// conjunction remembers a low pulse from each of its inputs.
+pub fn wired<'a>(modules: &BTreeMap<&'a str, Module<'a>>) -> BTreeMap<&'a str, Module<'a>> {
+    let mut map = modules.clone();
+
+    let conjunctions = map
+        .iter()
+        .filter_map(|(name, module)| match module.module_type {
+            ModuleType::Conjunction { .. } => Some(name),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let inputs = map.iter().fold(
+        BTreeMap::<&str, Vec<&str>>::new(),
+        |mut acc, (id, module)| {
+            for conjunction in conjunctions.iter() {
+                if module.outputs.contains(conjunction) {
+                    acc.entry(conjunction)
+                        .and_modify(|item| {
+                            item.push(id);
+                        })
+                        .or_insert(vec![id]);
+                }
+            }
+            acc
+        },
+    );
+
+    inputs.into_iter().for_each(|(conjunction, input_modules)| {
+        map.entry(conjunction).and_modify(|module| {
+            if let ModuleType::Conjunction { inputs, .. } = &mut module.module_type {
+                *inputs = input_modules
+                    .into_iter()
+                    .map(|id| (id, Signal::Low))
+                    .collect();
+            }
+        });
+    });
+
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use proptest::prelude::*;
+
+    /// Modules borrow their names from the input, so generated names are
+    /// leaked to live as long as the test.
+    fn any_name() -> impl Strategy<Value = &'static str> {
+        "[a-z]{2,3}".prop_map(|name| &*name.leak())
+    }
+
+    /// Modules as they are parsed: conjunctions do not know their inputs
+    /// yet, and every flip-flop is off.
+    fn any_modules() -> impl Strategy<Value = BTreeMap<&'static str, Module<'static>>> {
+        let outputs = || prop::collection::vec(any_name(), 1..5);
+        let module_type = prop_oneof![
+            Just(ModuleType::Conjunction {
+                inputs: BTreeMap::new()
+            }),
+            Just(ModuleType::FlipFlop {
+                status: Status::Off
+            }),
+        ];
+
+        (
+            prop::collection::btree_map(any_name(), (module_type, outputs()), 1..20),
+            prop::option::of(outputs()),
+        )
+            .prop_map(|(modules, broadcaster)| {
+                let broadcaster =
+                    broadcaster.map(|outputs| ("broadcaster", (ModuleType::Broadcaster, outputs)));
+
+                modules
+                    .into_iter()
+                    .chain(broadcaster)
+                    .map(|(name, (module_type, outputs))| {
+                        let module = Module {
+                            name,
+                            outputs,
+                            module_type,
+                        };
+                        (name, module)
+                    })
+                    .collect()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in any_modules()) {
+            let input = expected.values().map(|module| format!("{module}\n")).collect::<String>();
+            prop_assert_eq!(parse_all_nom7(modules, &input), Ok(expected));
+        }
+
+        #[test]
+        fn crlf_round_trip(expected in any_modules()) {
+            let input = expected.values().map(|module| format!("{module}\r\n")).collect::<String>();
+            prop_assert_eq!(parse_all_nom7(modules, &input), Ok(expected));
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use aoc_core::Rng;
+
+/// A network of `size` counters, built like the puzzle's: the broadcaster
+/// starts each chain of flip-flops counting presses, and a conjunction
+/// watching the chain fires, and resets it, once the count reaches its
+/// period. Each fires `rx`'s feeder through an inverter, so `rx` gets a
+/// low pulse after as many presses as the periods' least common multiple.
+///
+/// Counters have twelve bits, as in the puzzle's input, up to five of
+/// them. Past that they get narrower, so the answer still fits in 64 bits,
+/// down to two bits for the most there can be, 30.
+///
+/// Returns the network with the presses `rx` waits for.
+pub(crate) fn network(rng: &mut Rng, size: usize) -> (String, usize) {
+    let counters = size.clamp(1, 30);
+    let bits = (60 / counters).clamp(2, 12);
+
+    let mut names = ('a'..='z')
+        .flat_map(|a| ('a'..='z').map(move |b| format!("{a}{b}")))
+        .filter(|name| name != "rx")
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut names);
+    let mut names = names.into_iter();
+    let mut name = || names.next().unwrap();
+
+    let feeder = name();
+    let mut modules = vec![format!("&{feeder} -> rx")];
+    let mut starts = Vec::new();
+    let mut presses = 1;
+
+    for _ in 0..counters {
+        // Odd, and using the top bit, like the puzzle's periods.
+        let period = rng.usize(1 << (bits - 1)..1 << bits) | 1;
+        presses = lcm(presses, period);
+
+        let flip_flops = (0..bits).map(|_| name()).collect::<Vec<_>>();
+        let (hub, inverter) = (name(), name());
+
+        let mut resets = vec![flip_flops[0].clone()];
+        for (bit, flip_flop) in flip_flops.iter().enumerate() {
+            let mut outputs = flip_flops
+                .get(bit + 1)
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>();
+            if period & 1 << bit != 0 {
+                outputs.push(hub.clone());
+            } else {
+                resets.push(flip_flop.clone());
+            }
+            modules.push(format!("%{flip_flop} -> {}", outputs.join(", ")));
+        }
+        resets.push(inverter.clone());
+        modules.push(format!("&{hub} -> {}", resets.join(", ")));
+        modules.push(format!("&{inverter} -> {feeder}"));
+        starts.push(flip_flops[0].clone());
+    }
+    modules.push(format!("broadcaster -> {}", starts.join(", ")));
+
+    rng.shuffle(&mut modules);
+    (modules.join("\n") + "\n", presses)
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+
+    a / x * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rx_waits_for_every_counter() {
+        for seed in 0..8 {
+            let (input, presses) = network(
+                &mut Rng::with_seed(seed),
+                [1, 2, 4, 5, 6, 10, 20, 30][seed as usize],
+            );
+            let input = &*input.leak();
+
+            crate::part1(input).unwrap();
+            assert_eq!(crate::part2(input).unwrap(), presses.to_string());
+        }
+    }
+}
@@ -1,5 +1,47 @@
+mod configuration;
+mod generate;
 mod part1;
 mod part2;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use configuration::{modules, wired, Module, ModuleType, Signal, Status};
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+
+use anyhow::{Context, Result};
+use aoc_core::{Rng, Solution};
+use aoc_parse::parse_all_nom7;
+use std::{collections::BTreeMap, fmt::Display};
+
+/// The modules by name, before any of them has seen a pulse.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<BTreeMap<&'static str, Module<'static>>> {
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all_nom7(modules, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 20;
+
+    type Input = BTreeMap<&'static str, Module<'static>>;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate::network(rng, size).0)
+    }
+}
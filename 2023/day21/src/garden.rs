@@ -0,0 +1,46 @@
+use nom::{
+    branch::alt,
+    character::complete::{char, line_ending},
+    combinator::{map_res, value},
+    multi::{many1, separated_list1},
+    IResult,
+};
+use pathfinding::matrix::Matrix;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Tile {
+    Start,
+    Garden,
+    Rock,
+}
+
+#[tracing::instrument(skip(input))]
+fn tile(input: &str) -> IResult<&str, Tile> {
+    alt((
+        value(Tile::Garden, char('.')),
+        value(Tile::Rock, char('#')),
+        value(Tile::Start, char('S')),
+    ))(input)
+}
+
+/// The map of the garden plots and rocks. The elf steps between
+/// neighbouring tiles, so the rows must line up.
+#[tracing::instrument(skip(input))]
+pub fn garden(input: &str) -> IResult<&str, Matrix<Tile>> {
+    map_res(separated_list1(line_ending, many1(tile)), Matrix::from_rows)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_gardens() {
+        let (_, grid) = garden(".#.\n.S.\n").unwrap();
+        assert_eq!(grid[(1, 1)], Tile::Start);
+        assert_eq!(grid[(0, 1)], Tile::Rock);
+
+        assert!(garden("").is_err());
+        assert!(garden("...\n.S\n").is_err());
+    }
+}
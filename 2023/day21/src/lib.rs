@@ -1,5 +1,43 @@
+mod garden;
 mod part1;
 mod part2;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use garden::{garden, Tile};
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+
+use anyhow::{Context, Result};
+use aoc_core::Solution;
+use aoc_parse::parse_all_nom7;
+use pathfinding::matrix::Matrix;
+use std::fmt::Display;
+
+/// The garden map, which part 2 repeats in every direction.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Matrix<Tile>> {
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all_nom7(garden, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 21;
+
+    type Input = Matrix<Tile>;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+}
@@ -1,46 +1,27 @@
+use crate::{parse, Tile};
 use anyhow::{Context, Result};
-use pathfinding::{
-    directed::dijkstra::dijkstra_reach,
-    matrix::{directions, Matrix},
-};
+use aoc_core::SolveError;
+use aoc_grid::Direction4;
+use pathfinding::{directed::dijkstra::dijkstra_reach, matrix::Matrix};
 use tracing::info;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-enum Tile {
-    Start,
-    Garden,
-    Rock,
-}
-
 const STEPS_AMOUNT: usize = 64 - 1;
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+fn process(grid: &Matrix<Tile>) -> Result<String, SolveError> {
     info!("processing input");
 
-    let grid = input
-        .lines()
-        .map(|line| {
-            line.chars().map(|c| match c {
-                '.' => Tile::Garden,
-                '#' => Tile::Rock,
-                'S' => Tile::Start,
-                _ => unreachable!("invalid input"),
-            })
-        })
-        .collect::<Matrix<Tile>>();
-
     let start_position = grid
         .items()
         .find(|(_, tile)| *tile == &Tile::Start)
         .map(|(pos, _)| pos)
-        .expect("should have a start position");
+        .ok_or_else(|| SolveError::malformed("no start tile"))?;
 
     let result = dijkstra_reach(&start_position, |&pos, cost| {
-        let successors = directions::DIRECTIONS_4
+        let successors = Direction4::ALL
             .into_iter()
             .filter_map(|direction| {
-                if let Some(neighbor_position) = grid.move_in_direction(pos, direction) {
+                if let Some(neighbor_position) = grid.move_in_direction(pos, direction.row_col()) {
                     let tile = *grid.get(neighbor_position).expect("should have a neighbor");
 
                     if tile == Tile::Rock || cost > STEPS_AMOUNT {
@@ -62,9 +43,14 @@ fn process(input: &'static str) -> Result<String> {
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+pub fn solve_part1(grid: &Matrix<Tile>) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
+
+    process(grid).context("process part 1")
+}
 
-    process(input).context("process part 1")
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
@@ -1,40 +1,27 @@
+use crate::{parse, Tile};
 use anyhow::{Context, Result};
-use pathfinding::{
-    directed::dijkstra::dijkstra_reach,
-    matrix::{directions, Matrix},
-};
+use aoc_core::SolveError;
+use aoc_grid::Direction4;
+use pathfinding::{directed::dijkstra::dijkstra_reach, matrix::Matrix};
 use tracing::info;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-enum Tile {
-    Start,
-    Garden,
-    Rock,
-}
-
 const STEPS_AMOUNT: usize = 26501365;
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+fn process(grid: &Matrix<Tile>) -> Result<String, SolveError> {
     info!("processing input");
 
-    let grid = input
-        .lines()
-        .map(|line| {
-            line.chars().map(|c| match c {
-                '.' => Tile::Garden,
-                '#' => Tile::Rock,
-                'S' => Tile::Start,
-                _ => unreachable!("invalid input"),
-            })
-        })
-        .collect::<Matrix<Tile>>();
-
     let start_position = grid
         .items()
         .find(|(_, tile)| *tile == &Tile::Start)
         .map(|(pos, _)| pos)
-        .expect("should have a start position");
+        .ok_or_else(|| SolveError::malformed("no start tile"))?;
+
+    // The quadratic below only holds for square gardens that repeat
+    // evenly in every direction.
+    if grid.rows != grid.columns {
+        return Err(SolveError::unsupported("garden is not square"));
+    }
 
     let divisor = STEPS_AMOUNT / (grid.columns);
     let rem = STEPS_AMOUNT % (grid.columns);
@@ -42,18 +29,18 @@ fn process(input: &'static str) -> Result<String> {
     info!(?divisor, ?rem);
 
     // for n in 0..3 {
+    #[allow(unused)]
     let n = 1;
     let coeff = dijkstra_reach(&start_position, |&pos, cost| {
-        let successors = directions::DIRECTIONS_4
+        let successors = Direction4::ALL
             .into_iter()
             .filter_map(|direction| {
-                let npos = match (grid.move_in_direction(pos, direction), direction) {
+                let npos = match (grid.move_in_direction(pos, direction.row_col()), direction) {
                     (Some(p), _) => p,
-                    (_, directions::N) => (grid.rows - 1, pos.1),
-                    (_, directions::S) => (0, pos.1),
-                    (_, directions::E) => (pos.0, 0),
-                    (_, directions::W) => (pos.0, grid.columns - 1),
-                    _ => unreachable!(),
+                    (_, Direction4::North) => (grid.rows - 1, pos.1),
+                    (_, Direction4::South) => (0, pos.1),
+                    (_, Direction4::East) => (pos.0, 0),
+                    (_, Direction4::West) => (pos.0, grid.columns - 1),
                 };
 
                 let tile = *grid.get(npos).expect("should have a neighbor");
@@ -87,11 +74,16 @@ fn process(input: &'static str) -> Result<String> {
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(grid))]
+pub fn solve_part2(grid: &Matrix<Tile>) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
 
-    process(input).context("process part 2")
+    process(grid).context("process part 2")
+}
+
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -111,7 +103,7 @@ mod tests {
 .##.#.####.
 .##..##.##.
 ..........."##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "16");
     }
 }
@@ -0,0 +1,72 @@
+use aoc_core::Rng;
+use std::collections::HashSet;
+
+/// A snapshot of `size` bricks falling onto a ten by ten area, a few to
+/// each level as in the puzzle's input. No two bricks share a cube.
+pub(crate) fn snapshot(rng: &mut Rng, size: usize) -> String {
+    let levels = (size / 3).max(1) as i32;
+    let mut taken = HashSet::new();
+    let mut snapshot = String::new();
+
+    for _ in 0..size {
+        loop {
+            let start = [rng.i32(0..10), rng.i32(0..10), rng.i32(1..=levels)];
+            // Bricks run along one axis, up to four cubes long.
+            let axis = rng.usize(0..3);
+            let mut end = start;
+            end[axis] += rng.i32(0..4);
+            if end[0] > 9 || end[1] > 9 {
+                continue;
+            }
+
+            let cubes = (0..=end[axis] - start[axis])
+                .map(|offset| {
+                    let mut cube = start;
+                    cube[axis] += offset;
+                    cube
+                })
+                .collect::<Vec<_>>();
+            if cubes.iter().any(|cube| taken.contains(cube)) {
+                continue;
+            }
+
+            taken.extend(cubes);
+            let [x1, y1, z1] = start;
+            let [x2, y2, z2] = end;
+            snapshot += &format!("{x1},{y1},{z1}~{x2},{y2},{z2}\n");
+            break;
+        }
+    }
+
+    snapshot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bricks_do_not_overlap() {
+        let snapshot = snapshot(&mut Rng::with_seed(22), 300);
+
+        let mut cubes = Vec::new();
+        for line in snapshot.lines() {
+            let n = line
+                .split(['~', ','])
+                .map(|n| n.parse::<i32>().unwrap())
+                .collect::<Vec<_>>();
+            assert!(n[2] >= 1 && n[3] < 10 && n[4] < 10);
+
+            for x in n[0]..=n[3] {
+                for y in n[1]..=n[4] {
+                    for z in n[2]..=n[5] {
+                        cubes.push((x, y, z));
+                    }
+                }
+            }
+        }
+
+        assert_eq!(snapshot.lines().count(), 300);
+        assert_eq!(cubes.iter().collect::<HashSet<_>>().len(), cubes.len());
+    }
+}
@@ -1,5 +1,47 @@
+mod generate;
 mod part1;
 mod part2;
+mod snapshot;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+pub use snapshot::{bricks, Brick, Coordinate};
+
+use anyhow::{Context, Result};
+use aoc_core::{Rng, Solution};
+use aoc_parse::parse_all_nom7;
+use std::fmt::Display;
+
+/// The bricks in the snapshot, as they were caught falling.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Vec<Brick>> {
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all_nom7(bricks, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 22;
+
+    type Input = Vec<Brick>;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate::snapshot(rng, size))
+    }
+}
@@ -1,35 +1,23 @@
+use crate::{parse, Brick};
 use anyhow::{Context, Result};
-use glam::IVec3;
+use aoc_core::SolveError;
 use itertools::Itertools;
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, alpha1, line_ending, space0, space1},
-    combinator::{map, recognize},
-    multi::{many1, separated_list1},
-    sequence::{pair, separated_pair, terminated, tuple},
-    IResult,
-};
 use tracing::info;
 
-type Coordinate = IVec3;
-
-#[derive(Debug, Clone, Copy)]
-struct Brick {
-    start: Coordinate,
-    end: Coordinate,
-}
-
+// Still to be used by the settling sketched out in `process`.
+#[allow(unused)]
 impl Brick {
     fn minimum_height(&self) -> i32 {
         self.start.z.min(self.end.z)
     }
 
     fn parallel(&self, other: &Brick) -> bool {
-        let line1 = self.end - self.start;
-        let line2 = other.start - other.end;
+        // Widened so that bricks at the far ends of the `i32` range can
+        // neither overflow the subtraction nor the cross product.
+        let line1 = self.end.as_i64vec3() - self.start.as_i64vec3();
+        let line2 = other.start.as_i64vec3() - other.end.as_i64vec3();
 
-        line1.x * line2.y - line2.x * line1.y == 0
+        i128::from(line1.x) * i128::from(line2.y) == i128::from(line2.x) * i128::from(line1.y)
     }
 
     fn is_disintegratable(&self, bricks: &[Brick]) -> bool {
@@ -85,38 +73,11 @@ impl Brick {
     }
 }
 
-#[tracing::instrument(skip(input))]
-fn coordinate(input: &str) -> IResult<&str, IVec3> {
-    map(
-        tuple((
-            complete::i32,
-            tag(","),
-            complete::i32,
-            tag(","),
-            complete::i32,
-        )),
-        |(x, _, y, _, z)| IVec3::new(x, y, z),
-    )(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn brick(input: &str) -> IResult<&str, Brick> {
-    map(
-        separated_pair(coordinate, tag("~"), coordinate),
-        |(start, end)| Brick { start, end },
-    )(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn bricks(input: &str) -> IResult<&str, Vec<Brick>> {
-    separated_list1(line_ending, brick)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(bricks))]
+fn process(bricks: &[Brick]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, mut bricks) = bricks(input)?;
+    let mut bricks = bricks.to_vec();
 
     bricks.sort_by(|a, b| {
         a.start
@@ -163,11 +124,16 @@ fn process(input: &'static str) -> Result<String> {
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(bricks))]
+pub fn solve_part1(bricks: &[Brick]) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
 
-    process(input).context("process part 1")
+    process(bricks).context("process part 1")
+}
+
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -183,7 +149,7 @@ mod tests {
 2,0,5~2,2,5
 0,1,6~2,1,6
 1,1,8~1,1,9"##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "5");
     }
 }
@@ -1,27 +1,25 @@
+use crate::{parse, Brick};
 use anyhow::{Context, Result};
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, alpha1, line_ending, space0, space1},
-    combinator::{map, recognize},
-    multi::{many1, separated_list1},
-    sequence::{pair, terminated, tuple},
-    IResult,
-};
+use aoc_core::SolveError;
 use tracing::info;
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(_bricks))]
+fn process(_bricks: &[Brick]) -> Result<String, SolveError> {
     info!("processing input");
 
     Ok("".to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(bricks))]
+pub fn solve_part2(bricks: &[Brick]) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
+
+    process(bricks).context("process part 2")
+}
 
-    process(input).context("process part 2")
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -31,7 +29,7 @@ mod tests {
     #[test_log::test]
     fn it_works() {
         let input = r##""##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "42");
     }
 }
@@ -0,0 +1,91 @@
+use glam::IVec3;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, line_ending},
+    combinator::map,
+    multi::separated_list1,
+    sequence::{separated_pair, tuple},
+    IResult,
+};
+use std::fmt;
+
+pub type Coordinate = IVec3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Brick {
+    pub start: Coordinate,
+    pub end: Coordinate,
+}
+
+/// Written as a line of the input, like `1,0,1~1,2,1`.
+impl fmt::Display for Brick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Brick { start, end } = self;
+        write!(
+            f,
+            "{},{},{}~{},{},{}",
+            start.x, start.y, start.z, end.x, end.y, end.z
+        )
+    }
+}
+
+#[tracing::instrument(skip(input))]
+fn coordinate(input: &str) -> IResult<&str, IVec3> {
+    map(
+        tuple((
+            complete::i32,
+            tag(","),
+            complete::i32,
+            tag(","),
+            complete::i32,
+        )),
+        |(x, _, y, _, z)| IVec3::new(x, y, z),
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn brick(input: &str) -> IResult<&str, Brick> {
+    map(
+        separated_pair(coordinate, tag("~"), coordinate),
+        |(start, end)| Brick { start, end },
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn bricks(input: &str) -> IResult<&str, Vec<Brick>> {
+    separated_list1(line_ending, brick)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use proptest::prelude::*;
+
+    /// A straight brick of cubes along one axis, above the ground.
+    fn any_brick() -> impl Strategy<Value = Brick> {
+        let axis = prop::sample::select(vec![IVec3::X, IVec3::Y, IVec3::Z]);
+
+        (0..10, 0..10, 1..300, axis, 0..5).prop_map(|(x, y, z, axis, len)| {
+            let start = IVec3::new(x, y, z);
+            Brick {
+                start,
+                end: start + axis * len,
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in prop::collection::vec(any_brick(), 1..50)) {
+            let input = expected.iter().map(|brick| format!("{brick}\n")).collect::<String>();
+            prop_assert_eq!(parse_all_nom7(bricks, &input), Ok(expected));
+        }
+
+        #[test]
+        fn crlf_round_trip(expected in prop::collection::vec(any_brick(), 1..50)) {
+            let input = expected.iter().map(|brick| format!("{brick}\r\n")).collect::<String>();
+            prop_assert_eq!(parse_all_nom7(bricks, &input), Ok(expected));
+        }
+    }
+}
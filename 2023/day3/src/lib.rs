@@ -1,5 +1,42 @@
 mod part1;
 mod part2;
+mod schematic;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+pub use schematic::{schematic, Number, Schematic, Span, Symbol};
+
+use anyhow::{Context, Result};
+use aoc_core::Solution;
+use aoc_parse::parse_all_located;
+use std::fmt::Display;
+
+/// The schematic both parts look for part numbers in.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Schematic> {
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all_located(schematic, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 3;
+
+    type Input = Schematic;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+}
@@ -1,108 +1,33 @@
+use crate::{parse, schematic::Schematic};
 use anyhow::{Context, Result};
-use glam::IVec2;
-use nom::{
-    branch::alt,
-    bytes::complete::{is_not, take_till1},
-    character::complete::digit1,
-    combinator::iterator,
-    IResult, Parser,
-};
-use nom_locate::LocatedSpan;
-
-type Span<'a> = LocatedSpan<&'a str>;
-type SpanCoord<'a> = LocatedSpan<&'a str, IVec2>;
-
-#[derive(Debug, PartialEq)]
-enum Value<'a> {
-    Empty,
-    Symbol(SpanCoord<'a>),
-    Number(SpanCoord<'a>),
-}
-
-#[tracing::instrument]
-fn coord(input: Span) -> SpanCoord {
-    let x = input.get_column() as i32 - 1;
-    let y = input.location_line() as i32 - 1;
-    input.map_extra(|_| IVec2::new(x, y))
-}
-
-#[tracing::instrument]
-fn schematic(input: Span) -> IResult<Span, Vec<Value>> {
-    let mut it = iterator(
-        input,
-        alt((
-            digit1
-                .map(|span| coord(span))
-                .map(Value::Number),
-            is_not(".\n0123456789")
-                .map(|span| coord(span))
-                .map(Value::Symbol),
-            take_till1(|c: char| c.is_ascii_digit() || c != '.' && c != '\n').map(|_| Value::Empty),
-        )),
-    );
-
-    let parsed = it
-        .filter(|value| value != &Value::Empty)
-        .collect::<Vec<Value>>();
-    let res: IResult<_, _> = it.finish();
-
-    res.map(|(input, _)| (input, parsed))
-}
-
-#[tracing::instrument]
-fn process(input: &'static str) -> Result<String> {
-    let (_, schematic) = schematic(Span::new(input))?;
+use aoc_core::SolveError;
 
+#[tracing::instrument(skip(schematic))]
+fn process(schematic: &Schematic) -> Result<String, SolveError> {
     let sum = schematic
+        .numbers
         .iter()
-        .filter_map(|value| {
-            let Value::Number(num) = value else {
-                return None;
-            };
-            let neighbors = [
-                // east border
-                IVec2::new(num.fragment().len() as i32, 0),
-                // west border
-                IVec2::new(-1, 0),
-            ]
-            .into_iter()
-            .chain(
-                // north border
-                (-1..=num.fragment().len() as i32).map(|x_offset| IVec2::new(x_offset, 1)),
-            )
-            .chain(
-                // south border
-                (-1..=num.fragment().len() as i32).map(|x_offset| IVec2::new(x_offset, -1)),
-            )
-            .map(|pos| pos + num.extra)
-            .collect::<Vec<IVec2>>();
-
+        .filter(|number| {
             schematic
+                .symbols
                 .iter()
-                .any(|symbol| {
-                    let Value::Symbol(sym) = symbol else {
-                        return false;
-                    };
-                    neighbors.iter().any(|pos| &pos == &&sym.extra)
-                })
-                .then_some(
-                    num.fragment()
-                        .parse::<u32>()
-                        .expect("should be a valid number"),
-                )
+                .any(|symbol| number.is_adjacent(symbol.position))
         })
-        .sum::<u32>();
+        .map(|number| u64::from(number.value))
+        .sum::<u64>();
 
     Ok(sum.to_string())
 }
 
-#[tracing::instrument]
-pub fn part1() -> Result<()> {
-    let file = include_str!("../input1.txt");
-    let result = process(file).context("process part 1")?;
-    println!("Part 1: {}", result);
+#[tracing::instrument(skip(schematic))]
+pub fn solve_part1(schematic: &Schematic) -> Result<String> {
+    let _frame = aoc_telemetry::frame!("part 1");
+
+    process(schematic).context("process part 1")
+}
 
-    Ok(())
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -122,7 +47,7 @@ mod tests {
 ...$.*....
 .664.598..
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "4361");
     }
 }
@@ -1,134 +1,48 @@
+use crate::{parse, schematic::Schematic};
 use anyhow::{Context, Result};
+use aoc_core::SolveError;
 use glam::IVec2;
-use nom::{
-    branch::alt,
-    bytes::complete::{is_a, take_till1},
-    character::complete::digit1,
-    combinator::iterator,
-    IResult, Parser,
-};
-use nom_locate::LocatedSpan;
+use itertools::Itertools;
+use std::collections::HashMap;
 
-type Span<'a> = LocatedSpan<&'a str>;
-type SpanCoord<'a> = LocatedSpan<&'a str, IVec2>;
-
-#[derive(Debug, PartialEq)]
-enum Value<'a> {
-    Empty,
-    Symbol(SpanCoord<'a>),
-    Number(SpanCoord<'a>),
-}
-
-#[tracing::instrument]
-fn coord(input: Span) -> SpanCoord {
-    let x = input.get_column() as i32 - 1;
-    let y = input.location_line() as i32 - 1;
-    input.map_extra(|_| IVec2::new(x, y))
-}
-
-#[tracing::instrument]
-fn schematic(input: Span) -> IResult<Span, Vec<Value>> {
-    let mut it = iterator(
-        input,
-        alt((
-            digit1
-                .map(|span| coord(span))
-                .map(Value::Number),
-            is_a("*").map(|span| coord(span)).map(Value::Symbol),
-            take_till1(|c: char| c.is_ascii_digit() || c == '*' && c != '\n').map(|_| Value::Empty),
-        )),
-    );
-
-    let parsed = it
-        .filter(|value| value != &Value::Empty)
-        .collect::<Vec<Value>>();
-    let res: IResult<_, _> = it.finish();
-
-    res.map(|(input, _)| (input, parsed))
-}
-
-#[tracing::instrument]
-fn process(input: &'static str) -> Result<String> {
-    let (_, schematic) = schematic(Span::new(input))?;
-
-    let numbers = schematic
+#[tracing::instrument(skip(schematic))]
+fn process(schematic: &Schematic) -> Result<String, SolveError> {
+    let gears = schematic
+        .symbols
         .iter()
-        .filter_map(|value| {
-            let Value::Number(num) = value else {
-                return None;
-            };
-
-            let neighbors = [
-                // east border
-                IVec2::new(num.fragment().len() as i32, 0),
-                // west border
-                IVec2::new(-1, 0),
-            ]
-            .into_iter()
-            .chain(
-                // north border
-                (-1..=num.fragment().len() as i32).map(|x_offset| IVec2::new(x_offset, 1)),
-            )
-            .chain(
-                // south border
-                (-1..=num.fragment().len() as i32).map(|x_offset| IVec2::new(x_offset, -1)),
-            )
-            .map(|pos| pos + num.extra)
-            .collect::<Vec<IVec2>>();
-
-            let mut symbol_pos = IVec2::new(0, 0);
-
-            schematic
-                .iter()
-                .any(|symbol| {
-                    let Value::Symbol(sym) = symbol else {
-                        return false;
-                    };
-                    neighbors
-                        .iter()
-                        .find(|pos| {
-                            let r = pos == &&sym.extra;
-                            if r {
-                                symbol_pos = sym.extra;
-                            }
-
-                            r
-                        })
-                        .is_some()
-                })
-                .then_some(
-                    num.fragment()
-                        .parse::<u32>()
-                        .expect("should be a valid number"),
-                )
-                .map(|n| (symbol_pos, n))
-        })
+        .filter(|symbol| symbol.symbol == '*')
         .collect::<Vec<_>>();
 
-    let mut sum = 0;
-    for (pos1, number) in numbers.iter().enumerate() {
-        for (pos2, other) in numbers.iter().enumerate() {
-            if number.0 == other.0 && pos1 != pos2 {
-                sum += number.1 * other.1;
-            }
+    // Each number belongs to the first gear it touches.
+    let mut gear_numbers = HashMap::<IVec2, Vec<u64>>::new();
+    for number in &schematic.numbers {
+        if let Some(gear) = gears.iter().find(|gear| number.is_adjacent(gear.position)) {
+            gear_numbers
+                .entry(gear.position)
+                .or_default()
+                .push(u64::from(number.value));
         }
     }
 
-    // approach above will add every pair twice, divide by 2
-    // here. This is real ugly, there's probably a much better way of
-    // doing this.
-    sum /= 2;
+    // Every pair of numbers on the same gear adds its ratio.
+    let sum = gear_numbers
+        .values()
+        .flat_map(|numbers| numbers.iter().tuple_combinations())
+        .map(|(a, b)| u128::from(a * b))
+        .sum::<u128>();
 
     Ok(sum.to_string())
 }
 
-#[tracing::instrument]
-pub fn part2() -> Result<()> {
-    let file = include_str!("../input2.txt");
-    let result = process(file).context("process part 2")?;
-    println!("Part 2: {}", result);
+#[tracing::instrument(skip(schematic))]
+pub fn solve_part2(schematic: &Schematic) -> Result<String> {
+    let _frame = aoc_telemetry::frame!("part 2");
+
+    process(schematic).context("process part 2")
+}
 
-    Ok(())
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -148,7 +62,7 @@ mod tests {
 ...$.*....
 .664.598..
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "467835");
     }
 
@@ -160,7 +74,7 @@ mod tests {
 ............
 1*2.........
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "27");
     }
 }
@@ -0,0 +1,267 @@
+use glam::IVec2;
+use nom::{
+    branch::alt,
+    bytes::complete::is_a,
+    character::complete::{digit1, line_ending, satisfy},
+    combinator::{map, map_res, recognize, verify},
+    multi::{many1, separated_list1},
+    IResult,
+};
+use nom_locate::LocatedSpan;
+use std::fmt;
+
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+/// A part number, positioned at its first digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub position: IVec2,
+    /// Digits it takes up, leading zeros included.
+    pub len: i32,
+}
+
+impl Number {
+    /// Whether `position` touches one of the number's digits, diagonally
+    /// included.
+    pub fn is_adjacent(&self, position: IVec2) -> bool {
+        (self.position.y - 1..=self.position.y + 1).contains(&position.y)
+            && (self.position.x - 1..=self.position.x + self.len).contains(&position.x)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub symbol: char,
+    pub position: IVec2,
+}
+
+/// The engine schematic: its numbers and symbols in reading order, on a
+/// grid of `width` by `height` cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Written as the input grid, with `.` wherever there is nothing.
+impl fmt::Display for Schematic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut grid = vec![vec!['.'; self.width as usize]; self.height as usize];
+
+        for number in &self.numbers {
+            let digits = format!("{:0len$}", number.value, len = number.len as usize);
+            let row = &mut grid[number.position.y as usize];
+            for (x, digit) in (number.position.x as usize..).zip(digits.chars()) {
+                row[x] = digit;
+            }
+        }
+        for symbol in &self.symbols {
+            grid[symbol.position.y as usize][symbol.position.x as usize] = symbol.symbol;
+        }
+
+        grid.iter()
+            .try_for_each(|row| writeln!(f, "{}", row.iter().collect::<String>()))
+    }
+}
+
+enum Cell {
+    Empty(i32),
+    Number(Number),
+    Symbol(Symbol),
+}
+
+impl Cell {
+    fn len(&self) -> i32 {
+        match self {
+            Cell::Empty(len) => *len,
+            Cell::Number(number) => number.len,
+            Cell::Symbol(_) => 1,
+        }
+    }
+}
+
+fn width(row: &[Cell]) -> i32 {
+    row.iter().map(Cell::len).sum()
+}
+
+fn position(span: Span) -> IVec2 {
+    IVec2::new(
+        span.get_column() as i32 - 1,
+        span.location_line() as i32 - 1,
+    )
+}
+
+#[tracing::instrument(skip(input))]
+fn cell(input: Span) -> IResult<Span, Cell> {
+    alt((
+        map_res(digit1, |span: Span| {
+            span.fragment().parse().map(|value| {
+                Cell::Number(Number {
+                    value,
+                    position: position(span),
+                    len: span.len() as i32,
+                })
+            })
+        }),
+        map(is_a("."), |span: Span| Cell::Empty(span.len() as i32)),
+        map(
+            recognize(satisfy(|c| c.is_ascii_punctuation() && c != '.')),
+            |span: Span| {
+                Cell::Symbol(Symbol {
+                    symbol: span.fragment().chars().next().unwrap_or_default(),
+                    position: position(span),
+                })
+            },
+        ),
+    ))(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn schematic(input: Span) -> IResult<Span, Schematic> {
+    // Neighbours are looked for on the rows around, so they line up.
+    let rectangular = |rows: &Vec<Vec<Cell>>| rows.iter().all(|row| width(row) == width(&rows[0]));
+
+    map(
+        verify(separated_list1(line_ending, many1(cell)), rectangular),
+        |rows| {
+            let mut schematic = Schematic {
+                numbers: Vec::new(),
+                symbols: Vec::new(),
+                width: width(&rows[0]),
+                height: rows.len() as i32,
+            };
+            for cell in rows.into_iter().flatten() {
+                match cell {
+                    Cell::Empty(_) => {}
+                    Cell::Number(number) => schematic.numbers.push(number),
+                    Cell::Symbol(symbol) => schematic.symbols.push(symbol),
+                }
+            }
+
+            schematic
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_located;
+    use proptest::prelude::*;
+
+    #[derive(Debug, Clone)]
+    enum Token {
+        Empty(i32),
+        Number(u32, i32),
+        Symbol(char),
+    }
+
+    fn any_token() -> impl Strategy<Value = Token> {
+        prop_oneof![
+            (1..4_i32).prop_map(Token::Empty),
+            (0..1000_u32, 0..2_i32).prop_map(|(value, zeros)| {
+                let digits = value.checked_ilog10().unwrap_or(0) as i32 + 1;
+                Token::Number(value, digits + zeros)
+            }),
+            prop::sample::select(b"*#+$/@=%&-".to_vec())
+                .prop_map(|symbol| Token::Symbol(symbol as char)),
+        ]
+    }
+
+    /// Lays out rows of tokens, keeping a `.` between neighbouring numbers
+    /// so they do not read as one, and pads every row to the widest.
+    fn any_schematic() -> impl Strategy<Value = Schematic> {
+        prop::collection::vec(prop::collection::vec(any_token(), 1..10), 1..10).prop_map(|rows| {
+            let mut schematic = Schematic {
+                numbers: Vec::new(),
+                symbols: Vec::new(),
+                width: 0,
+                height: rows.len() as i32,
+            };
+
+            for (y, row) in rows.into_iter().enumerate() {
+                let mut x = 0;
+                let mut after_number = false;
+
+                for token in row {
+                    let position = IVec2::new(x, y as i32);
+                    match token {
+                        Token::Empty(len) => x += len,
+                        Token::Number(value, len) => {
+                            let position = position + IVec2::X * i32::from(after_number);
+                            schematic.numbers.push(Number {
+                                value,
+                                position,
+                                len,
+                            });
+                            x = position.x + len;
+                        }
+                        Token::Symbol(symbol) => {
+                            schematic.symbols.push(Symbol { symbol, position });
+                            x += 1;
+                        }
+                    }
+                    after_number = matches!(token, Token::Number(..));
+                }
+
+                schematic.width = schematic.width.max(x);
+            }
+
+            schematic
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in any_schematic()) {
+            prop_assert_eq!(parse_all_located(schematic, &expected.to_string()), Ok(expected));
+        }
+
+        #[test]
+        fn crlf_round_trip(expected in any_schematic()) {
+            let input = expected.to_string().replace('\n', "\r\n");
+            prop_assert_eq!(parse_all_located(schematic, &input), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn cells_keep_their_positions() {
+        let (_, schematic) = schematic(Span::new("467..\n..*12\n")).unwrap();
+
+        assert_eq!(
+            schematic.numbers,
+            vec![
+                Number {
+                    value: 467,
+                    position: IVec2::new(0, 0),
+                    len: 3
+                },
+                Number {
+                    value: 12,
+                    position: IVec2::new(3, 1),
+                    len: 2
+                },
+            ]
+        );
+        assert_eq!(
+            schematic.symbols,
+            vec![Symbol {
+                symbol: '*',
+                position: IVec2::new(2, 1)
+            }]
+        );
+        assert!(schematic
+            .numbers
+            .iter()
+            .all(|n| n.is_adjacent(IVec2::new(2, 1))));
+        assert!(!schematic.numbers[0].is_adjacent(IVec2::new(4, 1)));
+    }
+
+    #[test]
+    fn rows_must_line_up() {
+        assert!(schematic(Span::new("467..\n..*\n")).is_err());
+    }
+}
@@ -1,5 +1,53 @@
 mod part1;
 mod part2;
+mod scratchcards;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+pub use scratchcards::{scratchcards, Card, Scratchcards};
+
+use anyhow::{Context, Result};
+use aoc_core::Solution;
+use aoc_parse::parse_all_nom7;
+use std::fmt::Display;
+
+/// The pile of scratchcards both parts score.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Scratchcards> {
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all_nom7(scratchcards, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 4;
+
+    type Input = Scratchcards;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailing_garbage_is_rejected() {
+        assert!(parse("Card 1: 1 2 | 1 3\n").is_ok());
+        assert!(parse("Card 1: 1 2 | 1 3\ngarbage").is_err());
+    }
+}
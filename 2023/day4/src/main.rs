@@ -1,16 +1,18 @@
 use anyhow::Result;
-use day4::{part1, part2};
+use aoc_telemetry::{init_telemetry, Telemetry};
+use day4::{parse, solve_part1, solve_part2};
 
 #[tracing::instrument]
 fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
+    let _telemetry = init_telemetry(&Telemetry::default())?;
 
-    let input1 = include_str!("../input1.txt");
-    let result = part1(input1)?;
+    let input = include_str!("../input.txt");
+    let parsed = parse(input)?;
+
+    let result = solve_part1(&parsed)?;
     println!("Part 1: {result}");
 
-    let input2 = include_str!("../input2.txt");
-    let result = part2(input2)?;
+    let result = solve_part2(&parsed)?;
     println!("Part 2: {result}");
 
     Ok(())
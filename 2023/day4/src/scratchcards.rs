@@ -0,0 +1,346 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, line_ending, space0, space1},
+    combinator::{map, verify},
+    multi::{fold_many1, separated_list1},
+    sequence::{pair, tuple},
+    IResult,
+};
+use std::fmt;
+
+/// Card numbers are always below 100, so a single `u128` holds a whole set.
+type NumberSet = u128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Card {
+    pub id: u32,
+    winning: NumberSet,
+    own: NumberSet,
+}
+
+impl Card {
+    /// How many of our own numbers appear among the winning ones.
+    pub fn matches(&self) -> u32 {
+        (self.winning & self.own).count_ones()
+    }
+}
+
+/// Written as a line of the input, with the numbers right-aligned in
+/// columns of two like the puzzle does.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let numbers = |f: &mut fmt::Formatter<'_>, set: NumberSet| {
+            (0..100)
+                .filter(|n| set & (1 << n) != 0)
+                .try_for_each(|n| write!(f, " {n:>2}"))
+        };
+
+        write!(f, "Card {:>3}:", self.id)?;
+        numbers(f, self.winning)?;
+        write!(f, " |")?;
+        numbers(f, self.own)
+    }
+}
+
+/// A pile of scratchcards with their match counts computed once up front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scratchcards {
+    cards: Vec<Card>,
+    matches: Vec<u32>,
+}
+
+impl Scratchcards {
+    pub fn new(cards: Vec<Card>) -> Self {
+        let matches = cards.iter().map(Card::matches).collect();
+
+        Self { cards, matches }
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn matches(&self) -> &[u32] {
+        &self.matches
+    }
+
+    /// Total score where each card is worth `score(matches)` points.
+    /// `None` if the total does not fit in a `u64`.
+    pub fn score_with(&self, score: impl Fn(u32) -> u64) -> Option<u64> {
+        self.matches
+            .iter()
+            .try_fold(0_u64, |total, &m| total.checked_add(score(m)))
+    }
+
+    /// The puzzle's scoring: one point for the first match, doubled for
+    /// every match after that. `None` if the total does not fit in a `u64`,
+    /// which takes a card with more than 64 matches.
+    pub fn points(&self) -> Option<u64> {
+        self.matches.iter().try_fold(0_u64, |total, &m| {
+            let points = match m {
+                0 => 0,
+                m => 1_u64.checked_shl(m - 1)?,
+            };
+            total.checked_add(points)
+        })
+    }
+
+    /// Number of instances of each card once every copy has been won,
+    /// where a card with `m` matches wins one copy of each of the next `m`
+    /// cards. `None` if a count does not fit in a `u64`.
+    pub fn copies(&self) -> Option<Vec<u64>> {
+        self.copies_with(|m| m as usize)
+    }
+
+    /// Like [`Scratchcards::copies`], but `prize(matches)` decides how many
+    /// of the following cards get copied. Copies never run past the end of
+    /// the pile.
+    pub fn copies_with(&self, prize: impl Fn(u32) -> usize) -> Option<Vec<u64>> {
+        let mut copies = vec![1_u64; self.matches.len()];
+
+        for (i, &m) in self.matches.iter().enumerate() {
+            let times = copies[i];
+            let end = (i + 1).saturating_add(prize(m)).min(copies.len());
+
+            for copy in &mut copies[i + 1..end] {
+                *copy = copy.checked_add(times)?;
+            }
+        }
+
+        Some(copies)
+    }
+
+    /// Like [`Scratchcards::copies_with`], but copies only cascade for
+    /// `rounds` generations: round 0 is the original pile, round 1 the
+    /// copies won by the originals, and so on.
+    pub fn copies_after(&self, rounds: usize, prize: impl Fn(u32) -> usize) -> Option<Vec<u64>> {
+        let len = self.matches.len();
+        let reach = self
+            .matches
+            .iter()
+            .enumerate()
+            .map(|(i, &m)| (i + 1).saturating_add(prize(m)).min(len))
+            .collect::<Vec<_>>();
+
+        let mut total = vec![1_u64; len];
+        let mut generation = vec![1_u64; len];
+
+        for _ in 0..rounds {
+            // Spread each card's instances over the range it wins by
+            // counting where each range starts and ends, keeping every
+            // round linear. A range only ends after it starts, so the
+            // running count never drops below zero.
+            let mut starts = vec![0_u64; len + 1];
+            let mut ends = vec![0_u64; len + 1];
+
+            for (i, &times) in generation.iter().enumerate() {
+                if times > 0 && reach[i] > i + 1 {
+                    starts[i + 1] = starts[i + 1].checked_add(times)?;
+                    ends[reach[i]] = ends[reach[i]].checked_add(times)?;
+                }
+            }
+
+            let mut running = 0_u64;
+            for (i, next) in generation.iter_mut().enumerate() {
+                running = running.checked_add(starts[i])? - ends[i];
+                *next = running;
+            }
+
+            if generation.iter().all(|&times| times == 0) {
+                break;
+            }
+
+            for (t, g) in total.iter_mut().zip(&generation) {
+                *t = t.checked_add(*g)?;
+            }
+        }
+
+        Some(total)
+    }
+
+    /// Total number of cards held at the end of the puzzle. `None` if it
+    /// does not fit in a `u64`.
+    pub fn total_cards(&self) -> Option<u64> {
+        self.copies()?
+            .into_iter()
+            .try_fold(0_u64, |total, copies| total.checked_add(copies))
+    }
+}
+
+impl fmt::Display for Scratchcards {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cards.iter().try_for_each(|card| writeln!(f, "{card}"))
+    }
+}
+
+#[tracing::instrument(skip(input))]
+fn numbers(input: &str) -> IResult<&str, NumberSet> {
+    fold_many1(
+        pair(verify(complete::u8, |n| *n < 100), space0),
+        || 0,
+        |acc, (number, _)| acc | (1 << number),
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn card(input: &str) -> IResult<&str, Card> {
+    map(
+        tuple((
+            tag("Card"),
+            space1,
+            complete::u32,
+            tag(":"),
+            space1,
+            numbers,
+            tag("|"),
+            space1,
+            numbers,
+        )),
+        |(_, _, id, _, _, winning, _, _, own)| Card { id, winning, own },
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn scratchcards(input: &str) -> IResult<&str, Scratchcards> {
+    map(separated_list1(line_ending, card), Scratchcards::new)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use proptest::prelude::*;
+
+    fn any_card() -> impl Strategy<Value = Card> {
+        let numbers = prop::collection::btree_set(0..100_u32, 1..25)
+            .prop_map(|numbers| numbers.into_iter().fold(0, |set, n| set | (1 << n)));
+
+        (1..1000_u32, numbers.clone(), numbers).prop_map(|(id, winning, own)| Card {
+            id,
+            winning,
+            own,
+        })
+    }
+
+    /// A card written with any run of spaces and tabs where the grammar
+    /// takes one, numbers in any order, and either line ending, paired with
+    /// the card it reads as.
+    fn any_spaced_card() -> impl Strategy<Value = (Card, String)> {
+        let gap = || "[ \t]{1,3}";
+        let numbers = || {
+            prop::collection::vec((0..100_u32, gap()), 1..25).prop_map(|numbers| {
+                let set = numbers.iter().fold(0, |set, (n, _)| set | (1 << n));
+                let written = numbers
+                    .iter()
+                    .map(|(n, gap)| format!("{n}{gap}"))
+                    .collect::<String>();
+                (set, written)
+            })
+        };
+        let line_ending = prop_oneof![Just("\n"), Just("\r\n")];
+
+        (
+            1..1000_u32,
+            numbers(),
+            numbers(),
+            gap(),
+            gap(),
+            gap(),
+            line_ending,
+        )
+            .prop_map(
+                |(id, (winning, written_winning), (own, written_own), g1, g2, g3, end)| {
+                    let card = Card { id, winning, own };
+                    let line = format!("Card{g1}{id}:{g2}{written_winning}|{g3}{written_own}{end}");
+                    (card, line)
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(cards in prop::collection::vec(any_card(), 1..20)) {
+            let cards = Scratchcards::new(cards);
+            let input = cards.to_string();
+            prop_assert_eq!(parse_all_nom7(scratchcards, &input), Ok(cards));
+        }
+
+        #[test]
+        fn spaced_round_trip(cards in prop::collection::vec(any_spaced_card(), 1..20)) {
+            let input = cards.iter().map(|(_, line)| line.as_str()).collect::<String>();
+            let expected = Scratchcards::new(cards.into_iter().map(|(card, _)| card).collect());
+            prop_assert_eq!(parse_all_nom7(scratchcards, &input), Ok(expected));
+        }
+    }
+
+    const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn matches_are_counted_once() {
+        let (_, cards) = scratchcards(EXAMPLE).unwrap();
+        assert_eq!(cards.matches(), &[4, 2, 2, 1, 0, 0]);
+        assert_eq!(cards.points(), Some(13));
+    }
+
+    #[test]
+    fn copies_cascade() {
+        let (_, cards) = scratchcards(EXAMPLE).unwrap();
+        assert_eq!(cards.copies(), Some(vec![1, 2, 4, 8, 14, 1]));
+        assert_eq!(cards.total_cards(), Some(30));
+    }
+
+    #[test]
+    fn limited_rounds() {
+        let (_, cards) = scratchcards(EXAMPLE).unwrap();
+        let prize = |m| m as usize;
+
+        assert_eq!(cards.copies_after(0, prize), Some(vec![1; 6]));
+        assert_eq!(cards.copies_after(1, prize), Some(vec![1, 2, 3, 4, 4, 1]));
+        assert_eq!(cards.copies_after(6, prize), cards.copies());
+    }
+
+    #[test]
+    fn points_overflow() {
+        let numbers = (0..65).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+        let input = format!("Card 1: {numbers} | {numbers}\n");
+        let (_, cards) = scratchcards(&input).unwrap();
+
+        assert_eq!(cards.matches(), &[65]);
+        assert_eq!(cards.points(), None);
+    }
+
+    #[test]
+    fn copies_overflow() {
+        let numbers = (0..98).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+        let input = (1..=79)
+            .map(|id| format!("Card {id}: {numbers} | {numbers}\n"))
+            .collect::<String>();
+        let (_, cards) = scratchcards(&input).unwrap();
+
+        assert_eq!(cards.copies(), None);
+        assert_eq!(cards.copies_after(100, |m| m as usize), None);
+        assert_eq!(cards.total_cards(), None);
+    }
+
+    #[test]
+    fn numbers_stay_below_100() {
+        assert!(scratchcards("Card 1: 1 100 | 1 2\n").is_err());
+    }
+
+    #[test]
+    fn custom_prize() {
+        let (_, cards) = scratchcards(EXAMPLE).unwrap();
+
+        assert_eq!(cards.score_with(u64::from), Some(9));
+        assert_eq!(
+            cards.copies_with(|m| m.min(1) as usize),
+            Some(vec![1, 2, 3, 4, 5, 1])
+        );
+    }
+}
@@ -1,4 +1,8 @@
-use day5::{part1, part2};
+use aoc_core::{Rng, Solution};
+use day5::{parse, part1, part2, solve_part1, solve_part2, Puzzle};
+use divan::{black_box, Bencher};
+
+const INPUT: &str = include_str!("../input.txt");
 
 fn main() {
     divan::main();
@@ -6,10 +10,46 @@ fn main() {
 
 #[divan::bench]
 fn part1_benchmark() {
-    part1(divan::black_box(include_str!("../input1.txt",))).unwrap();
+    part1(black_box(INPUT)).unwrap();
 }
 
 #[divan::bench]
 fn part2_benchmark() {
-    part2(divan::black_box(include_str!("../input2.txt",))).unwrap();
+    part2(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn parse_benchmark() {
+    parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn solve_part1_benchmark(bencher: Bencher) {
+    let parsed = parse(INPUT).unwrap();
+    bencher.bench(|| solve_part1(black_box(&parsed)).unwrap());
+}
+
+#[divan::bench]
+fn solve_part2_benchmark(bencher: Bencher) {
+    let parsed = parse(INPUT).unwrap();
+    bencher.bench(|| solve_part2(black_box(&parsed)).unwrap());
+}
+
+/// A generated input of `size`, the same on every run.
+fn generated(size: usize) -> &'static str {
+    Puzzle::generate(&mut Rng::with_seed(2023), size)
+        .unwrap()
+        .leak()
+}
+
+#[divan::bench(args = [10, 100, 300])]
+fn part1_scaling(bencher: Bencher, size: usize) {
+    let input = generated(size);
+    bencher.bench(|| part1(black_box(input)).unwrap());
+}
+
+#[divan::bench(args = [10, 100, 300])]
+fn part2_scaling(bencher: Bencher, size: usize) {
+    let input = generated(size);
+    bencher.bench(|| part2(black_box(input)).unwrap());
 }
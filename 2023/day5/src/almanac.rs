@@ -0,0 +1,249 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{self, alpha1, line_ending, space0, space1},
+    combinator::{map, map_opt, recognize},
+    multi::{many1, separated_list1},
+    sequence::{pair, terminated, tuple},
+    IResult,
+};
+use std::{fmt, ops::Range};
+use tracing::info;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map<'a> {
+    pub name: &'a str,
+    /// `(source, destination)` pairs of the same length.
+    pub ranges: Vec<(Range<u64>, Range<u64>)>,
+}
+
+impl Map<'_> {
+    /// Where `seed` ends up: moved by the first range that holds it, or
+    /// left where it is.
+    pub fn apply(&self, seed: u64) -> u64 {
+        self.ranges
+            .iter()
+            .find(|(source, _)| source.contains(&seed))
+            .map_or(seed, |(source, destination)| {
+                destination.start + (seed - source.start)
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Almanac<'a> {
+    /// The numbers on the `seeds:` line, which each part reads its own way.
+    pub seeds: Vec<u64>,
+    pub maps: Vec<Map<'a>>,
+}
+
+impl Almanac<'_> {
+    /// The seeds line read as `start length` pairs. `None` if a number is
+    /// left over or a range runs past the largest u64.
+    pub fn seed_ranges(&self) -> Option<Vec<Range<u64>>> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return None;
+        }
+
+        self.seeds
+            .chunks_exact(2)
+            .map(|pair| Some(pair[0]..pair[0].checked_add(pair[1])?))
+            .collect()
+    }
+
+    /// Sends `seed` through every map in turn.
+    pub fn location(&self, seed: u64) -> u64 {
+        self.maps.iter().fold(seed, |seed, map| map.apply(seed))
+    }
+}
+
+/// Written as a section of the input, one `destination source length`
+/// line per range.
+impl fmt::Display for Map<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} map:", self.name)?;
+
+        for (source, destination) in &self.ranges {
+            let len = source.end - source.start;
+            write!(f, "\n{} {} {len}", destination.start, source.start)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Almanac<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "seeds:")?;
+        self.seeds
+            .iter()
+            .try_for_each(|seed| write!(f, " {seed}"))?;
+        writeln!(f)?;
+
+        for map in &self.maps {
+            write!(f, "\n{map}\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(input))]
+fn number(input: &str) -> IResult<&str, u64> {
+    map(pair(complete::u64, space0), |(n, _)| n)(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn numbers(input: &str) -> IResult<&str, Vec<u64>> {
+    many1(number)(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn seeds(input: &str) -> IResult<&str, Vec<u64>> {
+    let (input, seeds) = map(
+        terminated(
+            tuple((tag("seeds:"), space1, numbers)),
+            pair(line_ending, line_ending),
+        ),
+        |(_, _, ns)| ns,
+    )(input)?;
+
+    info!(?seeds);
+
+    Ok((input, seeds))
+}
+
+#[tracing::instrument(skip(input))]
+fn map_name(input: &str) -> IResult<&str, &str> {
+    recognize(many1(alt((alpha1, tag("-")))))(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn range(input: &str) -> IResult<&str, (Range<u64>, Range<u64>)> {
+    // `destination source length`, where neither range may run past the
+    // largest u64.
+    map_opt(tuple((number, number, number)), |(dst, src, len)| {
+        Some((src..src.checked_add(len)?, dst..dst.checked_add(len)?))
+    })(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn almanac_map(input: &str) -> IResult<&str, Map<'_>> {
+    map(
+        tuple((
+            map_name,
+            space1,
+            tag("map:"),
+            line_ending,
+            separated_list1(line_ending, range),
+        )),
+        |(name, _, _, _, ranges)| {
+            let map = Map { name, ranges };
+
+            info!(?map);
+
+            map
+        },
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn almanac_maps(input: &str) -> IResult<&str, Vec<Map<'_>>> {
+    separated_list1(pair(line_ending, line_ending), almanac_map)(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn almanac(input: &str) -> IResult<&str, Almanac<'_>> {
+    map(pair(seeds, almanac_maps), |(seeds, maps)| Almanac {
+        seeds,
+        maps,
+    })(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use proptest::prelude::*;
+
+    fn any_map() -> impl Strategy<Value = Map<'static>> {
+        let name = prop::sample::select(vec![
+            "seed-to-soil",
+            "soil-to-fertilizer",
+            "fertilizer-to-water",
+            "humidity-to-location",
+        ]);
+        let range = (0..1_u64 << 32, 0..1_u64 << 32, 1..1_u64 << 20)
+            .prop_map(|(dst, src, len)| ((src..src + len), (dst..dst + len)));
+
+        (name, prop::collection::vec(range, 1..10)).prop_map(|(name, ranges)| Map { name, ranges })
+    }
+
+    fn any_almanac() -> impl Strategy<Value = Almanac<'static>> {
+        (
+            prop::collection::vec(0..1_u64 << 32, 1..20),
+            prop::collection::vec(any_map(), 1..8),
+        )
+            .prop_map(|(seeds, maps)| Almanac { seeds, maps })
+    }
+
+    /// An almanac written with any run of spaces and tabs where the grammar
+    /// takes one and either line ending, paired with the almanac it reads
+    /// as. Gaps and line endings are drawn in turn from short random lists.
+    fn any_spaced_almanac() -> impl Strategy<Value = (Almanac<'static>, String)> {
+        let gaps = prop::collection::vec("[ \t]{1,3}", 1..8);
+        let line_endings = prop::collection::vec(prop_oneof![Just("\n"), Just("\r\n")], 1..4);
+
+        (any_almanac(), gaps, line_endings).prop_map(|(almanac, gaps, line_endings)| {
+            let mut gap = gaps.iter().cycle();
+            let mut end = line_endings.iter().cycle();
+            let mut input = format!("seeds:{}", gap.next().unwrap());
+
+            for seed in &almanac.seeds {
+                input += &format!("{seed}{}", gap.next().unwrap());
+            }
+            for map in &almanac.maps {
+                input += &format!("{}{}", end.next().unwrap(), end.next().unwrap());
+                input += &format!("{}{}map:", map.name, gap.next().unwrap());
+
+                for (source, destination) in &map.ranges {
+                    let numbers = [destination.start, source.start, source.end - source.start];
+                    input += end.next().unwrap();
+                    for n in numbers {
+                        input += &format!("{n}{}", gap.next().unwrap());
+                    }
+                }
+            }
+
+            (almanac, input)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in any_almanac()) {
+            let input = expected.to_string();
+            prop_assert_eq!(parse_all_nom7(almanac, &input), Ok(expected));
+        }
+
+        #[test]
+        fn spaced_round_trip((expected, input) in any_spaced_almanac()) {
+            prop_assert_eq!(parse_all_nom7(almanac, &input), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn seed_ranges_come_in_pairs() {
+        let almanac = |seeds| Almanac {
+            seeds,
+            maps: Vec::new(),
+        };
+
+        assert_eq!(
+            almanac(vec![79, 14, 55, 13]).seed_ranges(),
+            Some(vec![79..93, 55..68])
+        );
+        assert_eq!(almanac(vec![79, 14, 55]).seed_ranges(), None);
+        assert_eq!(almanac(vec![u64::MAX, 1]).seed_ranges(), None);
+    }
+}
@@ -0,0 +1,71 @@
+use aoc_core::Rng;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// An almanac whose seven maps each shuffle `0..1000 * size` in `size`
+/// ranges, with five seed ranges of up to `10 * size` seeds.
+///
+/// Part 2 tries every seed, so the seed ranges stay far shorter than the
+/// puzzle's.
+pub(crate) fn almanac(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1) as u64;
+    let span = 1000 * size;
+
+    let mut almanac = String::from("seeds:");
+    for _ in 0..5 {
+        let len = rng.u64(1..=10 * size);
+        almanac += &format!(" {} {len}", rng.u64(0..=span - len));
+    }
+    almanac.push('\n');
+
+    for name in MAPS {
+        // Cut the span into ranges, then lay them out again in another
+        // order, so every number maps to exactly one.
+        let mut cuts = rng.choose_multiple(1..span, size as usize - 1);
+        cuts.sort();
+        let mut sources = [0]
+            .into_iter()
+            .chain(cuts)
+            .chain([span])
+            .collect::<Vec<_>>()
+            .windows(2)
+            .map(|cut| (cut[0], cut[1] - cut[0]))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut sources);
+
+        almanac += &format!("\n{name} map:\n");
+        let mut destination = 0;
+        for (source, len) in sources {
+            almanac += &format!("{destination} {source} {len}\n");
+            destination += len;
+        }
+    }
+
+    almanac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_land_in_the_almanac() {
+        for seed in 0..10 {
+            let size = 1 + seed as usize;
+            let input = &*almanac(&mut Rng::with_seed(seed), size).leak();
+
+            for part in [crate::part1, crate::part2] {
+                let location = part(input).unwrap().parse::<usize>().unwrap();
+                assert!(location < 1000 * size, "{input}");
+            }
+        }
+    }
+}
@@ -1,152 +1,125 @@
+use crate::{parse, Almanac, Map};
 use anyhow::{Context, Result};
-use indicatif::ParallelProgressIterator;
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, alpha1, line_ending, space0, space1},
-    combinator::{map, recognize},
-    multi::{many1, separated_list1},
-    sequence::{pair, terminated, tuple},
-    IResult,
-};
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use aoc_core::SolveError;
 use std::ops::Range;
 
-#[derive(Debug, PartialEq)]
-struct Map<'a> {
-    name: &'a str,
-    ranges: Vec<(Range<u64>, Range<u64>)>,
-}
-
-#[derive(Debug)]
-struct Almanac<'a> {
-    seeds: Vec<Range<u64>>,
-    maps: Vec<Map<'a>>,
-}
-
-#[tracing::instrument]
-fn number(input: &str) -> IResult<&str, u64> {
-    map(pair(complete::u64, space0), |(n, _)| n)(input)
-}
-
-#[tracing::instrument]
-fn numbers(input: &str) -> IResult<&str, Vec<u64>> {
-    many1(number)(input)
-}
-
-#[tracing::instrument]
-fn seed_range(input: &str) -> IResult<&str, Range<u64>> {
-    map(pair(number, number), |(start, len)| (start..(start + len)))(input)
-}
-
-#[tracing::instrument]
-fn seed_ranges(input: &str) -> IResult<&str, Vec<Range<u64>>> {
-    many1(seed_range)(input)
-}
-
-#[tracing::instrument]
-fn seeds(input: &str) -> IResult<&str, Vec<Range<u64>>> {
-    map(
-        terminated(
-            tuple((tag("seeds:"), space1, seed_ranges)),
-            pair(line_ending, line_ending),
-        ),
-        |(_, _, ns)| ns,
-    )(input)
-}
-
-#[tracing::instrument]
-fn map_name(input: &str) -> IResult<&str, &str> {
-    recognize(many1(alt((alpha1, tag("-")))))(input)
-}
-
-#[tracing::instrument]
-fn range(input: &str) -> IResult<&str, Vec<u64>> {
-    numbers(input)
-}
+/// Sends whole ranges of seeds through the maps, splitting them where a
+/// map's ranges begin and end, instead of following every seed.
+fn lowest_location(seeds: &[Range<u64>], maps: &[Map]) -> Option<u64> {
+    let mut ranges = seeds
+        .iter()
+        .filter(|range| !range.is_empty())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for map in maps {
+        let mut mapped = Vec::new();
+
+        while let Some(range) = ranges.pop() {
+            if range.is_empty() {
+                continue;
+            }
+
+            // Like a single seed, a range goes through the first of the
+            // map's ranges it meets. What is left over on either side
+            // tries the map again. An empty map range holds no seeds, so
+            // it never takes one.
+            let overlap = map.ranges.iter().find(|(source, _)| {
+                !source.is_empty() && source.start < range.end && range.start < source.end
+            });
+            let Some((source, destination)) = overlap else {
+                mapped.push(range);
+                continue;
+            };
+
+            let start = range.start.max(source.start);
+            let end = range.end.min(source.end);
+            mapped.push(
+                destination.start + (start - source.start)
+                    ..destination.start + (end - source.start),
+            );
+            ranges.push(range.start..start);
+            ranges.push(end..range.end);
+        }
+
+        ranges = mapped;
+    }
 
-#[tracing::instrument]
-fn almanac_map(input: &str) -> IResult<&str, Map> {
-    map(
-        tuple((
-            map_name,
-            space1,
-            tag("map:"),
-            line_ending,
-            separated_list1(line_ending, range),
-        )),
-        |(name, _, _, _, ranges)| {
-            let ranges = ranges
-                .into_iter()
-                .map(|range| {
-                    let dst = range[0];
-                    let src = range[1];
-                    let len = range[2];
-
-                    ((src..src + len), (dst..dst + len))
-                })
-                .collect::<Vec<_>>();
-
-            Map { name, ranges }
-        },
-    )(input)
+    ranges.iter().map(|range| range.start).min()
 }
 
-#[tracing::instrument]
-fn almanac_maps(input: &str) -> IResult<&str, Vec<Map>> {
-    separated_list1(pair(line_ending, line_ending), almanac_map)(input)
-}
+#[tracing::instrument(skip(almanac))]
+fn process(almanac: &Almanac) -> Result<String, SolveError> {
+    let seeds = almanac
+        .seed_ranges()
+        .ok_or_else(|| SolveError::malformed("the seeds do not pair up into ranges"))?;
+    let closest = lowest_location(&seeds, &almanac.maps)
+        .ok_or_else(|| SolveError::malformed("every seed range is empty"))?;
 
-#[tracing::instrument]
-fn almanac(input: &str) -> IResult<&str, Almanac> {
-    map(pair(seeds, almanac_maps), |(seeds, maps)| Almanac {
-        seeds,
-        maps,
-    })(input)
+    Ok(closest.to_string())
 }
 
-#[tracing::instrument]
-fn process(input: &'static str) -> Result<String> {
-    let (_, almanac) = almanac(input)?;
-
-    let locations = almanac
-        .seeds
-        .iter()
-        .flat_map(|range| range.clone())
-        .collect::<Vec<u64>>();
-
-    let closest = locations
-        .into_par_iter()
-        .progress()
-        .map(|seed| {
-            almanac.maps.iter().fold(seed, |seed, map| {
-                let valid = map
-                    .ranges
-                    .iter()
-                    .find(|(source_range, _)| source_range.contains(&seed));
-
-                let Some((source, destination)) = valid else {
-                    return seed;
-                };
-
-                let offset = seed - source.start;
-                destination.start + offset
-            })
-        })
-        .min()
-        .unwrap();
+#[tracing::instrument(skip(almanac))]
+pub fn solve_part2(almanac: &Almanac) -> Result<String> {
+    let _frame = aoc_telemetry::frame!("part 2");
 
-    Ok(closest.to_string())
+    process(almanac).context("process part 2")
 }
 
-#[tracing::instrument]
 pub fn part2(input: &'static str) -> Result<String> {
-    process(input).context("process part 2")
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    /// Follows every seed through the maps on its own, which is too slow
+    /// for the real input but easy to believe.
+    fn seed_by_seed((seeds, maps): &(Vec<Range<u64>>, Vec<Map>)) -> Option<u64> {
+        seeds
+            .iter()
+            .flat_map(|range| range.clone())
+            .map(|seed| maps.iter().fold(seed, |seed, map| map.apply(seed)))
+            .min()
+    }
+
+    #[test]
+    fn agrees_with_seed_by_seed() {
+        let range = || (0..100_u64, 0..30_u64).prop_map(|(start, len)| start..start + len);
+        let map = prop::collection::vec((range(), 0..100_u64), 1..5).prop_map(|ranges| Map {
+            name: "seed-to-soil",
+            ranges: ranges
+                .into_iter()
+                .map(|(source, dst)| (source.clone(), dst..dst + (source.end - source.start)))
+                .collect(),
+        });
+        let almanacs = (
+            prop::collection::vec(range(), 1..4),
+            prop::collection::vec(map, 1..4),
+        );
+
+        aoc_differential::assert_agree(almanacs, seed_by_seed, |(seeds, maps)| {
+            lowest_location(seeds, maps)
+        });
+    }
+
+    #[test]
+    fn empty_map_ranges_hold_no_seeds() {
+        let maps = [Map {
+            name: "seed-to-soil",
+            ranges: vec![(34..34, 0..0)],
+        }];
+
+        let seeds = 33..61;
+
+        assert_eq!(
+            lowest_location(std::slice::from_ref(&seeds), &maps),
+            Some(33)
+        );
+        assert_eq!(lowest_location(&[seeds.clone(), 40..40], &maps), Some(33));
+    }
 
     #[test]
     fn it_works() {
@@ -184,7 +157,7 @@ humidity-to-location map:
 60 56 37
 56 93 4
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "46");
     }
 }
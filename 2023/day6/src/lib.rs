@@ -1,5 +1,42 @@
 mod part1;
 mod part2;
+mod sheet;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+pub use sheet::{sheet, Sheet};
+
+use anyhow::{Context, Result};
+use aoc_core::Solution;
+use aoc_parse::parse_all;
+use std::fmt::Display;
+
+/// The race sheet, which each part reads its own way.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Sheet> {
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all(sheet, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 6;
+
+    type Input = Sheet;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+}
@@ -1,65 +1,14 @@
+use crate::{parse, Sheet};
 use anyhow::{Context, Result};
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending, space1},
-    combinator::map,
-    multi::separated_list1,
-    sequence::{pair, preceded, separated_pair},
-    IResult,
-};
+use aoc_core::SolveError;
 use tracing::info;
 
-#[derive(Debug)]
-struct Sheet {
-    times: Vec<u32>,
-    distances: Vec<u32>,
-}
-
-#[tracing::instrument(skip(input))]
-fn numbers(input: &str) -> IResult<&str, Vec<u32>> {
-    separated_list1(space1, complete::u32)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn time_line(input: &str) -> IResult<&str, Vec<u32>> {
-    let (input, ts) = preceded(pair(tag("Time:"), space1), numbers)(input)?;
-
-    info!(?ts);
-
-    Ok((input, ts))
-}
-
-#[tracing::instrument(skip(input))]
-fn distance_line(input: &str) -> IResult<&str, Vec<u32>> {
-    let (input, ds) = preceded(pair(tag("Distance:"), space1), numbers)(input)?;
-
-    info!(?ds);
-
-    Ok((input, ds))
-}
-
-#[tracing::instrument(skip(input))]
-fn sheet(input: &str) -> IResult<&str, Sheet> {
-    let (input, sheet) = map(
-        separated_pair(time_line, line_ending, distance_line),
-        |(times, distances)| Sheet { times, distances },
-    )(input)?;
-
-    info!(?sheet);
-
-    Ok((input, sheet))
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(sheet))]
+fn process(sheet: &Sheet) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, sheet) = sheet(input)?;
-
     let result = sheet
-        .times
-        .into_iter()
-        .zip(sheet.distances)
+        .races()
         .map(|(time, distance)| {
             let time = time as f64;
             let distance = distance as f64;
@@ -74,20 +23,26 @@ fn process(input: &'static str) -> Result<String> {
 
             info!(?time, ?distance, ?r1, ?r2, ?max, ?min);
 
-            (max - min) as u32
+            (max - min) as u64
         })
-        .product::<u32>();
+        .try_fold(1_u64, |product, ways| product.checked_mul(ways))
+        .ok_or_else(|| SolveError::malformed("the ways to win do not fit in a u64"))?;
 
     info!(?result);
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(sheet))]
+pub fn solve_part1(sheet: &Sheet) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
 
-    process(input).context("process part 1")
+    process(sheet).context("process part 1")
+}
+
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -99,7 +54,7 @@ mod tests {
         let input = r##"Time:      7  15   30
 Distance:  9  40  200
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "288");
     }
 }
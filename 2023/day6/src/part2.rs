@@ -1,66 +1,17 @@
+use crate::{parse, Sheet};
 use anyhow::{Context, Result};
-use nom::{
-    bytes::complete::{tag, take_until},
-    character::complete::{line_ending, space1},
-    combinator::map,
-    sequence::{pair, preceded, separated_pair},
-    IResult,
-};
+use aoc_core::SolveError;
 use tracing::info;
 
-#[derive(Debug)]
-struct Sheet {
-    time: u64,
-    distance: u64,
-}
-
-#[tracing::instrument(skip(input))]
-fn number(input: &str) -> IResult<&str, u64> {
-    map(take_until("\n"), |s: &str| {
-        let mut s = s.to_string();
-        s.retain(|c| !c.is_whitespace());
-        s.parse::<u64>().expect("should be a number")
-    })(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn time_line(input: &str) -> IResult<&str, u64> {
-    let (input, ts) = preceded(pair(tag("Time:"), space1), number)(input)?;
-
-    info!(?ts);
-
-    Ok((input, ts))
-}
-
-#[tracing::instrument(skip(input))]
-fn distance_line(input: &str) -> IResult<&str, u64> {
-    let (input, ds) = preceded(pair(tag("Distance:"), space1), number)(input)?;
-
-    info!(?ds);
-
-    Ok((input, ds))
-}
-
-#[tracing::instrument(skip(input))]
-fn sheet(input: &str) -> IResult<&str, Sheet> {
-    let (input, sheet) = map(
-        separated_pair(time_line, line_ending, distance_line),
-        |(time, distance)| Sheet { time, distance },
-    )(input)?;
-
-    info!(?sheet);
-
-    Ok((input, sheet))
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(sheet))]
+fn process(sheet: &Sheet) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, sheet) = sheet(input)?;
-
-    let time = sheet.time as f64;
-    let distance = sheet.distance as f64;
+    let (time, distance) = sheet
+        .kerned()
+        .ok_or_else(|| SolveError::malformed("the race is longer than a u64"))?;
+    let time = time as f64;
+    let distance = distance as f64;
 
     let determinant = (time * time - 4.0 * distance).sqrt();
     let max = (((time + determinant) / 2.0) - 1.0).ceil();
@@ -75,11 +26,16 @@ fn process(input: &'static str) -> Result<String> {
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(sheet))]
+pub fn solve_part2(sheet: &Sheet) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
 
-    process(input).context("process part 2")
+    process(sheet).context("process part 2")
+}
+
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -91,7 +47,7 @@ mod tests {
         let input = r##"Time:      7  15   30
 Distance:  9  40  200
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "71503");
     }
 }
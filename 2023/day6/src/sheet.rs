@@ -0,0 +1,163 @@
+use aoc_parse::{
+    header,
+    nom::{
+        character::complete::line_ending, combinator::map, sequence::separated_pair, IResult,
+        Parser,
+    },
+    numbers,
+};
+use std::fmt;
+use tracing::info;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sheet {
+    pub times: Vec<u64>,
+    pub distances: Vec<u64>,
+}
+
+impl Sheet {
+    /// Each race's time and record distance, as part 1 reads the sheet.
+    pub fn races(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.times
+            .iter()
+            .copied()
+            .zip(self.distances.iter().copied())
+    }
+
+    /// The one long race part 2 reads, with the spaces between the numbers
+    /// ignored. `None` if either number does not fit in a `u64`.
+    pub fn kerned(&self) -> Option<(u64, u64)> {
+        let kerned = |numbers: &[u64]| {
+            numbers.iter().try_fold(0_u64, |acc, &n| {
+                let shift = 10_u64.checked_pow(n.checked_ilog10().unwrap_or(0) + 1)?;
+                acc.checked_mul(shift)?.checked_add(n)
+            })
+        };
+
+        Some((kerned(&self.times)?, kerned(&self.distances)?))
+    }
+}
+
+/// Written as the puzzle does, with each race's time and distance
+/// right-aligned in a column.
+impl fmt::Display for Sheet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self
+            .races()
+            .map(|(time, distance)| time.max(distance).to_string().len())
+            .collect::<Vec<_>>();
+
+        write!(f, "Time:    ")?;
+        for (time, width) in self.times.iter().zip(&widths) {
+            write!(f, "  {time:>width$}")?;
+        }
+        write!(f, "\nDistance:")?;
+        for (distance, width) in self.distances.iter().zip(&widths) {
+            write!(f, "  {distance:>width$}")?;
+        }
+        writeln!(f)
+    }
+}
+
+#[tracing::instrument(skip(input))]
+pub fn sheet(input: &str) -> IResult<&str, Sheet> {
+    let (input, sheet) = map(
+        separated_pair(
+            header("Time", numbers()),
+            line_ending,
+            header("Distance", numbers()),
+        ),
+        |(times, distances)| Sheet { times, distances },
+    )
+    .parse(input)?;
+
+    info!(?sheet);
+
+    Ok((input, sheet))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all;
+    use proptest::prelude::*;
+
+    fn any_sheet() -> impl Strategy<Value = Sheet> {
+        prop::collection::vec((0..10_000_u64, 0..10_000_000_u64), 1..8).prop_map(|races| {
+            let (times, distances) = races.into_iter().unzip();
+            Sheet { times, distances }
+        })
+    }
+
+    /// A sheet written with any run of spaces and tabs where the grammar
+    /// takes one and either line ending, paired with the sheet it reads as.
+    fn any_spaced_sheet() -> impl Strategy<Value = (Sheet, String)> {
+        let gaps = prop::collection::vec("[ \t]{1,3}", 1..8);
+        let line_ending = prop_oneof![Just("\n"), Just("\r\n")];
+
+        (any_sheet(), "[ \t]{0,3}", gaps, line_ending).prop_map(
+            |(sheet, after_colon, gaps, end)| {
+                let mut gap = gaps.iter().cycle();
+                let mut row = |numbers: &[u64]| {
+                    numbers
+                        .iter()
+                        .map(u64::to_string)
+                        .reduce(|row, n| format!("{row}{}{n}", gap.next().unwrap()))
+                        .unwrap_or_default()
+                };
+                let input = format!(
+                    "Time:{after_colon}{}{end}Distance:{after_colon}{}{end}",
+                    row(&sheet.times),
+                    row(&sheet.distances)
+                );
+
+                (sheet, input)
+            },
+        )
+    }
+
+    /// The sheet part 2 sees, with the digits of each number spread over
+    /// columns.
+    fn kerned(time: u64, distance: u64) -> String {
+        let kerned = |n: u64| {
+            n.to_string()
+                .chars()
+                .map(|digit| format!("  {digit}"))
+                .collect::<String>()
+        };
+        let width = time.max(distance).to_string().len() * 3;
+
+        format!(
+            "Time:    {:>width$}\nDistance:{:>width$}\n",
+            kerned(time),
+            kerned(distance)
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in any_sheet()) {
+            prop_assert_eq!(parse_all(sheet, &expected.to_string()), Ok(expected));
+        }
+
+        #[test]
+        fn spaced_round_trip((expected, input) in any_spaced_sheet()) {
+            prop_assert_eq!(parse_all(sheet, &input), Ok(expected));
+        }
+
+        #[test]
+        fn kerned_round_trip(time in 0..1_000_000_000_u64, distance in 0..1_000_000_000_000_000_u64) {
+            let parsed = parse_all(sheet, &kerned(time, distance)).unwrap();
+            prop_assert_eq!(parsed.kerned(), Some((time, distance)));
+        }
+    }
+
+    #[test]
+    fn kerning_overflows() {
+        let sheet = Sheet {
+            times: vec![u64::MAX, 1],
+            distances: vec![1],
+        };
+        assert_eq!(sheet.kerned(), None);
+    }
+}
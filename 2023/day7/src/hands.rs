@@ -0,0 +1,87 @@
+use nom::{
+    character::complete::{self, line_ending, one_of, space1},
+    combinator::map_opt,
+    multi::{count, separated_list1},
+    sequence::separated_pair,
+    IResult,
+};
+use std::fmt;
+
+/// The card labels, strongest first as part 1 ranks them.
+pub const LABELS: &str = "AKQJT98765432";
+
+/// A hand of five card labels and its bid. What the labels are worth is
+/// up to each part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hand {
+    pub cards: [char; 5],
+    pub bid: u32,
+}
+
+/// Written as a line of the input, like `32T3K 765`.
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cards.iter().try_for_each(|card| write!(f, "{card}"))?;
+        write!(f, " {}", self.bid)
+    }
+}
+
+#[tracing::instrument(skip(input))]
+fn hand(input: &str) -> IResult<&str, Hand> {
+    map_opt(
+        separated_pair(count(one_of(LABELS), 5), space1, complete::u32),
+        |(cards, bid)| {
+            Some(Hand {
+                cards: cards.try_into().ok()?,
+                bid,
+            })
+        },
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn hands(input: &str) -> IResult<&str, Vec<Hand>> {
+    separated_list1(line_ending, hand)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use proptest::prelude::*;
+
+    fn any_hand() -> impl Strategy<Value = Hand> {
+        let card = prop::sample::select(LABELS.chars().collect::<Vec<_>>());
+
+        (prop::array::uniform5(card), 1..1000_u32).prop_map(|(cards, bid)| Hand { cards, bid })
+    }
+
+    /// A hand written with any run of spaces and tabs before its bid and
+    /// either line ending, paired with the hand it reads as.
+    fn any_spaced_hand() -> impl Strategy<Value = (Hand, String)> {
+        (
+            any_hand(),
+            "[ \t]{1,3}",
+            prop_oneof![Just("\n"), Just("\r\n")],
+        )
+            .prop_map(|(hand, gap, end)| {
+                let line = hand.to_string().replacen(' ', &gap, 1) + end;
+                (hand, line)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in prop::collection::vec(any_hand(), 1..20)) {
+            let input = expected.iter().map(|hand| format!("{hand}\n")).collect::<String>();
+            prop_assert_eq!(parse_all_nom7(hands, &input), Ok(expected));
+        }
+
+        #[test]
+        fn spaced_round_trip(lines in prop::collection::vec(any_spaced_hand(), 1..20)) {
+            let input = lines.iter().map(|(_, line)| line.as_str()).collect::<String>();
+            let expected = lines.into_iter().map(|(hand, _)| hand).collect::<Vec<_>>();
+            prop_assert_eq!(parse_all_nom7(hands, &input), Ok(expected));
+        }
+    }
+}
@@ -1,12 +1,7 @@
+use crate::{parse, Hand};
 use anyhow::{Context, Result};
+use aoc_core::SolveError;
 use itertools::Itertools;
-use nom::{
-    character::complete::{self, line_ending, one_of, space1},
-    combinator::map,
-    multi::{many1, separated_list1},
-    sequence::separated_pair,
-    IResult,
-};
 use std::collections::BTreeMap;
 use tracing::info;
 
@@ -33,7 +28,7 @@ impl From<char> for Card {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 enum RankedHand {
     HighCard(Vec<Card>),
     OnePair(Vec<Card>),
@@ -44,25 +39,8 @@ enum RankedHand {
     FiveOfAKind(Vec<Card>),
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct Hand {
-    cards: RankedHand,
-    bid: u32,
-}
-
-#[tracing::instrument(skip(input))]
-fn bid(input: &str) -> IResult<&str, u32> {
-    complete::u32(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn card(input: &str) -> IResult<&str, Card> {
-    map(one_of("AKQJT98765432"), |c| c.into())(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn cards(input: &str) -> IResult<&str, RankedHand> {
-    map(many1(card), |cards| {
+impl From<Vec<Card>> for RankedHand {
+    fn from(cards: Vec<Card>) -> Self {
         let mut map = cards
             .iter()
             .cloned()
@@ -114,43 +92,46 @@ fn cards(input: &str) -> IResult<&str, RankedHand> {
         info!(?ranked_hand);
 
         ranked_hand
-    })(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn hand(input: &str) -> IResult<&str, Hand> {
-    map(separated_pair(cards, space1, bid), |(cards, bid)| Hand {
-        cards,
-        bid,
-    })(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn hands(input: &str) -> IResult<&str, Vec<Hand>> {
-    separated_list1(line_ending, hand)(input)
+    }
 }
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(hands))]
+fn process(hands: &[Hand]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, mut hands) = hands(input)?;
-    hands.sort();
-
-    let result = hands
+    let mut ranked = hands
+        .iter()
+        .map(|hand| {
+            let cards = hand
+                .cards
+                .iter()
+                .map(|&c| Card::from(c))
+                .collect::<Vec<_>>();
+            (RankedHand::from(cards), hand.bid)
+        })
+        .collect::<Vec<_>>();
+    ranked.sort();
+
+    let result = ranked
         .iter()
         .enumerate()
-        .map(|(index, hand)| (index + 1) as u32 * hand.bid)
-        .sum::<u32>();
+        .map(|(index, (_, bid))| u32::try_from(index + 1).ok()?.checked_mul(*bid))
+        .try_fold(0_u32, |sum, winnings| sum.checked_add(winnings?))
+        .ok_or_else(|| SolveError::malformed("the winnings do not fit in a u32"))?;
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(hands))]
+pub fn solve_part2(hands: &[Hand]) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
+
+    process(hands).context("process part 2")
+}
 
-    process(input).context("process part 2")
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -165,7 +146,7 @@ KK677 28
 KTJJT 220
 QQQJA 483
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "5905");
     }
 }
@@ -0,0 +1,111 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, line_ending},
+    combinator::map,
+    multi::fold_many1,
+    sequence::{separated_pair, terminated, tuple},
+    IResult,
+};
+use std::{collections::BTreeMap, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Node<'a> {
+    pub left: &'a str,
+    pub right: &'a str,
+}
+
+/// The left/right instructions and the network of nodes they walk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CamelMap<'a> {
+    pub directions: &'a str,
+    pub nodes: BTreeMap<&'a str, Node<'a>>,
+}
+
+impl fmt::Display for CamelMap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}\n", self.directions)?;
+
+        for (name, Node { left, right }) in &self.nodes {
+            writeln!(f, "{name} = ({left}, {right})")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(input))]
+fn directions(input: &str) -> IResult<&str, &str> {
+    terminated(alphanumeric1, line_ending)(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn node(input: &str) -> IResult<&str, (&str, Node<'_>)> {
+    terminated(
+        map(
+            tuple((
+                alphanumeric1,
+                tag(" = ("),
+                alphanumeric1,
+                tag(", "),
+                alphanumeric1,
+                tag(")"),
+            )),
+            |(name, _, left, _, right, _)| (name, Node { left, right }),
+        ),
+        line_ending,
+    )(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn nodes(input: &str) -> IResult<&str, BTreeMap<&str, Node<'_>>> {
+    fold_many1(node, BTreeMap::new, |mut acc, (name, node)| {
+        acc.insert(name, node);
+        acc
+    })(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn camel_map(input: &str) -> IResult<&str, CamelMap<'_>> {
+    map(
+        separated_pair(directions, line_ending, nodes),
+        |(directions, nodes)| CamelMap { directions, nodes },
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use proptest::prelude::*;
+
+    /// Parsed maps borrow from their input, so generated names are leaked
+    /// to live as long as the test.
+    fn leaked(pattern: &'static str) -> impl Strategy<Value = &'static str> {
+        pattern.prop_map(|s| &*s.leak())
+    }
+
+    fn any_camel_map() -> impl Strategy<Value = CamelMap<'static>> {
+        let name = || leaked("[0-9A-Z]{2}[AZ0-9]");
+        let node = (name(), name()).prop_map(|(left, right)| Node { left, right });
+
+        (
+            leaked("[LR]{1,20}"),
+            prop::collection::btree_map(name(), node, 1..20),
+        )
+            .prop_map(|(directions, nodes)| CamelMap { directions, nodes })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(expected in any_camel_map()) {
+            let input = expected.to_string();
+            prop_assert_eq!(parse_all_nom7(camel_map, &input), Ok(expected));
+        }
+
+        #[test]
+        fn crlf_round_trip(expected in any_camel_map()) {
+            let input = expected.to_string().replace('\n', "\r\n");
+            prop_assert_eq!(parse_all_nom7(camel_map, &input), Ok(expected));
+        }
+    }
+}
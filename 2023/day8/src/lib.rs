@@ -1,5 +1,42 @@
+mod camel_map;
 mod part1;
 mod part2;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use camel_map::{camel_map, CamelMap, Node};
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+
+use anyhow::{Context, Result};
+use aoc_core::Solution;
+use aoc_parse::parse_all_nom7;
+use std::fmt::Display;
+
+/// The map both parts follow through the desert.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<CamelMap<'static>> {
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all_nom7(camel_map, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 8;
+
+    type Input = CamelMap<'static>;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+}
@@ -1,101 +1,71 @@
-use std::collections::BTreeMap;
-
+use crate::{parse, CamelMap};
 use anyhow::{Context, Result};
-use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, line_ending},
-    combinator::map,
-    multi::fold_many1,
-    sequence::{separated_pair, terminated, tuple},
-    IResult,
-};
+use aoc_core::SolveError;
 use tracing::info;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Node<'a> {
-    left: &'a str,
-    right: &'a str,
-}
-
-#[tracing::instrument(skip(input))]
-fn directions(input: &str) -> IResult<&str, &str> {
-    terminated(alpha1, line_ending)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn node<'a>(input: &'a str) -> IResult<&'a str, (&'a str, Node<'a>)> {
-    terminated(
-        map(
-            tuple((alpha1, tag(" = ("), alpha1, tag(", "), alpha1, tag(")"))),
-            |(name, _, left, _, right, _)| (name, Node { left, right }),
-        ),
-        line_ending,
-    )(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn nodes<'a>(input: &'a str) -> IResult<&str, BTreeMap<&str, Node<'a>>> {
-    fold_many1(node, BTreeMap::new, |mut acc, (name, node)| {
-        acc.insert(name, node);
-        acc
-    })(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn camel_map<'a>(input: &'a str) -> IResult<&'a str, (&str, BTreeMap<&str, Node<'a>>)> {
-    separated_pair(directions, line_ending, nodes)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(map))]
+fn process(map: &CamelMap) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, (directions, nodes)) = camel_map(input)?;
+    let CamelMap { directions, nodes } = map;
     let (mut name, mut node) = nodes
         .iter()
         .next()
-        .expect("should have at least one element");
+        .ok_or_else(|| SolveError::malformed("no nodes"))?;
 
     let mut next_name = name;
 
+    // Folding stops with `Err(Ok(steps))` once ZZZ is reached, or with
+    // `Err(Err(_))` when the map sends us somewhere it does not describe, or
+    // round in circles for longer than we are allowed to look.
     let result = directions
         .chars()
         .cycle()
         .try_fold(0, |mut acc, direction| {
+            aoc_core::step().map_err(Err)?;
+
             node = match direction {
                 'L' => {
-                    next_name = &&node.left;
-                    nodes.get(node.left).expect("should have a valid left node")
+                    next_name = &node.left;
+                    nodes.get(node.left)
                 }
                 'R' => {
-                    next_name = &&node.right;
-                    nodes
-                        .get(node.right)
-                        .expect("should have a valid right node")
+                    next_name = &node.right;
+                    nodes.get(node.right)
                 }
-                _ => unreachable!(),
-            };
+                _ => {
+                    return Err(Err(SolveError::malformed(format!(
+                        "unknown direction {direction:?}"
+                    ))))
+                }
+            }
+            .ok_or_else(|| Err(SolveError::malformed(format!("unknown node {next_name}"))))?;
 
             if name == &"ZZZ" {
-                Err(acc)
+                Err(Ok(acc))
             } else {
                 name = next_name;
                 acc += 1;
                 Ok(acc)
             }
         })
-        .unwrap_err();
+        .unwrap_err()?;
 
     info!(?result);
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(map))]
+pub fn solve_part1(map: &CamelMap) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
+
+    process(map).context("process part 1")
+}
 
-    process(input).context("process part 1")
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -114,7 +84,7 @@ EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "2");
     }
 
@@ -126,7 +96,7 @@ AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "6");
     }
 }
@@ -1,22 +1,9 @@
-use std::{cmp::Ordering, collections::BTreeMap};
-
+use crate::{parse, CamelMap};
 use anyhow::{Context, Result};
-use nom::{
-    bytes::complete::tag,
-    character::complete::{alphanumeric1, line_ending},
-    combinator::map,
-    multi::fold_many1,
-    sequence::{separated_pair, terminated, tuple},
-    IResult,
-};
+use aoc_core::SolveError;
+use std::cmp::Ordering;
 use tracing::info;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Node<'a> {
-    left: &'a str,
-    right: &'a str,
-}
-
 fn gcd(a: usize, b: usize) -> usize {
     match (a, b) {
         (a, 0) => a,
@@ -32,47 +19,11 @@ fn lcm(a: usize, b: usize) -> usize {
     a * b / gcd(a, b)
 }
 
-#[tracing::instrument(skip(input))]
-fn directions(input: &str) -> IResult<&str, &str> {
-    terminated(alphanumeric1, line_ending)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn node<'a>(input: &'a str) -> IResult<&'a str, (&'a str, Node<'a>)> {
-    terminated(
-        map(
-            tuple((
-                alphanumeric1,
-                tag(" = ("),
-                alphanumeric1,
-                tag(", "),
-                alphanumeric1,
-                tag(")"),
-            )),
-            |(name, _, left, _, right, _)| (name, Node { left, right }),
-        ),
-        line_ending,
-    )(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn nodes<'a>(input: &'a str) -> IResult<&str, BTreeMap<&str, Node<'a>>> {
-    fold_many1(node, BTreeMap::new, |mut acc, (name, node)| {
-        acc.insert(name, node);
-        acc
-    })(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn camel_map<'a>(input: &'a str) -> IResult<&'a str, (&str, BTreeMap<&str, Node<'a>>)> {
-    separated_pair(directions, line_ending, nodes)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(map))]
+fn process(map: &CamelMap) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, (directions, nodes)) = camel_map(input)?;
+    let CamelMap { directions, nodes } = map;
     let starting_points = nodes
         .iter()
         .filter(|(name, _)| name.ends_with('A'))
@@ -91,22 +42,29 @@ fn process(input: &'static str) -> Result<String> {
                 .chars()
                 .cycle()
                 .try_fold(0, |mut acc, direction| {
+                    aoc_core::step().map_err(Err)?;
+
                     node = match direction {
                         'L' => {
-                            next_name = &&node.left;
-                            nodes.get(node.left).expect("should have a valid left node")
+                            next_name = &node.left;
+                            nodes.get(node.left)
                         }
                         'R' => {
-                            next_name = &&node.right;
-                            nodes
-                                .get(node.right)
-                                .expect("should have a valid right node")
+                            next_name = &node.right;
+                            nodes.get(node.right)
+                        }
+                        _ => {
+                            return Err(Err(SolveError::malformed(format!(
+                                "unknown direction {direction:?}"
+                            ))))
                         }
-                        _ => unreachable!(),
-                    };
+                    }
+                    .ok_or_else(|| {
+                        Err(SolveError::malformed(format!("unknown node {next_name}")))
+                    })?;
 
                     if name.ends_with('Z') {
-                        Err(acc)
+                        Err(Ok(acc))
                     } else {
                         name = next_name;
                         acc += 1;
@@ -115,19 +73,26 @@ fn process(input: &'static str) -> Result<String> {
                 })
                 .unwrap_err()
         })
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
         .reduce(lcm)
-        .unwrap();
+        .ok_or_else(|| SolveError::malformed("no node ends with A"))?;
 
     info!(?result);
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(map))]
+pub fn solve_part2(map: &CamelMap) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
+
+    process(map).context("process part 2")
+}
 
-    process(input).context("process part 2")
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -147,7 +112,7 @@ mod tests {
 22Z = (22B, 22B)
 XXX = (XXX, XXX)
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "6");
     }
 }
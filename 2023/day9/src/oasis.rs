@@ -0,0 +1,105 @@
+use nom::{
+    character::complete::{self, line_ending, space1},
+    multi::separated_list1,
+    IResult,
+};
+
+#[tracing::instrument(skip(input))]
+fn history(input: &str) -> IResult<&str, Vec<i32>> {
+    separated_list1(space1, complete::i32)(input)
+}
+
+/// The OASIS report: one history of readings per line.
+#[tracing::instrument(skip(input))]
+pub fn oasis(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
+    separated_list1(line_ending, history)(input)
+}
+
+/// The history followed by each row of differences between neighbouring
+/// readings, down to the first row that is all zeros, or empty for a
+/// history of one reading. `None` if a difference does not fit in an `i32`.
+pub fn differences(history: &[i32]) -> Option<Vec<Vec<i32>>> {
+    let mut rows = vec![history.to_vec()];
+
+    while let Some(row) = rows.last().filter(|row| row.iter().any(|&n| n != 0)) {
+        let next = row
+            .windows(2)
+            .map(|pair| pair[1].checked_sub(pair[0]))
+            .collect::<Option<Vec<_>>>()?;
+        rows.push(next);
+    }
+
+    Some(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all_nom7;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    fn render(oasis: &[Vec<i32>]) -> String {
+        oasis
+            .iter()
+            .map(|history| format!("{}\n", history.iter().join(" ")))
+            .collect()
+    }
+
+    /// A report written with any run of spaces and tabs between readings
+    /// and either line ending, paired with the histories it reads as.
+    fn any_spaced_oasis() -> impl Strategy<Value = (Vec<Vec<i32>>, String)> {
+        let history = prop::collection::vec((any::<i32>(), "[ \t]{1,3}"), 1..25);
+
+        prop::collection::vec((history, prop_oneof![Just("\n"), Just("\r\n")]), 1..20).prop_map(
+            |lines| {
+                let input = lines
+                    .iter()
+                    .map(|(history, end)| {
+                        let readings = history
+                            .iter()
+                            .map(|(reading, gap)| format!("{gap}{reading}"))
+                            .collect::<String>();
+                        format!("{}{end}", readings.trim_start_matches([' ', '\t']))
+                    })
+                    .collect();
+                let oasis = lines
+                    .into_iter()
+                    .map(|(history, _)| history.into_iter().map(|(reading, _)| reading).collect())
+                    .collect();
+
+                (oasis, input)
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(
+            expected in prop::collection::vec(prop::collection::vec(any::<i32>(), 1..25), 1..20),
+        ) {
+            let input = render(&expected);
+            prop_assert_eq!(parse_all_nom7(oasis, &input), Ok(expected));
+        }
+
+        #[test]
+        fn spaced_round_trip((expected, input) in any_spaced_oasis()) {
+            prop_assert_eq!(parse_all_nom7(oasis, &input), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn differences_stop_at_zeros() {
+        assert_eq!(
+            differences(&[1, 3, 6]),
+            Some(vec![vec![1, 3, 6], vec![2, 3], vec![1], vec![]])
+        );
+        assert_eq!(differences(&[1]), Some(vec![vec![1], vec![]]));
+        assert_eq!(differences(&[0, 0]), Some(vec![vec![0, 0]]));
+    }
+
+    #[test]
+    fn differences_overflow() {
+        assert_eq!(differences(&[i32::MIN, i32::MAX]), None);
+    }
+}
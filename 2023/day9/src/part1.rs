@@ -1,56 +1,38 @@
+use crate::{oasis::differences, parse};
 use anyhow::{Context, Result};
-use itertools::Itertools;
-use nom::{
-    character::complete::{self, line_ending, space1},
-    multi::separated_list1,
-    IResult,
-};
-use std::ops::Not;
+use aoc_core::SolveError;
 use tracing::info;
 
-#[tracing::instrument(skip(input))]
-fn history(input: &str) -> IResult<&str, Vec<i32>> {
-    separated_list1(space1, complete::i32)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn oasis(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
-    separated_list1(line_ending, history)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(oasis))]
+fn process(oasis: &[Vec<i32>]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, oasis) = oasis(input)?;
-
     info!(?oasis);
 
     let result = oasis
-        .into_iter()
+        .iter()
         .map(|history| {
-            std::iter::successors(Some(history), |measurements| {
-                measurements.iter().all(|num| num == &0).not().then_some(
-                    measurements
-                        .iter()
-                        .tuple_windows::<(&i32, &i32)>()
-                        .map(|(left, right)| right - left)
-                        .collect(),
-                )
-            })
-            .map(|v| *v.last().unwrap())
-            .sum::<i32>()
+            differences(history)?
+                .iter()
+                .filter_map(|row| row.last())
+                .try_fold(0_i32, |acc, &n| acc.checked_add(n))
         })
-        .sum::<i32>();
+        .try_fold(0_i32, |acc, next| acc.checked_add(next?))
+        .ok_or_else(|| SolveError::malformed("the predictions do not fit in an i32"))?;
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(oasis))]
+pub fn solve_part1(oasis: &[Vec<i32>]) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
+
+    process(oasis).context("process part 1")
+}
 
-    process(input).context("process part 1")
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -63,7 +45,7 @@ mod tests {
 1 3 6 10 15 21
 10 13 16 21 30 45
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "114");
     }
 }
@@ -1,58 +1,40 @@
+use crate::{oasis::differences, parse};
 use anyhow::{Context, Result};
-use itertools::Itertools;
-use nom::{
-    character::complete::{self, line_ending, space1},
-    multi::separated_list1,
-    IResult,
-};
-use std::ops::Not;
+use aoc_core::SolveError;
 use tracing::info;
 
-#[tracing::instrument(skip(input))]
-fn history(input: &str) -> IResult<&str, Vec<i32>> {
-    separated_list1(space1, complete::i32)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn oasis(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
-    separated_list1(line_ending, history)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(oasis))]
+fn process(oasis: &[Vec<i32>]) -> Result<String, SolveError> {
     info!("processing input");
-    let (_, oasis) = oasis(input)?;
-
     info!(?oasis);
 
+    // Each row's prediction is its first reading minus the prediction of
+    // the row below, so fold from the bottom row upwards.
     let result = oasis
-        .into_iter()
+        .iter()
         .map(|history| {
-            std::iter::successors(Some(history), |measurements| {
-                measurements.iter().all(|num| num == &0).not().then_some(
-                    measurements
-                        .iter()
-                        .tuple_windows::<(&i32, &i32)>()
-                        .map(|(left, right)| right - left)
-                        .collect(),
-                )
-            })
-            .map(|v| *v.first().unwrap())
-            .fold(0, |mut acc, n| {
-                acc = n - acc;
-                acc
-            })
+            differences(history)?
+                .iter()
+                .rev()
+                .filter_map(|row| row.first())
+                .try_fold(0_i32, |acc, &n| n.checked_sub(acc))
         })
-        .sum::<i32>();
+        .try_fold(0_i32, |acc, next| acc.checked_add(next?))
+        .ok_or_else(|| SolveError::malformed("the predictions do not fit in an i32"))?;
 
     Ok(result.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(oasis))]
+pub fn solve_part2(oasis: &[Vec<i32>]) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
 
-    process(input).context("process part 2")
+    process(oasis).context("process part 2")
+}
+
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -65,7 +47,14 @@ mod tests {
 1 3 6 10 15 21
 10 13 16 21 30 45
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
+        assert_eq!(result, "2");
+    }
+
+    #[test_log::test]
+    fn odd_depth() {
+        // Three rows of differences below the history: 2 3 4, 1 1 and 0.
+        let result = process(&parse("3 5 8 12\n").unwrap()).unwrap();
         assert_eq!(result, "2");
     }
 }
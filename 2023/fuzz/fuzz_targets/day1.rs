@@ -0,0 +1,5 @@
+#![no_main]
+
+libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+    aoc_runner::fuzz(&aoc_runner::Day::solution::<day1::Puzzle>("day1"), data);
+});
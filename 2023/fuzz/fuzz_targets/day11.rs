@@ -0,0 +1,5 @@
+#![no_main]
+
+libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+    aoc_runner::fuzz(&aoc_runner::Day::solution::<day11::Puzzle>("day11"), data);
+});
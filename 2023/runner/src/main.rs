@@ -0,0 +1,23 @@
+use std::{path::Path, process::ExitCode};
+
+const YEAR: u16 = 2023;
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+fn main() -> ExitCode {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    aoc_runner::main(YEAR, &root, DAYS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Inputs fuzzing found a day panicking on, see `fuzz/`.
+    #[test]
+    fn fuzz_regressions() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let crashes = aoc_runner::replay(&root.join("fuzz/regressions"), DAYS).unwrap();
+        assert!(crashes.is_empty(), "{crashes:#?}");
+    }
+}
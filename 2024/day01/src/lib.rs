@@ -1,5 +1,43 @@
+mod lists;
 mod part1;
 mod part2;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use lists::{LocationLists, Metric};
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+
+use anyhow::{Context, Result};
+use aoc_core::Solution;
+use std::fmt::Display;
+use tracing::info;
+
+/// The two location lists both parts compare.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<LocationLists> {
+    info!("parsing input");
+    let _frame = aoc_telemetry::frame!("parse");
+
+    LocationLists::parse(input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 1;
+
+    type Input = LocationLists;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+}
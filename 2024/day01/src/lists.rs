@@ -0,0 +1,190 @@
+use aoc_parse::{parse_all_nom7, ParseError};
+use nom::{
+    character::complete::{self, line_ending, space1},
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult,
+};
+use std::{cmp::Ordering, collections::HashMap, fmt};
+
+/// How the distance between the two lists is measured, pairing the smallest
+/// number on the left with the smallest on the right and so on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Sum of the absolute differences, as asked by the puzzle.
+    Manhattan,
+    /// Sum of the squared differences.
+    SquaredEuclidean,
+    /// Largest absolute difference.
+    Chebyshev,
+}
+
+/// Both location lists, sorted, together with how often each number appears
+/// in the right list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocationLists {
+    left: Vec<i64>,
+    right: Vec<i64>,
+    right_counts: HashMap<i64, i64>,
+}
+
+impl LocationLists {
+    pub fn new(mut left: Vec<i64>, mut right: Vec<i64>) -> Self {
+        left.sort_unstable();
+        right.sort_unstable();
+
+        let mut right_counts = HashMap::new();
+        for n in &right {
+            *right_counts.entry(*n).or_insert(0) += 1;
+        }
+
+        Self {
+            left,
+            right,
+            right_counts,
+        }
+    }
+
+    #[tracing::instrument(skip(input))]
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let numbers = parse_all_nom7(numbers, input)?;
+        let (left, right) = numbers.into_iter().unzip();
+
+        Ok(Self::new(left, right))
+    }
+
+    pub fn left(&self) -> &[i64] {
+        &self.left
+    }
+
+    pub fn right(&self) -> &[i64] {
+        &self.right
+    }
+
+    /// `None` if the distance does not fit in a `u64`.
+    pub fn distance(&self) -> Option<u64> {
+        self.distance_with(Metric::Manhattan)
+    }
+
+    /// `None` if the distance does not fit in a `u64`.
+    pub fn distance_with(&self, metric: Metric) -> Option<u64> {
+        let mut differences = self
+            .left
+            .iter()
+            .zip(&self.right)
+            .map(|(a, b)| a.abs_diff(*b));
+
+        match metric {
+            Metric::Manhattan => differences.try_fold(0_u64, |total, d| total.checked_add(d)),
+            Metric::SquaredEuclidean => {
+                differences.try_fold(0_u64, |total, d| total.checked_add(d.checked_mul(d)?))
+            }
+            Metric::Chebyshev => Some(differences.max().unwrap_or(0)),
+        }
+    }
+
+    /// Each number on the left multiplied by how often it appears on the
+    /// right. `None` if the score does not fit in an `i64`.
+    pub fn similarity(&self) -> Option<i64> {
+        self.left.iter().try_fold(0_i64, |total, n| {
+            let count = self.right_counts.get(n).copied().unwrap_or(0);
+            total.checked_add(n.checked_mul(count)?)
+        })
+    }
+
+    /// Numbers present in both lists, each repeated as many times as it
+    /// appears in the list holding fewer copies of it.
+    pub fn intersection(&self) -> Vec<i64> {
+        let (mut i, mut j) = (0, 0);
+        let mut common = Vec::new();
+
+        while i < self.left.len() && j < self.right.len() {
+            match self.left[i].cmp(&self.right[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    common.push(self.left[i]);
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        common
+    }
+}
+
+/// Written as the input, sorted, one pair per line.
+impl fmt::Display for LocationLists {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (left, right) in self.left.iter().zip(&self.right) {
+            writeln!(f, "{left}   {right}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[tracing::instrument(skip(input))]
+fn number_pair(input: &str) -> IResult<&str, (i64, i64)> {
+    separated_pair(complete::i64, space1, complete::i64)(input)
+}
+
+#[tracing::instrument(skip(input))]
+fn numbers(input: &str) -> IResult<&str, Vec<(i64, i64)>> {
+    separated_list1(line_ending, number_pair)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn round_trip(pairs in prop::collection::vec((0..100_000_i64, 0..100_000_i64), 1..100)) {
+            let (left, right) = pairs.into_iter().unzip();
+            let expected = LocationLists::new(left, right);
+
+            prop_assert_eq!(LocationLists::parse(&expected.to_string()), Ok(expected));
+        }
+    }
+
+    const EXAMPLE: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+    #[test_log::test]
+    fn statistics() {
+        let lists = LocationLists::parse(EXAMPLE).unwrap();
+
+        assert_eq!(lists.distance(), Some(11));
+        assert_eq!(lists.distance_with(Metric::SquaredEuclidean), Some(35));
+        assert_eq!(lists.distance_with(Metric::Chebyshev), Some(5));
+        assert_eq!(lists.similarity(), Some(31));
+        assert_eq!(lists.intersection(), vec![3, 3, 3, 4]);
+    }
+
+    #[test_log::test]
+    fn overflow() {
+        let lists = LocationLists::new(vec![i64::MIN, i64::MIN], vec![i64::MAX, i64::MAX]);
+
+        assert_eq!(lists.distance(), None);
+        assert_eq!(lists.distance_with(Metric::SquaredEuclidean), None);
+        assert_eq!(lists.distance_with(Metric::Chebyshev), Some(u64::MAX));
+
+        let lists = LocationLists::new(vec![i64::MAX], vec![i64::MAX, i64::MAX]);
+        assert_eq!(lists.similarity(), None);
+    }
+
+    #[test]
+    fn trailing_garbage_is_rejected() {
+        let error = LocationLists::parse("3   4\n4   3\ngarbage\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (3, 1));
+    }
+}
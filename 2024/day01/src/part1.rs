@@ -1,46 +1,29 @@
+use crate::{lists::LocationLists, parse};
 use anyhow::{Context, Result};
-use nom::{
-    character::complete::{self, line_ending, space1},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
-};
+use aoc_core::SolveError;
 use tracing::info;
 
-#[tracing::instrument(skip(input))]
-fn number_pair(input: &str) -> IResult<&str, (i64, i64)> {
-    separated_pair(complete::i64, space1, complete::i64)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn numbers(input: &str) -> IResult<&str, Vec<(i64, i64)>> {
-    separated_list1(line_ending, number_pair)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(lists))]
+fn process(lists: &LocationLists) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, numbers) = numbers(input)?;
-    let (mut a, mut b): (Vec<i64>, Vec<i64>) = numbers.into_iter().unzip();
+    let distance = lists
+        .distance()
+        .ok_or_else(|| SolveError::malformed("the distance does not fit in a u64"))?;
 
-    a.sort();
-    b.sort();
+    Ok(distance.to_string())
+}
 
-    let d = a
-        .iter()
-        .zip(b.iter())
-        .map(|(a, b)| (b - a).abs())
-        .sum::<i64>();
+#[tracing::instrument(skip(lists))]
+pub fn solve_part1(lists: &LocationLists) -> Result<String> {
+    info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
 
-    Ok(d.to_string())
+    process(lists).context("process part 1")
 }
 
-#[tracing::instrument(skip(input))]
 pub fn part1(input: &'static str) -> Result<String> {
-    info!("part 1");
-
-    process(input).context("process part 1")
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -56,7 +39,7 @@ mod tests {
 3   9
 3   3
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "11");
     }
 }
@@ -1,42 +1,29 @@
+use crate::{lists::LocationLists, parse};
 use anyhow::{Context, Result};
-use nom::{
-    character::complete::{self, line_ending, space1},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
-};
+use aoc_core::SolveError;
 use tracing::info;
 
-#[tracing::instrument(skip(input))]
-fn number_pair(input: &str) -> IResult<&str, (i64, i64)> {
-    separated_pair(complete::i64, space1, complete::i64)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn numbers(input: &str) -> IResult<&str, Vec<(i64, i64)>> {
-    separated_list1(line_ending, number_pair)(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(lists))]
+fn process(lists: &LocationLists) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, numbers) = numbers(input)?;
-    let (a, b): (Vec<i64>, Vec<i64>) = numbers.into_iter().unzip();
-
-    let d: i64 = a
-        .iter()
-        .map(|x| *x * b.iter().filter(|y| *y == x).count() as i64)
-        .sum();
+    let similarity = lists
+        .similarity()
+        .ok_or_else(|| SolveError::malformed("the similarity score does not fit in an i64"))?;
 
-    Ok(d.to_string())
+    Ok(similarity.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(lists))]
+pub fn solve_part2(lists: &LocationLists) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
+
+    process(lists).context("process part 2")
+}
 
-    process(input).context("process part 2")
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -52,7 +39,7 @@ mod tests {
 3   9
 3   3
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "31");
     }
 }
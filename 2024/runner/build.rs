@@ -0,0 +1,3 @@
+fn main() -> anyhow::Result<()> {
+    aoc_build::days()
+}
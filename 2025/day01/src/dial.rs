@@ -0,0 +1,246 @@
+use aoc_grid::Direction4;
+use aoc_parse::{lines, unsigned};
+use nom::{IResult, Parser, character::complete::one_of, combinator::map_opt, sequence::pair};
+use std::fmt;
+
+/// A turn of the dial by some clicks, heading west (`L`) towards lower
+/// numbers or east (`R`) towards higher ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rotation {
+    direction: Direction4,
+    clicks: u32,
+}
+
+impl Rotation {
+    /// `None` for a vertical heading, which a dial cannot turn.
+    pub fn new(direction: Direction4, clicks: u32) -> Option<Self> {
+        direction
+            .is_horizontal()
+            .then_some(Self { direction, clicks })
+    }
+
+    pub fn left(clicks: u32) -> Self {
+        Self {
+            direction: Direction4::West,
+            clicks,
+        }
+    }
+
+    pub fn right(clicks: u32) -> Self {
+        Self {
+            direction: Direction4::East,
+            clicks,
+        }
+    }
+
+    pub fn direction(&self) -> Direction4 {
+        self.direction
+    }
+
+    pub fn clicks(&self) -> u32 {
+        self.clicks
+    }
+
+    /// Signed number of clicks, positive towards higher numbers.
+    pub fn delta(&self) -> i64 {
+        let (dx, _) = self.direction.delta();
+        dx as i64 * i64::from(self.clicks)
+    }
+}
+
+/// Written as a line of the input, like `L68`.
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let turn = if self.direction == Direction4::West {
+            'L'
+        } else {
+            'R'
+        };
+
+        write!(f, "{turn}{}", self.clicks)
+    }
+}
+
+/// What happened to the dial during a single rotation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Clicks {
+    /// Whether the dial ended the rotation pointing at zero.
+    pub landed_on_zero: bool,
+    /// How many clicks, including the last one, left the dial pointing at
+    /// zero.
+    pub passed_zero: u64,
+}
+
+/// A safe dial numbered `0..size`, wrapping around in both directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dial {
+    size: i64,
+    position: i64,
+}
+
+impl Dial {
+    pub fn new(size: i64, position: i64) -> Self {
+        assert!(size > 0, "dial size must be positive");
+
+        Self {
+            size,
+            position: position.rem_euclid(size),
+        }
+    }
+
+    pub fn size(&self) -> i64 {
+        self.size
+    }
+
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    /// Turns the dial, counting zeros arithmetically instead of click by
+    /// click.
+    pub fn rotate(&mut self, rotation: Rotation) -> Clicks {
+        let delta = rotation.delta();
+        let n = delta.unsigned_abs();
+        let size = self.size as u64;
+
+        // Distance to the first click that lands on zero going this way.
+        let first = match (delta >= 0, self.position) {
+            (_, 0) => size,
+            (true, p) => size - p as u64,
+            (false, p) => p as u64,
+        };
+        let passed_zero = if n < first { 0 } else { (n - first) / size + 1 };
+
+        self.position = (self.position + delta).rem_euclid(self.size);
+
+        Clicks {
+            landed_on_zero: self.position == 0,
+            passed_zero,
+        }
+    }
+
+    /// Applies every rotation, adding up what each one did.
+    pub fn rotate_all(&mut self, rotations: impl IntoIterator<Item = Rotation>) -> (u64, u64) {
+        rotations
+            .into_iter()
+            .map(|rotation| self.rotate(rotation))
+            .fold((0, 0), |(landed, passed), clicks| {
+                (
+                    landed + u64::from(clicks.landed_on_zero),
+                    passed + clicks.passed_zero,
+                )
+            })
+    }
+}
+
+impl Default for Dial {
+    /// The puzzle's dial: numbers 0 through 99, starting at 50.
+    fn default() -> Self {
+        Self::new(100, 50)
+    }
+}
+
+#[tracing::instrument(skip(input))]
+fn rotation(input: &str) -> IResult<&str, Rotation> {
+    map_opt(pair(one_of("LR"), unsigned()), |(c, clicks)| {
+        Rotation::new(Direction4::try_from(c).ok()?, clicks)
+    })
+    .parse(input)
+}
+
+#[tracing::instrument(skip(input))]
+pub fn rotations(input: &str) -> IResult<&str, Vec<Rotation>> {
+    lines(rotation).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Reference implementation turning the dial one click at a time.
+    fn simulate(size: i64, position: i64, rotation: Rotation) -> (i64, Clicks) {
+        let step = rotation.delta().signum();
+        let mut position = position.rem_euclid(size);
+        let mut passed_zero = 0;
+
+        for _ in 0..rotation.delta().abs() {
+            position = (position + step).rem_euclid(size);
+            if position == 0 {
+                passed_zero += 1;
+            }
+        }
+
+        let clicks = Clicks {
+            landed_on_zero: position == 0,
+            passed_zero,
+        };
+
+        (position, clicks)
+    }
+
+    fn any_rotation() -> impl Strategy<Value = Rotation> {
+        prop_oneof![
+            (0..1000_u32).prop_map(Rotation::left),
+            (0..1000_u32).prop_map(Rotation::right),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn rotations_round_trip(expected in prop::collection::vec(any_rotation(), 1..50)) {
+            let input = expected.iter().map(|rotation| format!("{rotation}\n")).collect::<String>();
+            prop_assert_eq!(aoc_parse::parse_all(rotations, &input), Ok(expected));
+        }
+
+        #[test]
+        fn rotate_matches_simulation(
+            size in 1..150_i64,
+            position in -200..200_i64,
+            rotations in prop::collection::vec(any_rotation(), 0..20),
+        ) {
+            let mut dial = Dial::new(size, position);
+            let mut expected = position;
+
+            for rotation in rotations {
+                let (next, clicks) = simulate(size, expected, rotation);
+                expected = next;
+
+                prop_assert_eq!(dial.rotate(rotation), clicks);
+                prop_assert_eq!(dial.position(), expected);
+            }
+        }
+    }
+
+    #[test_log::test]
+    fn left_past_zero_from_zero() {
+        let mut dial = Dial::new(100, 0);
+
+        assert_eq!(
+            dial.rotate(Rotation::left(250)),
+            Clicks {
+                landed_on_zero: false,
+                passed_zero: 2
+            }
+        );
+        assert_eq!(dial.position(), 50);
+    }
+
+    #[test_log::test]
+    fn distances_are_unsigned() {
+        assert!(aoc_parse::parse_all(rotations, "L-5\n").is_err());
+        assert!(aoc_parse::parse_all(rotations, "U5\n").is_err());
+        assert_eq!(Rotation::new(Direction4::North, 5), None);
+        assert!(aoc_parse::parse_all(rotations, "L9223372036854775808\n").is_err());
+
+        let mut dial = Dial::default();
+        dial.rotate(Rotation::left(u32::MAX));
+        assert_eq!(dial.position(), (50 - i64::from(u32::MAX)).rem_euclid(100));
+    }
+
+    #[test_log::test]
+    fn trailing_garbage_is_rejected() {
+        assert!(aoc_parse::parse_all(rotations, "L5\nR3\n").is_ok());
+        assert!(aoc_parse::parse_all(rotations, "L5\nR3\ngarbage").is_err());
+    }
+}
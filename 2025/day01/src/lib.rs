@@ -1,5 +1,44 @@
+mod dial;
 mod part1;
 mod part2;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use dial::{Clicks, Dial, Rotation, rotations};
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+
+use anyhow::{Context, Result};
+use aoc_core::Solution;
+use aoc_parse::parse_all;
+use std::fmt::Display;
+use tracing::info;
+
+/// The rotations both parts follow.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Vec<Rotation>> {
+    info!("parsing input");
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all(rotations, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 1;
+
+    type Input = Vec<Rotation>;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+}
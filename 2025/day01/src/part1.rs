@@ -1,69 +1,30 @@
-use anyhow::{Context, Result};
-use nom::{
-    IResult, Parser,
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, line_ending},
-    combinator::map,
-    multi::separated_list1,
-    sequence::pair,
+use crate::{
+    dial::{Dial, Rotation},
+    parse,
 };
+use anyhow::{Context, Result};
+use aoc_core::SolveError;
 use tracing::info;
 
-#[derive(Clone, Copy, Debug)]
-enum Rotation {
-    Left(i64),
-    Right(i64),
-}
-
-#[tracing::instrument(skip(input))]
-fn rotation(input: &str) -> IResult<&str, Rotation> {
-    map(pair(alt((tag("L"), tag("R"))), complete::i64), |(c, n)| {
-        if c == "L" {
-            Rotation::Left(n)
-        } else {
-            Rotation::Right(n)
-        }
-    })
-    .parse(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn rotations(input: &str) -> IResult<&str, Vec<Rotation>> {
-    separated_list1(line_ending, rotation).parse(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(rotations))]
+fn process(rotations: &[Rotation]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, rotations) = rotations.parse(input)?;
-    let mut dial = 50;
-    let mut zero_count = 0;
+    let (landed, _) = Dial::default().rotate_all(rotations.iter().copied());
 
-    for rot in rotations {
-        match rot {
-            Rotation::Left(n) => {
-                dial = (dial - n) % 100;
-            }
-            Rotation::Right(n) => {
-                dial = (dial + n) % 100;
-            }
-        }
+    Ok(landed.to_string())
+}
 
-        if dial == 0 {
-            zero_count += 1;
-        }
-    }
+#[tracing::instrument(skip(rotations))]
+pub fn solve_part1(rotations: &[Rotation]) -> Result<String> {
+    info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
 
-    Ok(zero_count.to_string())
+    process(rotations).context("process part 1")
 }
 
-#[tracing::instrument(skip(input))]
 pub fn part1(input: &'static str) -> Result<String> {
-    info!("part 1");
-
-    process(input).context("process part 1")
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -83,7 +44,7 @@ L99
 R14
 L82
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "3");
     }
 }
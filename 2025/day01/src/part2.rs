@@ -1,81 +1,30 @@
-use anyhow::{Context, Result};
-use nom::{
-    IResult, Parser,
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, line_ending},
-    combinator::map,
-    multi::separated_list1,
-    sequence::pair,
+use crate::{
+    dial::{Dial, Rotation},
+    parse,
 };
+use anyhow::{Context, Result};
+use aoc_core::SolveError;
 use tracing::info;
 
-#[derive(Clone, Copy, Debug)]
-enum Rotation {
-    Left(i64),
-    Right(i64),
-}
-
-#[tracing::instrument(skip(input))]
-fn rotation(input: &str) -> IResult<&str, Rotation> {
-    map(pair(alt((tag("L"), tag("R"))), complete::i64), |(c, n)| {
-        if c == "L" {
-            Rotation::Left(n)
-        } else {
-            Rotation::Right(n)
-        }
-    })
-    .parse(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn rotations(input: &str) -> IResult<&str, Vec<Rotation>> {
-    separated_list1(line_ending, rotation).parse(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(rotations))]
+fn process(rotations: &[Rotation]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, rotations) = rotations.parse(input)?;
-    let mut dial = 50;
-    let mut crossings = 0;
+    let (_, passed) = Dial::default().rotate_all(rotations.iter().copied());
 
-    for rot in rotations {
-        match rot {
-            Rotation::Left(n) => {
-                crossings += n / 100;
-                let remainder = n % 100;
-
-                if dial > 0 && (dial - remainder) <= 0 {
-                    crossings += 1;
-                }
-
-                dial -= remainder;
-                dial = dial.rem_euclid(100);
-            }
-            Rotation::Right(n) => {
-                crossings += n / 100;
-                let remainder = n % 100;
-
-                dial += remainder;
+    Ok(passed.to_string())
+}
 
-                if dial >= 100 {
-                    crossings += 1;
-                }
-                dial = dial.rem_euclid(100);
-            }
-        }
-    }
+#[tracing::instrument(skip(rotations))]
+pub fn solve_part2(rotations: &[Rotation]) -> Result<String> {
+    info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
 
-    Ok(crossings.to_string())
+    process(rotations).context("process part 2")
 }
 
-#[tracing::instrument(skip(input))]
 pub fn part2(input: &'static str) -> Result<String> {
-    info!("part 2");
-
-    process(input).context("process part 2")
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -95,7 +44,7 @@ L99
 R14
 L82
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "6");
     }
 
@@ -105,7 +54,7 @@ L82
 L50
 L100
 L150"##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "4");
     }
 }
@@ -1,4 +1,7 @@
-use day01::{part1, part2};
+use day02::{parse, part1, part2, solve_part1, solve_part2};
+use divan::{Bencher, black_box};
+
+const INPUT: &str = include_str!("../input.txt");
 
 fn main() {
     divan::main();
@@ -6,10 +9,27 @@ fn main() {
 
 #[divan::bench]
 fn part1_benchmark() {
-    part1(divan::black_box(include_str!("../input.txt",))).unwrap();
+    part1(black_box(INPUT)).unwrap();
 }
 
 #[divan::bench]
 fn part2_benchmark() {
-    part2(divan::black_box(include_str!("../input.txt",))).unwrap();
+    part2(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn parse_benchmark() {
+    parse(black_box(INPUT)).unwrap();
+}
+
+#[divan::bench]
+fn solve_part1_benchmark(bencher: Bencher) {
+    let parsed = parse(INPUT).unwrap();
+    bencher.bench(|| solve_part1(black_box(&parsed)).unwrap());
+}
+
+#[divan::bench]
+fn solve_part2_benchmark(bencher: Bencher) {
+    let parsed = parse(INPUT).unwrap();
+    bencher.bench(|| solve_part2(black_box(&parsed)).unwrap());
 }
@@ -1,5 +1,45 @@
 mod part1;
 mod part2;
+mod repeats;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+pub use repeats::{Repeats, invalid_id_sum, invalid_ids, ranges};
+
+use anyhow::{Context, Result};
+use aoc_core::Solution;
+use aoc_parse::parse_all;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use tracing::info;
+
+/// The ID ranges both parts look for invalid IDs in.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Vec<RangeInclusive<u64>>> {
+    info!("parsing input");
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all(ranges, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 2;
+
+    type Input = Vec<RangeInclusive<u64>>;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+}
@@ -1,70 +1,34 @@
-use std::ops::RangeInclusive;
-
-use anyhow::{Context, Result};
-use nom::{
-    IResult, Parser,
-    bytes::complete::tag,
-    character::complete::{self},
-    combinator::map,
-    multi::separated_list1,
-    sequence::separated_pair,
+use crate::{
+    parse,
+    repeats::{Repeats, invalid_id_sum},
 };
+use anyhow::{Context, Result};
+use aoc_core::SolveError;
+use std::ops::RangeInclusive;
 use tracing::info;
 
-#[tracing::instrument(skip(input))]
-fn range(input: &str) -> IResult<&str, RangeInclusive<u64>> {
-    map(
-        separated_pair(complete::u64, tag("-"), complete::u64),
-        |(low, high)| RangeInclusive::new(low, high),
-    )
-    .parse(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn ranges(input: &str) -> IResult<&str, Vec<RangeInclusive<u64>>> {
-    separated_list1(tag(","), range).parse(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(ranges))]
+fn process(ranges: &[RangeInclusive<u64>]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, ranges) = ranges(input)?;
-
-    let count = ranges
+    let sum = ranges
         .iter()
-        .map(|range| {
-            range
-                .clone()
-                .into_iter()
-                .filter(|n| {
-                    let s = n.to_string();
-                    let len = s.len();
+        .map(|range| invalid_id_sum(range, Repeats::AtLeast(2)))
+        .sum::<u128>();
 
-                    // Try all possible substring lengths
-                    for sub_len in 1..=len / 2 {
-                        if len % sub_len == 0 {
-                            let pattern = &s[..sub_len];
-                            let repeated = pattern.repeat(len / sub_len);
-                            if repeated == s {
-                                return true;
-                            }
-                        }
-                    }
-                    false
-                })
-                .sum::<u64>()
-        })
-        .sum::<u64>();
+    Ok(sum.to_string())
+}
+
+#[tracing::instrument(skip(ranges))]
+pub fn solve_part2(ranges: &[RangeInclusive<u64>]) -> Result<String> {
+    info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
 
-    Ok(count.to_string())
+    process(ranges).context("process part 2")
 }
 
-#[tracing::instrument(skip(input))]
 pub fn part2(input: &'static str) -> Result<String> {
-    info!("part 1");
-
-    process(input).context("process part 2")
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -75,7 +39,7 @@ mod tests {
     fn it_works() {
         let input = r##"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "4174379265");
     }
 }
@@ -0,0 +1,247 @@
+use aoc_parse::range;
+use nom::{IResult, Parser, bytes::complete::tag, multi::separated_list1};
+use std::ops::RangeInclusive;
+
+/// Which IDs count as invalid: those made of some block of digits repeated
+/// either exactly `n` times, or at least `n` times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeats {
+    Exactly(u32),
+    AtLeast(u32),
+}
+
+impl Repeats {
+    fn allows(&self, count: u32) -> bool {
+        match *self {
+            Repeats::Exactly(n) => count == n,
+            Repeats::AtLeast(n) => count >= n.max(2),
+        }
+    }
+}
+
+fn pow10(exp: u32) -> u128 {
+    10_u128.pow(exp)
+}
+
+/// The repunit-style factor that repeats a `block`-digit seed up to `len`
+/// digits, e.g. `1001001` for a block of 3 repeated to 9 digits.
+fn factor(len: u32, block: u32) -> u128 {
+    (pow10(len) - 1) / (pow10(block) - 1)
+}
+
+fn divisors(n: u32) -> impl Iterator<Item = u32> {
+    (1..=n).filter(move |&d| n.is_multiple_of(d))
+}
+
+fn mobius(mut n: u32) -> i128 {
+    let mut result = 1;
+    let mut p = 2;
+
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+
+    if n > 1 { -result } else { result }
+}
+
+fn digits(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// Period lengths `d` of `len`-digit IDs to collect: a number whose shortest
+/// repeating block has `d` digits is invalid when some allowed repeat count
+/// uses a block that is itself a multiple of `d`.
+fn periods(len: u32, repeats: Repeats) -> impl Iterator<Item = u32> {
+    let blocks = divisors(len)
+        .filter(move |&block| block < len && repeats.allows(len / block))
+        .collect::<Vec<_>>();
+
+    divisors(len).filter(move |d| blocks.iter().any(|block| block.is_multiple_of(*d)))
+}
+
+/// Range of `block`-digit seeds whose repetition to `len` digits lands
+/// inside `range`.
+fn seeds(range: &RangeInclusive<u64>, len: u32, block: u32) -> RangeInclusive<u128> {
+    let f = factor(len, block);
+    let low = (pow10(block - 1)).max((*range.start() as u128).div_ceil(f));
+    let high = (pow10(block) - 1).min(*range.end() as u128 / f);
+
+    low..=high
+}
+
+/// Whether `seed`, read as a `block`-digit number, is not itself a shorter
+/// block repeated.
+fn is_primitive(seed: u128, block: u32) -> bool {
+    divisors(block)
+        .filter(|&d| d < block)
+        .all(|d| seed != seed / pow10(block - d) * factor(block, d))
+}
+
+/// Every invalid ID in `range`, each reported once.
+///
+/// IDs are built directly rather than searched for: for each digit length
+/// and shortest period, every primitive seed block in range is multiplied by
+/// the matching repunit factor.
+pub fn invalid_ids(range: &RangeInclusive<u64>, repeats: Repeats) -> impl Iterator<Item = u64> {
+    let range = range.clone();
+    let lengths = digits(*range.start())..=digits(*range.end());
+
+    lengths.flat_map(move |len| {
+        let range = range.clone();
+
+        periods(len, repeats).flat_map(move |period| {
+            let f = factor(len, period);
+
+            seeds(&range, len, period)
+                .filter(move |&seed| is_primitive(seed, period))
+                .map(move |seed| (seed * f) as u64)
+        })
+    })
+}
+
+/// Sum of every invalid ID in `range`.
+///
+/// Seeds for a fixed length and block form a contiguous run, so each run is
+/// summed in closed form. Numbers matching several block sizes are counted
+/// once by Möbius inversion over the divisors of the period.
+pub fn invalid_id_sum(range: &RangeInclusive<u64>, repeats: Repeats) -> u128 {
+    let periodic_sum = |len: u32, block: u32| -> i128 {
+        let seeds = seeds(range, len, block);
+        if seeds.is_empty() {
+            return 0;
+        }
+
+        let (low, high) = (*seeds.start(), *seeds.end());
+        ((low + high) * (high - low + 1) / 2 * factor(len, block)) as i128
+    };
+
+    let mut sum = 0_i128;
+
+    for len in digits(*range.start())..=digits(*range.end()) {
+        for period in periods(len, repeats) {
+            sum += divisors(period)
+                .map(|e| mobius(period / e) * periodic_sum(len, e))
+                .sum::<i128>();
+        }
+    }
+
+    sum as u128
+}
+
+#[tracing::instrument(skip(input))]
+pub fn ranges(input: &str) -> IResult<&str, Vec<RangeInclusive<u64>>> {
+    separated_list1(tag(","), range()).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all;
+    use proptest::prelude::*;
+
+    fn render(ranges: &[RangeInclusive<u64>]) -> String {
+        let ranges = ranges
+            .iter()
+            .map(|range| format!("{}-{}", range.start(), range.end()))
+            .collect::<Vec<_>>();
+
+        format!("{}\n", ranges.join(","))
+    }
+
+    proptest! {
+        #[test]
+        fn ranges_round_trip(
+            expected in prop::collection::vec(
+                (1..u64::MAX / 2, 0..1_000_000_u64).prop_map(|(start, len)| start..=start + len),
+                1..30,
+            ),
+        ) {
+            prop_assert_eq!(parse_all(ranges, &render(&expected)), Ok(expected));
+        }
+    }
+
+    fn is_invalid(n: u64, repeats: Repeats) -> bool {
+        let s = n.to_string();
+        let len = s.len();
+
+        (1..len)
+            .filter(|&block| len.is_multiple_of(block))
+            .filter(|block| repeats.allows((len / block) as u32))
+            .any(|block| s[..block].repeat(len / block) == s)
+    }
+
+    #[test_log::test]
+    fn matches_brute_force() {
+        let ranges = [
+            1..=100_000,
+            998..=1012,
+            565_653..=565_659,
+            1_188_511_880..=1_188_511_890,
+        ];
+
+        for range in ranges {
+            for repeats in [
+                Repeats::Exactly(2),
+                Repeats::Exactly(3),
+                Repeats::AtLeast(2),
+            ] {
+                let expected = range
+                    .clone()
+                    .filter(|&n| is_invalid(n, repeats))
+                    .collect::<Vec<_>>();
+
+                let mut found = invalid_ids(&range, repeats).collect::<Vec<_>>();
+                found.sort_unstable();
+
+                assert_eq!(found, expected, "{range:?} {repeats:?}");
+                assert_eq!(
+                    invalid_id_sum(&range, repeats),
+                    expected.iter().map(|&n| n as u128).sum::<u128>(),
+                    "{range:?} {repeats:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let repeats = prop_oneof![
+            (1..7_u32).prop_map(Repeats::Exactly),
+            (1..7_u32).prop_map(Repeats::AtLeast),
+        ];
+        let inputs = (1..1_000_000_000_000_u64, 0..2000_u64, repeats)
+            .prop_map(|(start, len, repeats)| (start..=start + len, repeats));
+
+        aoc_differential::assert_agree(
+            inputs,
+            |(range, repeats)| {
+                range
+                    .clone()
+                    .filter(|&n| is_invalid(n, *repeats))
+                    .map(u128::from)
+                    .sum::<u128>()
+            },
+            |(range, repeats)| invalid_id_sum(range, *repeats),
+        );
+    }
+
+    #[test_log::test]
+    fn wide_ranges() {
+        let range = 1..=1_000_000_000_000;
+
+        assert_eq!(
+            invalid_ids(&range, Repeats::Exactly(2))
+                .map(|n| n as u128)
+                .sum::<u128>(),
+            invalid_id_sum(&range, Repeats::Exactly(2))
+        );
+        assert!(invalid_id_sum(&range, Repeats::AtLeast(2)) > 0);
+    }
+}
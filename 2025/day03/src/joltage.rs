@@ -0,0 +1,91 @@
+use aoc_parse::grid;
+use nom::{IResult, Parser, character::complete::one_of, combinator::map};
+
+/// Largest number that can be made by turning on exactly `k` batteries of
+/// `bank`, keeping them in their original order. `None` when the bank holds
+/// fewer than `k` batteries, or the number does not fit in a `u64`, which
+/// can only happen for `k` above 19.
+///
+/// Classic "largest subsequence of length k": walk the bank keeping a
+/// monotonically decreasing stack, popping smaller digits while there are
+/// still enough batteries left to fill the remaining slots.
+#[tracing::instrument(skip(bank))]
+pub fn max_joltage(bank: &[u64], k: usize) -> Option<u64> {
+    if bank.len() < k {
+        return None;
+    }
+
+    let mut drops = bank.len() - k;
+    let mut stack = Vec::with_capacity(bank.len());
+
+    for &digit in bank {
+        while drops > 0 && stack.last().is_some_and(|&top| top < digit) {
+            stack.pop();
+            drops -= 1;
+        }
+
+        stack.push(digit);
+    }
+
+    stack[..k]
+        .iter()
+        .try_fold(0_u64, |acc, &d| acc.checked_mul(10)?.checked_add(d))
+}
+
+#[tracing::instrument(skip(input))]
+pub fn batteries(input: &str) -> IResult<&str, Vec<Vec<u64>>> {
+    grid(map(one_of("0123456789"), |c| {
+        c.to_digit(10).unwrap() as u64
+    }))
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_parse::parse_all;
+    use proptest::prelude::*;
+
+    fn render(banks: &[Vec<u64>]) -> String {
+        banks
+            .iter()
+            .map(|bank| bank.iter().map(u64::to_string).collect::<String>() + "\n")
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn batteries_round_trip(
+            expected in prop::collection::vec(prop::collection::vec(0..10_u64, 1..100), 1..20),
+        ) {
+            prop_assert_eq!(parse_all(batteries, &render(&expected)), Ok(expected));
+        }
+    }
+
+    #[test_log::test]
+    fn largest_subsequence() {
+        let cases = [
+            ("987654321111111", 98, 987654321111),
+            ("811111111111119", 89, 811111111119),
+            ("234234234234278", 78, 434234234278),
+            ("818181911112111", 92, 888911112111),
+        ];
+
+        for (bank, two, twelve) in cases {
+            let bank = parse_all(batteries, bank).unwrap().remove(0);
+            assert_eq!(max_joltage(&bank, 2), Some(two));
+            assert_eq!(max_joltage(&bank, 12), Some(twelve));
+        }
+    }
+
+    #[test_log::test]
+    fn short_bank() {
+        assert_eq!(max_joltage(&[1, 2], 3), None);
+    }
+
+    #[test_log::test]
+    fn too_many_digits() {
+        assert_eq!(max_joltage(&[9; 20], 19), Some(9_999_999_999_999_999_999));
+        assert_eq!(max_joltage(&[9; 20], 20), None);
+    }
+}
@@ -1,5 +1,44 @@
+mod joltage;
 mod part1;
 mod part2;
 
-pub use part1::part1;
-pub use part2::part2;
+pub use joltage::{batteries, max_joltage};
+pub use part1::{part1, solve_part1};
+pub use part2::{part2, solve_part2};
+
+use anyhow::{Context, Result};
+use aoc_core::Solution;
+use aoc_parse::parse_all;
+use std::fmt::Display;
+use tracing::info;
+
+/// The banks of batteries both parts pick from.
+#[tracing::instrument(skip(input))]
+pub fn parse(input: &'static str) -> Result<Vec<Vec<u64>>> {
+    info!("parsing input");
+    let _frame = aoc_telemetry::frame!("parse");
+
+    parse_all(batteries, input).context("parse input")
+}
+
+/// The puzzle, as runners and harnesses see it.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 3;
+
+    type Input = Vec<Vec<u64>>;
+
+    fn parse(input: &'static str) -> Result<Self::Input> {
+        crate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        solve_part2(input)
+    }
+}
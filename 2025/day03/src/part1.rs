@@ -1,53 +1,33 @@
+use crate::{joltage::max_joltage, parse};
 use anyhow::{Context, Result};
-use itertools::Itertools;
-use nom::{
-    IResult, Parser,
-    bytes::complete::tag,
-    character::complete::{self, line_ending, one_of},
-    combinator::map,
-    multi::{many1, separated_list1},
-    sequence::separated_pair,
-};
+use aoc_core::SolveError;
 use tracing::info;
 
-#[tracing::instrument(skip(input))]
-fn battery(input: &str) -> IResult<&str, Vec<u64>> {
-    many1(map(one_of("0123456789"), |c| {
-        c.to_digit(10).unwrap() as u64
-    }))
-    .parse(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn batteries(input: &str) -> IResult<&str, Vec<Vec<u64>>> {
-    separated_list1(line_ending, battery).parse(input)
-}
-
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(batteries))]
+fn process(batteries: &[Vec<u64>]) -> Result<String, SolveError> {
     info!("processing input");
 
-    let (_, batteries) = batteries(input)?;
-
-    let output: u64 = batteries
+    let output = batteries
         .iter()
-        .map(|bat| {
-            bat.iter()
-                .combinations(2)
-                .map(|v| v[0] * 10 + v[1])
-                .max()
-                .unwrap_or(0)
+        .map(|bank| {
+            max_joltage(bank, 2)
+                .ok_or_else(|| SolveError::malformed("bank has fewer than 2 batteries"))
         })
-        .sum();
+        .sum::<Result<u64, SolveError>>()?;
 
     Ok(output.to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(batteries))]
+pub fn solve_part1(batteries: &[Vec<u64>]) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
+
+    process(batteries).context("process part 1")
+}
 
-    process(input).context("process part 1")
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -61,7 +41,7 @@ mod tests {
 234234234234278
 818181911112111
 "##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "357");
     }
 }
@@ -0,0 +1,91 @@
+//! Lists a year's days for its runner, so no one has to.
+//!
+//! The runner's `build.rs` calls [`days`], and its `main.rs` includes the
+//! result, which defines `DAYS`:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/days.rs"));
+//! ```
+//!
+//! Every dependency of the runner with a `path` to a `dayN` directory is a
+//! day, whose crate implements `aoc_core::Solution` for its `Puzzle`.
+//! Registering a day is then just adding that dependency, which
+//! `aoc-scaffold` does.
+
+use anyhow::{Context, Result};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Writes the `DAYS` of the runner being built to `$OUT_DIR/days.rs`.
+pub fn days() -> Result<()> {
+    let path = PathBuf::from(env::var("CARGO_MANIFEST_DIR")?).join("Cargo.toml");
+    println!("cargo::rerun-if-changed={}", path.display());
+
+    let manifest =
+        fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+    let table = table(&manifest).with_context(|| format!("parsing {}", path.display()))?;
+
+    let out = PathBuf::from(env::var("OUT_DIR")?).join("days.rs");
+    fs::write(&out, table).with_context(|| format!("writing {}", out.display()))
+}
+
+/// The `DAYS` of a runner whose manifest is `manifest`, in day order.
+pub fn table(manifest: &str) -> Result<String> {
+    let manifest = manifest.parse::<toml::Table>()?;
+    let dependencies = manifest
+        .get("dependencies")
+        .and_then(toml::Value::as_table)
+        .context("no [dependencies]")?;
+
+    let mut days = dependencies
+        .iter()
+        .filter_map(|(name, dependency)| {
+            let path = dependency.get("path")?.as_str()?;
+            let dir = Path::new(path).file_name()?.to_str()?;
+            let day = dir.strip_prefix("day")?.parse::<u8>().ok()?;
+            Some((day, name.replace('-', "_"), dir))
+        })
+        .collect::<Vec<_>>();
+    days.sort();
+
+    let mut table = String::from(
+        "/// Every day the runner depends on, listed by `aoc_build::days`.\n\
+         const DAYS: &[aoc_runner::Day] = &[\n",
+    );
+    for (_, krate, dir) in days {
+        table += &format!("    aoc_runner::Day::solution::<{krate}::Puzzle>({dir:?}),\n");
+    }
+    table += "];\n";
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_days_in_order() {
+        let manifest = r#"
+[package]
+name = "runner"
+
+[dependencies]
+aoc-runner = { workspace = true }
+day10 = { path = "../day10" }
+day9 = { path = "../day9" }
+"#;
+
+        assert_eq!(
+            table(manifest).unwrap(),
+            "/// Every day the runner depends on, listed by `aoc_build::days`.\n\
+             const DAYS: &[aoc_runner::Day] = &[\n    \
+             aoc_runner::Day::solution::<day9::Puzzle>(\"day9\"),\n    \
+             aoc_runner::Day::solution::<day10::Puzzle>(\"day10\"),\n\
+             ];\n"
+        );
+        assert!(table("[package]\nname = \"runner\"\n").is_err());
+    }
+}
@@ -0,0 +1,342 @@
+use crate::{ClientError, History, Reply, history};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use ureq::Agent;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies the tool to the site's operators, as they ask automated
+/// clients to do.
+const USER_AGENT: &str = "github.com/felipebalbi/advent-of-code";
+
+/// File in the cache holding when the last request was answered, in
+/// milliseconds since the Unix epoch.
+const LAST_REQUEST: &str = "last-request";
+
+/// Talks to the puzzle site with the user's session cookie.
+///
+/// Inputs are cached under [`cache_dir`](Self::cache_dir), so each one is
+/// downloaded at most once, and requests that do reach the site are at
+/// least [`with_min_interval`](Self::with_min_interval) apart, including
+/// across runs.
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+    agent: Agent,
+}
+
+impl Client {
+    pub fn new(session: impl Into<String>, cache_dir: impl Into<PathBuf>) -> Self {
+        let config = Agent::config_builder()
+            // Statuses are mapped to `ClientError`s below.
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build();
+
+        Self {
+            base_url: BASE_URL.into(),
+            session: session.into().trim().into(),
+            cache_dir: cache_dir.into(),
+            min_interval: Duration::from_secs(5),
+            agent: config.into(),
+        }
+    }
+
+    /// A client for the current user.
+    ///
+    /// The session comes from `AOC_SESSION`, or else `~/.config/aoc/session`.
+    /// The cache lives in `AOC_CACHE_DIR`, or else `~/.cache/aoc`, which
+    /// keeps inputs out of the repository.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let config = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home().join(".config"));
+        let session_file = config.join("aoc/session");
+
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) => session,
+            Err(_) => match fs::read_to_string(&session_file) {
+                Ok(session) => session,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    return Err(ClientError::NoSession(session_file));
+                }
+                Err(e) => return Err(e.into()),
+            },
+        };
+
+        let cache_dir = env::var_os("AOC_CACHE_DIR")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("XDG_CACHE_HOME").map(|cache| PathBuf::from(cache).join("aoc")))
+            .unwrap_or_else(|| home().join(".cache/aoc"));
+
+        Ok(Self::new(session, cache_dir))
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// The input of `year` day `day`, from the cache if it has been
+    /// downloaded before.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let cached = self.year_dir(year).join(format!("day{day:02}.txt"));
+        match fs::read_to_string(&cached) {
+            Ok(input) => return Ok(input),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let input = self.get(year, day, &format!("/{year}/day/{day}/input"))?;
+        fs::create_dir_all(cached.parent().unwrap_or(&self.cache_dir))?;
+        fs::write(&cached, &input)?;
+
+        Ok(input)
+    }
+
+    /// Sends `answer` for `part` of `year` day `day` and records the reply
+    /// in the day's [`History`].
+    ///
+    /// Nothing is sent if the history already tells how it would go, see
+    /// [`History::refuse`], or if the site asked to wait after the last
+    /// answer.
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Reply, ClientError> {
+        let answer = answer.trim();
+        if !matches!(part, 1 | 2) {
+            return Err(ClientError::Refused(format!("there is no part {part}")));
+        }
+        if answer.is_empty() || answer.contains(char::is_whitespace) {
+            return Err(ClientError::Refused(format!("{answer:?} is not an answer")));
+        }
+
+        let mut history = self.history(year, day)?;
+        if let Some(reason) = history.refuse(part, answer) {
+            return Err(ClientError::Refused(reason));
+        }
+        if let Some(wait) = history.wait(history::now()) {
+            return Err(ClientError::TooSoon(wait));
+        }
+
+        self.throttle()?;
+        let response = self
+            .agent
+            .post(format!("{}/{year}/day/{day}/answer", self.base_url))
+            .header("Cookie", format!("session={}", self.session))
+            .send_form([("level", part.to_string()), ("answer", answer.to_string())]);
+        self.stamp()?;
+        let reply = Reply::parse(&self.read(year, day, response?)?);
+        history.record(part, answer, &reply)?;
+
+        Ok(reply)
+    }
+
+    /// Every answer submitted so far for `year` day `day`.
+    pub fn history(&self, year: u16, day: u8) -> Result<History, ClientError> {
+        let path = self.year_dir(year).join(format!("day{day:02}.submissions"));
+
+        Ok(History::load(path)?)
+    }
+
+    fn year_dir(&self, year: u16) -> PathBuf {
+        self.cache_dir.join(year.to_string())
+    }
+
+    fn get(&self, year: u16, day: u8, path: &str) -> Result<String, ClientError> {
+        self.throttle()?;
+        let response = self
+            .agent
+            .get(format!("{}{path}", self.base_url))
+            .header("Cookie", format!("session={}", self.session))
+            .call();
+        self.stamp()?;
+
+        self.read(year, day, response?)
+    }
+
+    fn read(
+        &self,
+        year: u16,
+        day: u8,
+        mut response: ureq::http::Response<ureq::Body>,
+    ) -> Result<String, ClientError> {
+        match response.status().as_u16() {
+            200 => Ok(response.body_mut().read_to_string()?),
+            400 | 401 | 403 => Err(ClientError::InvalidSession),
+            404 => Err(ClientError::NotUnlocked { year, day }),
+            status => Err(ClientError::Status(status)),
+        }
+    }
+
+    /// Waits until `min_interval` has passed since the last request to the
+    /// site was answered. The time is kept in the cache so back-to-back
+    /// runs are spaced out too.
+    fn throttle(&self) -> io::Result<()> {
+        let last = fs::read_to_string(self.cache_dir.join(LAST_REQUEST))
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(Duration::from_millis);
+        if let Some(wait) = last.and_then(|last| (last + self.min_interval).checked_sub(now())) {
+            thread::sleep(wait);
+        }
+
+        Ok(())
+    }
+
+    /// Records that a request has just been answered, or has failed.
+    /// Stamping once the response is back, rather than before sending,
+    /// means the site sees requests at least `min_interval` apart however
+    /// long they take to arrive.
+    fn stamp(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.cache_dir)?;
+        fs::write(
+            self.cache_dir.join(LAST_REQUEST),
+            now().as_millis().to_string(),
+        )
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+fn home() -> PathBuf {
+    env::var_os("HOME").map(PathBuf::from).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Bound, StandIn, Verdict,
+        reply::tests::{CORRECT, TOO_HIGH},
+    };
+
+    fn cache(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-client-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn fetches_and_caches() {
+        let server = StandIn::builder("s3cr3t")
+            .input(2025, 1, "L68\nR48\n")
+            .start()
+            .unwrap();
+        let client = Client::new("s3cr3t\n", cache("caches"))
+            .with_base_url(server.url())
+            .with_min_interval(Duration::ZERO);
+
+        assert_eq!(client.input(2025, 1).unwrap(), "L68\nR48\n");
+        assert_eq!(client.input(2025, 1).unwrap(), "L68\nR48\n");
+        assert_eq!(server.requests().len(), 1);
+        assert!(client.cache_dir().join("2025/day01.txt").exists());
+    }
+
+    #[test]
+    fn reports_bad_sessions_and_locked_days() {
+        let server = StandIn::builder("s3cr3t")
+            .input(2025, 1, "")
+            .start()
+            .unwrap();
+        let client = |session| {
+            Client::new(session, cache("errors"))
+                .with_base_url(server.url())
+                .with_min_interval(Duration::ZERO)
+        };
+
+        assert!(matches!(
+            client("expired").input(2025, 1),
+            Err(ClientError::InvalidSession)
+        ));
+        assert!(matches!(
+            client("s3cr3t").input(2025, 12),
+            Err(ClientError::NotUnlocked {
+                year: 2025,
+                day: 12
+            })
+        ));
+        // Failures are not cached.
+        assert!(!client("s3cr3t").cache_dir().join("2025/day12.txt").exists());
+    }
+
+    #[test]
+    fn submits_and_remembers() {
+        let server = StandIn::builder("s3cr3t")
+            .route("POST", "/2025/day/1/answer", 200, TOO_HIGH)
+            .route("POST", "/2025/day/2/answer", 200, CORRECT)
+            .start()
+            .unwrap();
+        let client = Client::new("s3cr3t", cache("submit"))
+            .with_base_url(server.url())
+            .with_min_interval(Duration::ZERO);
+
+        let reply = client.submit(2025, 1, 1, "1200\n").unwrap();
+        assert_eq!(reply.verdict, Verdict::Wrong(Some(Bound::TooHigh)));
+        assert_eq!(server.requests()[0].body, "level=1&answer=1200");
+
+        assert!(matches!(
+            client.submit(2025, 1, 1, "1200"),
+            Err(ClientError::Refused(_))
+        ));
+        assert!(matches!(
+            client.submit(2025, 1, 1, "1300"),
+            Err(ClientError::Refused(_))
+        ));
+        assert!(matches!(
+            client.submit(2025, 1, 1, "1180"),
+            Err(ClientError::TooSoon(_))
+        ));
+
+        assert_eq!(
+            client.submit(2025, 2, 2, "6892").unwrap().verdict,
+            Verdict::Correct
+        );
+        assert!(matches!(
+            client.submit(2025, 2, 2, "6893"),
+            Err(ClientError::Refused(_))
+        ));
+        assert_eq!(client.history(2025, 2).unwrap().attempts().len(), 1);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn spaces_out_requests() {
+        let server = StandIn::builder("s3cr3t")
+            .input(2025, 1, "1")
+            .input(2025, 2, "2")
+            .start()
+            .unwrap();
+        let interval = Duration::from_millis(200);
+        let client = Client::new("s3cr3t", cache("throttle"))
+            .with_base_url(server.url())
+            .with_min_interval(interval);
+
+        client.input(2025, 1).unwrap();
+        client.input(2025, 2).unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        // The stamp only has millisecond resolution.
+        assert!(requests[1].at - requests[0].at >= interval - Duration::from_millis(1));
+    }
+}
@@ -0,0 +1,37 @@
+use std::{io, path::PathBuf, time::Duration};
+use thiserror::Error;
+
+/// Why a request to the puzzle server failed.
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// Neither `AOC_SESSION` nor the session file is set.
+    #[error("no session cookie: set AOC_SESSION or write it to {}", .0.display())]
+    NoSession(PathBuf),
+
+    /// The server did not accept the session cookie, usually because it
+    /// expired.
+    #[error("the session cookie was rejected; log in again and refresh it")]
+    InvalidSession,
+
+    /// The puzzle does not exist, or has not unlocked yet.
+    #[error("{year} day {day} is not available yet")]
+    NotUnlocked { year: u16, day: u8 },
+
+    /// The answer was not sent because the submission history already
+    /// tells how it would go.
+    #[error("not submitting: {0}")]
+    Refused(String),
+
+    /// The site asked to wait this much longer before the next answer.
+    #[error("the site asked to wait {0:?} more before another answer")]
+    TooSoon(Duration),
+
+    #[error("unexpected HTTP status {0}")]
+    Status(u16),
+
+    #[error(transparent)]
+    Http(#[from] ureq::Error),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
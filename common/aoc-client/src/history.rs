@@ -0,0 +1,197 @@
+use crate::{Bound, Reply, Verdict};
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// One answer sent to the site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    /// Seconds since the Unix epoch.
+    pub at: u64,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub wait: Option<Duration>,
+}
+
+/// Every answer submitted for one day, kept next to the cached input as
+/// tab-separated lines of `at part verdict wait answer`, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Reads the history at `path`; a day never submitted has none.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let log = match fs::read_to_string(&path) {
+            Ok(log) => log,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let attempts = log
+            .lines()
+            .map(|line| {
+                attempt(line).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{}: bad entry {line:?}", path.display()),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self { path, attempts })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Why `answer` should not be sent for `part`, if the history already
+    /// tells how it would go: the part is solved, the answer was rejected
+    /// before, or it is outside the bounds earlier hints set.
+    pub fn refuse(&self, part: u8, answer: &str) -> Option<String> {
+        let attempts = || self.attempts.iter().filter(move |a| a.part == part);
+
+        if let Some(solved) = attempts().find(|a| a.verdict == Verdict::Correct) {
+            return Some(format!("part {part} is already solved: {}", solved.answer));
+        }
+        if attempts().any(|a| a.answer == answer && matches!(a.verdict, Verdict::Wrong(_))) {
+            return Some(format!("{answer} was already rejected"));
+        }
+
+        let value = answer.parse::<i128>().ok()?;
+        let bound = |bound| {
+            attempts()
+                .filter(move |a| a.verdict == Verdict::Wrong(Some(bound)))
+                .filter_map(|a| a.answer.parse::<i128>().ok())
+        };
+        if let Some(high) = bound(Bound::TooHigh).filter(|&high| value >= high).min() {
+            return Some(format!("{answer} is not below {high}, which was too high"));
+        }
+        if let Some(low) = bound(Bound::TooLow).filter(|&low| value <= low).max() {
+            return Some(format!("{answer} is not above {low}, which was too low"));
+        }
+
+        None
+    }
+
+    /// How much longer the site asked us to wait, if it did.
+    pub fn wait(&self, now: u64) -> Option<Duration> {
+        let last = self.attempts.last()?;
+        let until = last.at + last.wait?.as_secs();
+
+        (until > now).then(|| Duration::from_secs(until - now))
+    }
+
+    /// Appends the site's `reply` to an answer to the log.
+    pub fn record(&mut self, part: u8, answer: &str, reply: &Reply) -> io::Result<&Attempt> {
+        let attempt = Attempt {
+            at: now(),
+            part,
+            answer: answer.to_string(),
+            verdict: reply.verdict,
+            wait: reply.wait,
+        };
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut log = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(
+            log,
+            "{}\t{}\t{}\t{}\t{}",
+            attempt.at,
+            attempt.part,
+            attempt.verdict,
+            attempt.wait.map_or(0, |wait| wait.as_secs()),
+            attempt.answer
+        )?;
+
+        self.attempts.push(attempt);
+        Ok(self.attempts.last().unwrap())
+    }
+}
+
+/// Seconds since the Unix epoch.
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn attempt(line: &str) -> Option<Attempt> {
+    let mut fields = line.splitn(5, '\t');
+    let at = fields.next()?.parse().ok()?;
+    let part = fields.next()?.parse().ok()?;
+    let verdict = fields.next()?.parse().ok()?;
+    let wait = match fields.next()?.parse().ok()? {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    };
+    let answer = fields.next()?.to_string();
+
+    Some(Attempt {
+        at,
+        part,
+        answer,
+        verdict,
+        wait,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_what_is_already_known() {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.tsv", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let reply = |verdict| Reply {
+            verdict,
+            wait: Some(Duration::from_secs(60)),
+            message: String::new(),
+        };
+
+        let mut history = History::load(&path).unwrap();
+        history
+            .record(1, "500", &reply(Verdict::Wrong(Some(Bound::TooHigh))))
+            .unwrap();
+        history
+            .record(1, "100", &reply(Verdict::Wrong(Some(Bound::TooLow))))
+            .unwrap();
+        history
+            .record(1, "abc", &reply(Verdict::Wrong(None)))
+            .unwrap();
+
+        let history = History::load(&path).unwrap();
+        assert_eq!(history.attempts().len(), 3);
+        assert!(history.refuse(1, "abc").is_some());
+        assert!(history.refuse(1, "500").is_some());
+        assert!(history.refuse(1, "700").is_some());
+        assert!(history.refuse(1, "100").is_some());
+        assert!(history.refuse(1, "-3").is_some());
+        assert_eq!(history.refuse(1, "250"), None);
+        assert_eq!(history.refuse(2, "700"), None);
+
+        let at = history.attempts()[2].at;
+        assert_eq!(history.wait(at + 20), Some(Duration::from_secs(40)));
+        assert_eq!(history.wait(at + 60), None);
+    }
+}
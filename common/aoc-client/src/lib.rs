@@ -0,0 +1,24 @@
+//! Downloads puzzle inputs from the Advent of Code site and submits
+//! answers to it.
+//!
+//! Requests carry the user's session cookie, inputs are cached on disk
+//! outside the repository, and the site is never hit more often than the
+//! client's rate limit allows. Submitted answers are kept in a per-day
+//! [`History`], which is used to avoid sending answers already known to be
+//! wrong. With the `stand-in` feature the crate also
+//! ships [`stand_in`], a local server answering the same routes, so all of
+//! this is tested offline.
+
+mod client;
+mod error;
+mod history;
+mod reply;
+#[cfg(any(test, feature = "stand-in"))]
+pub mod stand_in;
+
+pub use client::{BASE_URL, Client};
+pub use error::ClientError;
+pub use history::{Attempt, History};
+pub use reply::{Bound, Reply, Verdict};
+#[cfg(any(test, feature = "stand-in"))]
+pub use stand_in::StandIn;
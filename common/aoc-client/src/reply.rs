@@ -0,0 +1,208 @@
+use std::{fmt, str::FromStr, time::Duration};
+
+/// Which side of the right answer a wrong one fell on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    TooHigh,
+    TooLow,
+}
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// Wrong, with a hint when the site gives one.
+    Wrong(Option<Bound>),
+    /// Submitted too soon after the previous answer; nothing was checked.
+    RateLimited,
+    /// The part is already solved, or is not unlocked yet.
+    WrongLevel,
+    /// A page this client does not recognise.
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong(None) => "wrong",
+            Verdict::Wrong(Some(Bound::TooHigh)) => "too-high",
+            Verdict::Wrong(Some(Bound::TooLow)) => "too-low",
+            Verdict::RateLimited => "rate-limited",
+            Verdict::WrongLevel => "wrong-level",
+            Verdict::Unknown => "unknown",
+        };
+
+        f.pad(verdict)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(verdict: &str) -> Result<Self, Self::Err> {
+        Ok(match verdict {
+            "correct" => Verdict::Correct,
+            "wrong" => Verdict::Wrong(None),
+            "too-high" => Verdict::Wrong(Some(Bound::TooHigh)),
+            "too-low" => Verdict::Wrong(Some(Bound::TooLow)),
+            "rate-limited" => Verdict::RateLimited,
+            "wrong-level" => Verdict::WrongLevel,
+            "unknown" => Verdict::Unknown,
+            _ => return Err(format!("unknown verdict {verdict:?}")),
+        })
+    }
+}
+
+/// The site's answer page, boiled down.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub verdict: Verdict,
+    /// How long to wait before the next submission, if the page says.
+    pub wait: Option<Duration>,
+    /// The page's message, without markup.
+    pub message: String,
+}
+
+impl Reply {
+    /// Reads the verdict out of the page returned for a submission. The
+    /// message is the text of its `<article>`, which is all the site puts
+    /// there.
+    pub fn parse(html: &str) -> Self {
+        let article = html
+            .split_once("<article>")
+            .and_then(|(_, rest)| rest.split_once("</article>"))
+            .map_or(html, |(article, _)| article);
+        let message = text(article);
+        let lower = message.to_lowercase();
+
+        let verdict = if lower.contains("that's the right answer") {
+            Verdict::Correct
+        } else if lower.contains("that's not the right answer") {
+            Verdict::Wrong(if lower.contains("too high") {
+                Some(Bound::TooHigh)
+            } else if lower.contains("too low") {
+                Some(Bound::TooLow)
+            } else {
+                None
+            })
+        } else if lower.contains("you gave an answer too recently") {
+            Verdict::RateLimited
+        } else if lower.contains("solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        };
+
+        Self {
+            verdict,
+            wait: wait(&lower),
+            message,
+        }
+    }
+}
+
+/// The text of an HTML fragment, with tags dropped and whitespace
+/// collapsed.
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Understands "you have 1m 23s left to wait" and "please wait one minute
+/// (or 5 minutes) before trying again".
+fn wait(message: &str) -> Option<Duration> {
+    if let Some((before, _)) = message.split_once(" left to wait") {
+        let (_, left) = before.rsplit_once("you have ")?;
+        return left
+            .split_whitespace()
+            .map(|amount| {
+                let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+                let number = number.parse::<u64>().ok()?;
+                match unit {
+                    "h" => Some(number * 3600),
+                    "m" => Some(number * 60),
+                    "s" => Some(number),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs);
+    }
+
+    let (_, rest) = message.split_once("please wait ")?;
+    let (amount, _) = rest.split_once(" minute")?;
+    let minutes = match amount {
+        "one" => 1,
+        amount => amount.parse().ok()?,
+    };
+
+    Some(Duration::from_secs(minutes * 60))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const CORRECT: &str = r#"<main>
+<article><p>That's the right answer!  You are <span class="day-success">one gold star</span> closer to decorating the North Pole. <a href="/2025/day/1#part2">[Continue to Part Two]</a></p></article>
+</main>"#;
+
+    pub(crate) const TOO_HIGH: &str = r#"<main>
+<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href="/2025/about">about page</a>, or you can ask for hints on the <a href="https://www.reddit.com/r/adventofcode/" target="_blank">subreddit</a>.  Please wait one minute before trying again. <a href="/2025/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+    pub(crate) const TOO_LOW: &str = r#"<main>
+<article><p>That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again. <a href="/2025/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+    pub(crate) const WRONG: &str = r#"<main>
+<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href="/2025/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+    pub(crate) const TOO_RECENT: &str = r#"<main>
+<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. <a href="/2025/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+    pub(crate) const WRONG_LEVEL: &str = r#"<main>
+<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href="/2025/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+
+    #[test]
+    fn verdicts() {
+        let verdict = |html| Reply::parse(html).verdict;
+
+        assert_eq!(verdict(CORRECT), Verdict::Correct);
+        assert_eq!(verdict(TOO_HIGH), Verdict::Wrong(Some(Bound::TooHigh)));
+        assert_eq!(verdict(TOO_LOW), Verdict::Wrong(Some(Bound::TooLow)));
+        assert_eq!(verdict(WRONG), Verdict::Wrong(None));
+        assert_eq!(verdict(TOO_RECENT), Verdict::RateLimited);
+        assert_eq!(verdict(WRONG_LEVEL), Verdict::WrongLevel);
+        assert_eq!(verdict("<html>maintenance</html>"), Verdict::Unknown);
+
+        for v in [Verdict::Correct, Verdict::Wrong(Some(Bound::TooLow))] {
+            assert_eq!(v.to_string().parse(), Ok(v));
+        }
+    }
+
+    #[test]
+    fn waits() {
+        let wait = |html| Reply::parse(html).wait;
+
+        assert_eq!(wait(CORRECT), None);
+        assert_eq!(wait(TOO_HIGH), Some(Duration::from_secs(60)));
+        assert_eq!(wait(TOO_LOW), Some(Duration::from_secs(300)));
+        assert_eq!(wait(TOO_RECENT), Some(Duration::from_secs(83)));
+        assert!(Reply::parse(TOO_RECENT).message.starts_with("You gave"));
+    }
+}
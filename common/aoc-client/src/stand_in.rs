@@ -0,0 +1,176 @@
+//! A tiny HTTP server standing in for the puzzle site, so the client can
+//! be tested without a network or a real session.
+//!
+//! It answers the routes it is given and, like the real site, refuses any
+//! request whose `session` cookie does not match. Every request is logged
+//! so tests can assert on what was, or was not, sent.
+
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::Instant,
+};
+
+/// What the real site says to a request without a valid session.
+const LOG_IN: &str = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+
+/// A request the stand-in received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+    pub at: Instant,
+}
+
+type Routes = HashMap<(String, String), (u16, String)>;
+
+/// Routes and session for a [`StandIn`] that has not started yet.
+#[derive(Debug, Clone)]
+pub struct Builder {
+    session: String,
+    routes: Routes,
+}
+
+impl Builder {
+    /// Serves `input` as the input of `year` day `day`.
+    pub fn input(self, year: u16, day: u8, input: &str) -> Self {
+        self.route("GET", &format!("/{year}/day/{day}/input"), 200, input)
+    }
+
+    /// Answers `method path` with `status` and `body`.
+    pub fn route(mut self, method: &str, path: &str, status: u16, body: &str) -> Self {
+        self.routes
+            .insert((method.into(), path.into()), (status, body.into()));
+        self
+    }
+
+    /// Binds a free port on localhost and serves from a background thread
+    /// until the returned [`StandIn`] is dropped.
+    pub fn start(self) -> io::Result<StandIn> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = thread::spawn({
+            let log = Arc::clone(&log);
+            let stop = Arc::clone(&stop);
+            move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    // A client hanging up mid-request only fails that request.
+                    if let Ok(stream) = stream {
+                        let _ = self.serve(stream, &log);
+                    }
+                }
+            }
+        });
+
+        Ok(StandIn {
+            addr,
+            log,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    fn serve(&self, stream: TcpStream, log: &Mutex<Vec<Request>>) -> io::Result<()> {
+        let mut reader = BufReader::new(&stream);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let mut words = line.split_whitespace();
+        let method = words.next().unwrap_or_default().to_string();
+        let path = words.next().unwrap_or_default().to_string();
+
+        let (mut length, mut session) = (0, None);
+        loop {
+            line.clear();
+            reader.read_line(&mut line)?;
+            let Some((name, value)) = line.trim_end().split_once(':') else {
+                break;
+            };
+            match name.to_ascii_lowercase().as_str() {
+                "content-length" => length = value.trim().parse().unwrap_or(0),
+                "cookie" => {
+                    session = value
+                        .split(';')
+                        .find_map(|cookie| cookie.trim().strip_prefix("session="))
+                        .map(str::to_string)
+                }
+                _ => {}
+            }
+        }
+
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        log.lock().unwrap().push(Request {
+            method: method.clone(),
+            path: path.clone(),
+            body: String::from_utf8_lossy(&body).into_owned(),
+            at: Instant::now(),
+        });
+
+        let (status, body) = if session.as_deref() != Some(self.session.as_str()) {
+            (400, LOG_IN)
+        } else if let Some((status, body)) = self.routes.get(&(method, path)) {
+            (*status, body.as_str())
+        } else {
+            (404, "404 Not Found\n")
+        };
+
+        write!(
+            &stream,
+            "HTTP/1.1 {status} Stand-in\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+}
+
+/// A running stand-in server. Stops when dropped.
+#[derive(Debug)]
+pub struct StandIn {
+    addr: SocketAddr,
+    log: Arc<Mutex<Vec<Request>>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl StandIn {
+    /// A stand-in that only accepts requests carrying `session`.
+    pub fn builder(session: &str) -> Builder {
+        Builder {
+            session: session.into(),
+            routes: Routes::new(),
+        }
+    }
+
+    /// The base URL to point a [`Client`](crate::Client) at.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Every request received so far, oldest first.
+    pub fn requests(&self) -> Vec<Request> {
+        self.log.lock().unwrap().clone()
+    }
+}
+
+impl Drop for StandIn {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use aoc_parse::ParseError;
+use thiserror::Error;
+
+/// Why a solver could not produce an answer for its input.
+///
+/// Solvers return this instead of panicking so a runner going through
+/// every day can report the failing one and carry on with the rest.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SolveError {
+    /// The input parsed, but does not describe a valid puzzle: a missing
+    /// start tile, a category name the rules never mention, and so on.
+    #[error("malformed input: {0}")]
+    MalformedInput(String),
+
+    /// The input could not be parsed at all.
+    #[error("malformed input at {0}")]
+    Parse(#[from] ParseError),
+
+    /// The puzzle is well formed but has no answer, e.g. nothing is ever
+    /// reachable.
+    #[error("no solution: {0}")]
+    NoSolution(String),
+
+    /// The solver relies on a property of the real inputs, such as a single
+    /// conjunction feeding `rx`, which this input does not have.
+    #[error("unsupported topology: {0}")]
+    UnsupportedTopology(String),
+
+    /// The solver ran out of the steps [`with_step_budget`] allowed it,
+    /// most likely going round in circles.
+    ///
+    /// [`with_step_budget`]: crate::with_step_budget
+    #[error("ran out of steps")]
+    OutOfSteps,
+}
+
+impl SolveError {
+    pub fn malformed(reason: impl Into<String>) -> Self {
+        Self::MalformedInput(reason.into())
+    }
+
+    pub fn no_solution(reason: impl Into<String>) -> Self {
+        Self::NoSolution(reason.into())
+    }
+
+    pub fn unsupported(reason: impl Into<String>) -> Self {
+        Self::UnsupportedTopology(reason.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_keep_their_location() {
+        let error = SolveError::from(ParseError::at("1 2\n3 x\n", 6, "a digit"));
+
+        assert!(
+            error
+                .to_string()
+                .starts_with("malformed input at line 2, column 3: expected a digit")
+        );
+    }
+}
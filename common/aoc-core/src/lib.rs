@@ -0,0 +1,13 @@
+//! Types every day's solver shares, whatever year it belongs to.
+
+mod error;
+mod solution;
+mod steps;
+
+pub use error::SolveError;
+pub use solution::Solution;
+pub use steps::{step, with_step_budget};
+
+/// What [`Solution::generate`] draws from, seeded so inputs can be made
+/// again.
+pub use fastrand::Rng;
@@ -0,0 +1,33 @@
+use crate::Rng;
+use std::fmt::Display;
+
+/// One day's puzzle, as every runner and harness sees it.
+///
+/// Each day crate implements it for a unit struct named `Puzzle`, which is
+/// how the runners' `build.rs` finds it (see `aoc-build`). `parse` runs once
+/// and both parts solve from what it returns, so the two can be timed
+/// apart. `Input` is whatever the day's parser builds, from lines or a grid
+/// up to the puzzle's own types, and may borrow from the `'static` input.
+///
+/// Days can also [`generate`](Self::generate) random inputs, to see how
+/// their solvers cope with more than the one `input.txt`.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    type Input;
+
+    fn parse(input: &'static str) -> anyhow::Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> anyhow::Result<impl Display>;
+
+    fn part2(input: &Self::Input) -> anyhow::Result<impl Display>;
+
+    /// A random valid input whose size grows with `size`; what it counts,
+    /// rows or bricks or modules, is up to the day. `None` for days that
+    /// have no generator.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let _ = (rng, size);
+        None
+    }
+}
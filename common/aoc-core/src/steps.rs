@@ -0,0 +1,70 @@
+use crate::SolveError;
+use std::cell::Cell;
+
+thread_local! {
+    static REMAINING: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Runs `f` with `steps` calls to [`step`] allowed on this thread, after
+/// which they fail. Used when fuzzing, so that an input sending a solver
+/// round in circles ends in an error rather than a hang.
+pub fn with_step_budget<T>(steps: u64, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<u64>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            REMAINING.with(|remaining| remaining.set(self.0));
+        }
+    }
+
+    // Put back whatever budget was there before, even if `f` panics.
+    let _restore = Restore(REMAINING.with(|remaining| remaining.replace(Some(steps))));
+
+    f()
+}
+
+/// Counts one turn of a loop whose length is up to the input, like
+/// following a map until it reaches `ZZZ`. Always succeeds outside
+/// [`with_step_budget`].
+pub fn step() -> Result<(), SolveError> {
+    REMAINING.with(|remaining| match remaining.get() {
+        None => Ok(()),
+        Some(0) => Err(SolveError::OutOfSteps),
+        Some(n) => {
+            remaining.set(Some(n - 1));
+            Ok(())
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(limit: u64) -> Result<u64, SolveError> {
+        let mut steps = 0;
+        while steps < limit {
+            step()?;
+            steps += 1;
+        }
+
+        Ok(steps)
+    }
+
+    #[test]
+    fn budgets_run_out() {
+        assert_eq!(count(1_000), Ok(1_000));
+        assert_eq!(with_step_budget(10, || count(10)), Ok(10));
+        assert_eq!(
+            with_step_budget(10, || count(11)),
+            Err(SolveError::OutOfSteps)
+        );
+
+        let nested = with_step_budget(10, || {
+            let inner = with_step_budget(1, || count(2));
+            (inner, count(10))
+        });
+        assert_eq!(nested, (Err(SolveError::OutOfSteps), Ok(10)));
+        assert_eq!(count(1_000), Ok(1_000));
+    }
+}
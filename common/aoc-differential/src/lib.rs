@@ -0,0 +1,148 @@
+//! Differential testing of a day's solver against a slower one that is
+//! easier to trust.
+//!
+//! A day registers the two in a test, with a proptest strategy for inputs
+//! small enough for the reference to get through:
+//!
+//! ```ignore
+//! #[test]
+//! fn agrees_with_brute_force() {
+//!     aoc_differential::assert_agree(small_rows(), brute_force, Row::arrangements);
+//! }
+//! ```
+//!
+//! Inputs the two disagree on are shrunk, so the report shows the smallest
+//! one proptest could find.
+
+use proptest::{
+    strategy::Strategy,
+    test_runner::{Config, TestCaseError, TestError, TestRunner},
+};
+use std::{
+    any::Any,
+    fmt::{self, Debug},
+    panic::{self, AssertUnwindSafe},
+};
+
+/// An input the two solvers gave different answers for. A solver that
+/// panicked has the panic's message instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement<I, O> {
+    pub input: I,
+    pub reference: Result<O, String>,
+    pub optimised: Result<O, String>,
+}
+
+impl<I: Debug, O: Debug> fmt::Display for Disagreement<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "the solvers disagree on {:#?}", self.input)?;
+        for (name, answer) in [
+            ("reference", &self.reference),
+            ("optimised", &self.optimised),
+        ] {
+            match answer {
+                Ok(answer) => writeln!(f, "  {name}: {answer:?}")?,
+                Err(panic) => writeln!(f, "  {name}: panicked: {panic}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs `reference` and `optimised` on inputs drawn from `inputs`, as many
+/// as proptest's configuration asks for (`PROPTEST_CASES`, 256 by default),
+/// and returns the smallest input they disagree on, if any.
+pub fn compare<S, O>(
+    inputs: S,
+    reference: impl Fn(&S::Value) -> O,
+    optimised: impl Fn(&S::Value) -> O,
+) -> Result<(), Disagreement<S::Value, O>>
+where
+    S: Strategy,
+    O: PartialEq + Debug,
+{
+    let mut runner = TestRunner::new(Config::default());
+    let result = runner.run(&inputs, |input| {
+        if answer(&reference, &input) == answer(&optimised, &input) {
+            Ok(())
+        } else {
+            Err(TestCaseError::fail("the solvers disagree"))
+        }
+    });
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(TestError::Fail(_, input)) => Err(Disagreement {
+            reference: answer(&reference, &input),
+            optimised: answer(&optimised, &input),
+            input,
+        }),
+        // Only strategies that filter out too many inputs get here.
+        Err(TestError::Abort(reason)) => panic!("comparing the solvers gave up: {reason}"),
+    }
+}
+
+/// [`compare`], panicking with the disagreement if there is one. This is
+/// what days call from their tests.
+#[track_caller]
+pub fn assert_agree<S, O>(
+    inputs: S,
+    reference: impl Fn(&S::Value) -> O,
+    optimised: impl Fn(&S::Value) -> O,
+) where
+    S: Strategy,
+    O: PartialEq + Debug,
+{
+    if let Err(disagreement) = compare(inputs, reference, optimised) {
+        panic!("{disagreement}");
+    }
+}
+
+fn answer<I, O>(solver: impl Fn(&I) -> O, input: &I) -> Result<O, String> {
+    panic::catch_unwind(AssertUnwindSafe(|| solver(input))).map_err(message)
+}
+
+fn message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => panic
+            .downcast_ref::<&str>()
+            .map_or_else(|| "panicked".to_string(), |message| message.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agreeing_solvers() {
+        assert_eq!(compare(0..1000_u32, |n| n * 2, |n| n + n), Ok(()));
+    }
+
+    #[test]
+    fn reports_the_smallest_disagreement() {
+        let disagreement = compare(0..1000_u32, |&n| n.min(100), |&n| n).unwrap_err();
+        assert_eq!(disagreement.input, 101);
+        assert_eq!(disagreement.reference, Ok(100));
+        assert_eq!(disagreement.optimised, Ok(101));
+
+        let disagreement = compare(
+            0..1000_u32,
+            |&n| n,
+            |&n| {
+                assert!(n < 5, "too big");
+                n
+            },
+        )
+        .unwrap_err();
+        assert_eq!(disagreement.input, 5);
+        assert_eq!(disagreement.optimised, Err("too big".to_string()));
+        assert!(
+            disagreement
+                .to_string()
+                .contains("optimised: panicked: too big")
+        );
+    }
+}
@@ -0,0 +1,466 @@
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid direction {0:?}")]
+pub struct ParseDirectionError(pub String);
+
+/// One of the four orthogonal headings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction4 {
+    /// Clockwise, starting from north.
+    pub const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    pub const UP: Self = Self::North;
+    pub const RIGHT: Self = Self::East;
+    pub const DOWN: Self = Self::South;
+    pub const LEFT: Self = Self::West;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        Self::ALL[index % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 3)
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    /// Heading after bouncing off a `/` mirror.
+    pub fn reflect_slash(self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::North,
+            Self::South => Self::West,
+            Self::West => Self::South,
+        }
+    }
+
+    /// Heading after bouncing off a `\` mirror.
+    pub fn reflect_backslash(self) -> Self {
+        match self {
+            Self::North => Self::West,
+            Self::West => Self::North,
+            Self::South => Self::East,
+            Self::East => Self::South,
+        }
+    }
+
+    /// Heading after hitting `mirror`, one of `/`, `\`, `|` or `-`. Flat
+    /// mirrors send a perpendicular beam straight back and leave a parallel
+    /// one alone.
+    pub fn reflect(self, mirror: char) -> Option<Self> {
+        Direction8::from(self)
+            .reflect(mirror)
+            .map(|d| Self::try_from(d).expect("reflection keeps orthogonal headings"))
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Self::North | Self::South)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        !self.is_vertical()
+    }
+
+    /// Unit step as `(dx, dy)`.
+    pub fn delta(self) -> (isize, isize) {
+        Direction8::from(self).delta()
+    }
+
+    /// Unit step as `(row, column)`, the order `grid[row][column]` and
+    /// `pathfinding::matrix` use, so north is `(-1, 0)`.
+    pub fn row_col(self) -> (isize, isize) {
+        Direction8::from(self).row_col()
+    }
+
+    /// Unit step in any vector type built from `(i32, i32)`, such as
+    /// `glam::IVec2`.
+    pub fn offset<T: From<(i32, i32)>>(self) -> T {
+        Direction8::from(self).offset()
+    }
+
+    /// The heading of a unit step, if it is one.
+    pub fn from_delta(delta: (isize, isize)) -> Option<Self> {
+        Direction8::from_delta(delta).and_then(|d| Self::try_from(d).ok())
+    }
+
+    /// Like [`Direction4::from_delta`], for a step given as `(row, column)`.
+    pub fn from_row_col((row, column): (isize, isize)) -> Option<Self> {
+        Self::from_delta((column, row))
+    }
+
+    /// Like [`Direction4::from_delta`], for anything convertible into
+    /// `(i32, i32)`, such as `glam::IVec2`.
+    pub fn from_offset(offset: impl Into<(i32, i32)>) -> Option<Self> {
+        let (x, y) = offset.into();
+        Self::from_delta((x as isize, y as isize))
+    }
+}
+
+impl TryFrom<char> for Direction4 {
+    type Error = ParseDirectionError;
+
+    /// Accepts `U/D/L/R`, `N/S/E/W`, `^v<>` and arrows.
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | 'u' | 'N' | 'n' | '^' | '↑' => Ok(Self::North),
+            'R' | 'r' | 'E' | 'e' | '>' | '→' => Ok(Self::East),
+            'D' | 'd' | 'S' | 's' | 'v' | 'V' | '↓' => Ok(Self::South),
+            'L' | 'l' | 'W' | 'w' | '<' | '←' => Ok(Self::West),
+            _ => Err(ParseDirectionError(c.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction4 {
+    type Err = ParseDirectionError;
+
+    /// Accepts a single character understood by [`Direction4::try_from`], or
+    /// a word such as `up` or `North`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::try_from(c),
+            _ => match s.to_ascii_lowercase().as_str() {
+                "up" | "north" => Ok(Self::North),
+                "right" | "east" => Ok(Self::East),
+                "down" | "south" => Ok(Self::South),
+                "left" | "west" => Ok(Self::West),
+                _ => Err(ParseDirectionError(s.to_string())),
+            },
+        }
+    }
+}
+
+impl TryFrom<Direction8> for Direction4 {
+    type Error = Direction8;
+
+    fn try_from(d: Direction8) -> Result<Self, Self::Error> {
+        match d {
+            Direction8::North => Ok(Self::North),
+            Direction8::East => Ok(Self::East),
+            Direction8::South => Ok(Self::South),
+            Direction8::West => Ok(Self::West),
+            diagonal => Err(diagonal),
+        }
+    }
+}
+
+/// The step as `(dx, dy)`, like [`Direction4::delta`]. Grids indexed by
+/// `(row, column)` want [`Direction4::row_col`] instead.
+impl From<Direction4> for (isize, isize) {
+    fn from(d: Direction4) -> Self {
+        d.delta()
+    }
+}
+
+/// From a `(dx, dy)` step, like [`Direction4::from_delta`].
+impl TryFrom<(isize, isize)> for Direction4 {
+    type Error = ParseDirectionError;
+
+    fn try_from(delta: (isize, isize)) -> Result<Self, Self::Error> {
+        Self::from_delta(delta).ok_or_else(|| ParseDirectionError(format!("{delta:?}")))
+    }
+}
+
+/// One of the eight compass headings, diagonals included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Clockwise, starting from north.
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    const DELTAS: [(isize, isize); 8] = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        Self::ALL[index % 8]
+    }
+
+    /// Quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    /// Quarter turn anticlockwise.
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 6)
+    }
+
+    /// Eighth of a turn clockwise.
+    pub fn turn_half_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    /// Eighth of a turn anticlockwise.
+    pub fn turn_half_left(self) -> Self {
+        Self::from_index(self.index() + 7)
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::from_index(self.index() + 4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// Heading after bouncing off a `/` mirror.
+    pub fn reflect_slash(self) -> Self {
+        let (dx, dy) = self.delta();
+        Self::from_delta((-dy, -dx)).expect("reflection keeps unit steps")
+    }
+
+    /// Heading after bouncing off a `\` mirror.
+    pub fn reflect_backslash(self) -> Self {
+        let (dx, dy) = self.delta();
+        Self::from_delta((dy, dx)).expect("reflection keeps unit steps")
+    }
+
+    /// Heading after hitting `mirror`, one of `/`, `\`, `|` or `-`.
+    pub fn reflect(self, mirror: char) -> Option<Self> {
+        let (dx, dy) = self.delta();
+
+        match mirror {
+            '/' => Some(self.reflect_slash()),
+            '\\' => Some(self.reflect_backslash()),
+            '|' => Self::from_delta((-dx, dy)),
+            '-' => Self::from_delta((dx, -dy)),
+            _ => None,
+        }
+    }
+
+    /// Unit step as `(dx, dy)`.
+    pub fn delta(self) -> (isize, isize) {
+        Self::DELTAS[self.index()]
+    }
+
+    /// Unit step as `(row, column)`, see [`Direction4::row_col`].
+    pub fn row_col(self) -> (isize, isize) {
+        let (dx, dy) = self.delta();
+        (dy, dx)
+    }
+
+    /// Unit step in any vector type built from `(i32, i32)`, such as
+    /// `glam::IVec2`.
+    pub fn offset<T: From<(i32, i32)>>(self) -> T {
+        let (dx, dy) = self.delta();
+        T::from((dx as i32, dy as i32))
+    }
+
+    /// The heading of a unit step, if it is one.
+    pub fn from_delta(delta: (isize, isize)) -> Option<Self> {
+        Self::DELTAS
+            .iter()
+            .position(|&d| d == delta)
+            .map(Self::from_index)
+    }
+
+    /// Like [`Direction8::from_delta`], for a step given as `(row, column)`.
+    pub fn from_row_col((row, column): (isize, isize)) -> Option<Self> {
+        Self::from_delta((column, row))
+    }
+
+    /// Like [`Direction8::from_delta`], for anything convertible into
+    /// `(i32, i32)`, such as `glam::IVec2`.
+    pub fn from_offset(offset: impl Into<(i32, i32)>) -> Option<Self> {
+        let (x, y) = offset.into();
+        Self::from_delta((x as isize, y as isize))
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(d: Direction4) -> Self {
+        match d {
+            Direction4::North => Self::North,
+            Direction4::East => Self::East,
+            Direction4::South => Self::South,
+            Direction4::West => Self::West,
+        }
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    /// Accepts anything [`Direction4`] does, plus `NE/SE/SW/NW` and the
+    /// diagonal arrows.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(d) = s.parse::<Direction4>() {
+            return Ok(d.into());
+        }
+
+        match s.to_ascii_uppercase().as_str() {
+            "NE" | "↗" => Ok(Self::NorthEast),
+            "SE" | "↘" => Ok(Self::SouthEast),
+            "SW" | "↙" => Ok(Self::SouthWest),
+            "NW" | "↖" => Ok(Self::NorthWest),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+/// The step as `(dx, dy)`, like [`Direction8::delta`].
+impl From<Direction8> for (isize, isize) {
+    fn from(d: Direction8) -> Self {
+        d.delta()
+    }
+}
+
+impl TryFrom<(isize, isize)> for Direction8 {
+    type Error = ParseDirectionError;
+
+    fn try_from(delta: (isize, isize)) -> Result<Self, Self::Error> {
+        Self::from_delta(delta).ok_or_else(|| ParseDirectionError(format!("{delta:?}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::IVec2;
+
+    #[test]
+    fn turns() {
+        for d in Direction4::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.reverse());
+            assert_eq!(Direction8::from(d).turn_right(), d.turn_right().into());
+        }
+
+        assert_eq!(Direction4::North.turn_right(), Direction4::East);
+        assert_eq!(Direction8::North.turn_half_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthWest.reverse(), Direction8::NorthEast);
+    }
+
+    #[test]
+    fn mirrors() {
+        use Direction4::*;
+
+        assert_eq!(East.reflect_slash(), North);
+        assert_eq!(East.reflect_backslash(), South);
+        assert_eq!(North.reflect('/'), Some(East));
+        assert_eq!(North.reflect('\\'), Some(West));
+        assert_eq!(East.reflect('|'), Some(West));
+        assert_eq!(East.reflect('-'), Some(East));
+        assert_eq!(East.reflect('.'), None);
+        assert_eq!(
+            Direction8::NorthEast.reflect('/'),
+            Some(Direction8::NorthEast)
+        );
+        assert_eq!(
+            Direction8::NorthEast.reflect('\\'),
+            Some(Direction8::SouthWest)
+        );
+        assert_eq!(
+            Direction8::NorthEast.reflect('|'),
+            Some(Direction8::NorthWest)
+        );
+
+        for d in Direction8::ALL {
+            assert_eq!(d.reflect_slash().reflect_slash(), d);
+            assert_eq!(d.reflect_backslash().reflect_backslash(), d);
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Direction4::North.offset::<IVec2>(), IVec2::new(0, -1));
+        assert_eq!(
+            Direction4::from_offset(IVec2::new(-1, 0)),
+            Some(Direction4::West)
+        );
+        assert_eq!(Direction4::from_offset(IVec2::new(1, 1)), None);
+        assert_eq!(
+            Direction8::from_offset(IVec2::new(1, 1)),
+            Some(Direction8::SouthEast)
+        );
+        assert_eq!(<(isize, isize)>::from(Direction4::South), (0, 1));
+        assert_eq!(Direction8::try_from((-1, -1)), Ok(Direction8::NorthWest));
+        assert!(Direction4::try_from((2, 0)).is_err());
+    }
+
+    #[test]
+    fn row_column_order() {
+        assert_eq!(Direction4::North.row_col(), (-1, 0));
+        assert_eq!(Direction4::East.row_col(), (0, 1));
+        assert_eq!(Direction8::SouthWest.row_col(), (1, -1));
+        assert_eq!(Direction4::from_row_col((0, -1)), Some(Direction4::West));
+        assert_eq!(Direction4::from_row_col((1, 1)), None);
+
+        for d in Direction8::ALL {
+            assert_eq!(Direction8::from_row_col(d.row_col()), Some(d));
+        }
+    }
+
+    #[test]
+    fn parsing() {
+        for (input, expected) in [
+            ("U", Direction4::North),
+            ("N", Direction4::North),
+            ("^", Direction4::North),
+            ("→", Direction4::East),
+            ("v", Direction4::South),
+            ("L", Direction4::West),
+            ("west", Direction4::West),
+        ] {
+            assert_eq!(input.parse::<Direction4>(), Ok(expected));
+        }
+
+        assert_eq!("NE".parse::<Direction8>(), Ok(Direction8::NorthEast));
+        assert_eq!("↙".parse::<Direction8>(), Ok(Direction8::SouthWest));
+        assert_eq!("D".parse::<Direction8>(), Ok(Direction8::South));
+        assert!("X".parse::<Direction4>().is_err());
+        assert!("NE".parse::<Direction4>().is_err());
+    }
+}
@@ -0,0 +1,11 @@
+//! Grid helpers shared by every year's puzzles.
+//!
+//! Coordinates follow the puzzle inputs: `x` grows to the right and `y`
+//! grows downwards, so [`Direction4::North`] is `(0, -1)`. Steps are
+//! `(dx, dy)` unless a method says otherwise; grids indexed by
+//! `(row, column)`, such as `pathfinding::matrix::Matrix`, take
+//! [`Direction4::row_col`], where north is `(-1, 0)`.
+
+mod direction;
+
+pub use direction::{Direction4, Direction8, ParseDirectionError};
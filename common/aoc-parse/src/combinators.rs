@@ -0,0 +1,137 @@
+use crate::Error;
+use nom::{
+    Parser,
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, one_of, space0, space1},
+    combinator::{map, map_res, opt, recognize},
+    multi::{many1, separated_list1},
+    sequence::{pair, preceded, separated_pair},
+};
+use std::{ops::RangeInclusive, str::FromStr};
+
+/// An integer with an optional leading `+` or `-`.
+pub fn integer<'a, T: FromStr>() -> impl Parser<&'a str, Output = T, Error = Error<'a>> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)
+}
+
+/// An integer made only of digits.
+pub fn unsigned<'a, T: FromStr>() -> impl Parser<&'a str, Output = T, Error = Error<'a>> {
+    map_res(digit1, str::parse)
+}
+
+/// Integers separated by spaces or tabs, e.g. `79 14  55 13`.
+pub fn numbers<'a, T: FromStr>() -> impl Parser<&'a str, Output = Vec<T>, Error = Error<'a>> {
+    separated_list1(space1, integer())
+}
+
+/// An inclusive range written `a-b`. Both ends are unsigned since the dash
+/// is the separator.
+pub fn range<'a, T: FromStr>() -> impl Parser<&'a str, Output = RangeInclusive<T>, Error = Error<'a>>
+{
+    map(
+        separated_pair(unsigned(), tag("-"), unsigned()),
+        |(low, high)| low..=high,
+    )
+}
+
+/// One record per line.
+pub fn lines<'a, P>(record: P) -> impl Parser<&'a str, Output = Vec<P::Output>, Error = Error<'a>>
+where
+    P: Parser<&'a str, Error = Error<'a>>,
+{
+    separated_list1(line_ending, record)
+}
+
+/// Blocks separated by a blank line, such as the maps of an almanac.
+pub fn blocks<'a, P>(block: P) -> impl Parser<&'a str, Output = Vec<P::Output>, Error = Error<'a>>
+where
+    P: Parser<&'a str, Error = Error<'a>>,
+{
+    separated_list1(pair(line_ending, line_ending), block)
+}
+
+/// A `key: value` header such as `Time:      7  15   30`, producing the
+/// value.
+pub fn header<'a, P>(
+    key: &'a str,
+    value: P,
+) -> impl Parser<&'a str, Output = P::Output, Error = Error<'a>>
+where
+    P: Parser<&'a str, Error = Error<'a>>,
+{
+    preceded((tag(key), char(':'), space0), value)
+}
+
+/// Rows of cells, one row per line.
+pub fn grid<'a, P>(cell: P) -> impl Parser<&'a str, Output = Vec<Vec<P::Output>>, Error = Error<'a>>
+where
+    P: Parser<&'a str, Error = Error<'a>>,
+{
+    lines(many1(cell))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_all;
+    use nom::character::complete::alpha1;
+
+    #[test]
+    fn integers() {
+        assert_eq!(parse_all(numbers::<i64>(), "3 -4\t+5"), Ok(vec![3, -4, 5]));
+        assert_eq!(
+            parse_all(numbers::<u32>(), "79 14  55 13\n"),
+            Ok(vec![79, 14, 55, 13])
+        );
+        assert!(parse_all(numbers::<u32>(), "1 -2").is_err());
+        assert!(parse_all(unsigned::<u8>(), "300").is_err());
+    }
+
+    #[test]
+    fn ranges() {
+        let ranges = separated_list1(char(','), range::<u64>());
+
+        assert_eq!(
+            parse_all(ranges, "11-22,95-115\n"),
+            Ok(vec![11..=22, 95..=115])
+        );
+    }
+
+    #[test]
+    fn records_and_blocks() {
+        let input = "a 1 2\nb 3\n\nc 4\n";
+        let record = separated_pair(alpha1, space1, numbers::<u32>());
+
+        assert_eq!(
+            parse_all(blocks(lines(record)), input),
+            Ok(vec![
+                vec![("a", vec![1, 2]), ("b", vec![3])],
+                vec![("c", vec![4])]
+            ])
+        );
+    }
+
+    #[test]
+    fn headers() {
+        let sheet = separated_pair(
+            header("Time", numbers::<u32>()),
+            line_ending,
+            header("Distance", numbers::<u32>()),
+        );
+
+        assert_eq!(
+            parse_all(sheet, "Time:      7  15   30\nDistance:  9  40  200\n"),
+            Ok((vec![7, 15, 30], vec![9, 40, 200]))
+        );
+    }
+
+    #[test]
+    fn grids() {
+        let cell = map(one_of(".#"), |c| c == '#');
+
+        assert_eq!(
+            parse_all(grid(cell), ".#\n#.\n"),
+            Ok(vec![vec![false, true], vec![true, false]])
+        );
+    }
+}
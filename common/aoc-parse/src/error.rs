@@ -0,0 +1,259 @@
+use crate::Error;
+use nom::{
+    Parser, character::complete::multispace0, combinator::all_consuming, sequence::terminated,
+};
+use std::fmt::Write;
+use thiserror::Error;
+
+/// A parse failure pointing back into the input it came from, rendered the
+/// way rustc renders its own diagnostics:
+///
+/// ```text
+/// line 2, column 10: expected a digit
+///   |
+/// 2 | Card 2: x3 32 20 16 61 | 61 30 68 82 17 32 24 19
+///   |         ^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("line {line}, column {column}: expected {expected}\n{}", self.excerpt())]
+pub struct ParseError {
+    /// Byte offset into the input where parsing gave up.
+    pub offset: usize,
+    /// 1-based line number of `offset`.
+    pub line: usize,
+    /// 1-based column of `offset`, counted in characters.
+    pub column: usize,
+    /// The whole line `offset` falls on, without its line ending.
+    pub source_line: String,
+    /// What the failing parser was looking for.
+    pub expected: &'static str,
+}
+
+impl ParseError {
+    /// Locates `offset` inside `input`.
+    pub fn at(input: &str, offset: usize, expected: &'static str) -> Self {
+        let offset = offset.min(input.len());
+        let before = &input[..offset];
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Self {
+            offset,
+            line: before.matches('\n').count() + 1,
+            column: before[start..].chars().count() + 1,
+            source_line: input[start..]
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            expected,
+        }
+    }
+
+    fn excerpt(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        // Keep tabs so the caret lines up however the terminal renders them.
+        let indent = self
+            .source_line
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+
+        let mut out = String::new();
+        let _ = writeln!(out, "{gutter} |");
+        let _ = writeln!(out, "{number} | {}", self.source_line);
+        let _ = write!(out, "{gutter} | {indent}^");
+        out
+    }
+}
+
+/// Turns the name `nom` gives an [`ErrorKind`](nom::error::ErrorKind) into
+/// the construct a reader would recognise. Going through the name rather
+/// than the enum lets errors from older `nom` majors share the table.
+fn expected(kind: &str) -> &'static str {
+    match kind {
+        "Tag" => "a literal",
+        "Char" => "a specific character",
+        "OneOf" => "one of the allowed characters",
+        "NoneOf" => "a character outside the excluded set",
+        "Digit" => "a digit",
+        "Hexadecimal Digit" => "a hexadecimal digit",
+        "Alphabetic" => "a letter",
+        "AlphaNumeric" => "a letter or digit",
+        "Space" => "a space",
+        "Multiple spaces" => "whitespace",
+        "CrLf" => "a line ending",
+        "End of file" => "end of input",
+        "Float" => "a number",
+        "Map on Result" | "Map on Option" => "a value in range",
+        "predicate verification" => "a valid value",
+        "Separated list"
+        | "Separated non empty list"
+        | "Many1"
+        | "Many(m, n)"
+        | "Count"
+        | "Count occurrence of >=1 patterns" => "more items",
+        "Alternative" => "one of the alternatives",
+        "TakeWhile1" | "TakeWhileMN" | "TakeTill1" => "at least one matching character",
+        "Take until" => "a terminator",
+        _ => "valid input",
+    }
+}
+
+/// Converts a failed `nom` result into a [`ParseError`] located in `input`,
+/// so `process` can keep writing
+///
+/// ```ignore
+/// let (_, cards) = scratchcards(input).diagnose(input)?;
+/// ```
+///
+/// and report where the input went wrong instead of leaking the parser's
+/// error type.
+pub trait Diagnose<T> {
+    fn diagnose(self, input: &str) -> Result<T, ParseError>;
+}
+
+impl<T> Diagnose<T> for Result<T, nom::Err<Error<'_>>> {
+    fn diagnose(self, input: &str) -> Result<T, ParseError> {
+        self.map_err(|error| match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => ParseError::at(
+                input,
+                input.len() - e.input.len(),
+                expected(e.code.description()),
+            ),
+            nom::Err::Incomplete(_) => ParseError::at(input, input.len(), "more input"),
+        })
+    }
+}
+
+#[cfg(feature = "nom7")]
+impl<T> Diagnose<T> for Result<T, nom7::Err<nom7::error::Error<&str>>> {
+    fn diagnose(self, input: &str) -> Result<T, ParseError> {
+        self.map_err(|error| match error {
+            nom7::Err::Error(e) | nom7::Err::Failure(e) => ParseError::at(
+                input,
+                input.len() - e.input.len(),
+                expected(e.code.description()),
+            ),
+            nom7::Err::Incomplete(_) => ParseError::at(input, input.len(), "more input"),
+        })
+    }
+}
+
+#[cfg(feature = "nom_locate")]
+impl<T> Diagnose<T> for Result<T, nom7::Err<nom7::error::Error<nom_locate::LocatedSpan<&str>>>> {
+    fn diagnose(self, input: &str) -> Result<T, ParseError> {
+        self.map_err(|error| match error {
+            nom7::Err::Error(e) | nom7::Err::Failure(e) => ParseError::at(
+                input,
+                e.input.location_offset(),
+                expected(e.code.description()),
+            ),
+            nom7::Err::Incomplete(_) => ParseError::at(input, input.len(), "more input"),
+        })
+    }
+}
+
+/// Runs `parser` over the whole of `input`. Trailing whitespace is allowed,
+/// anything else left over is an error, unlike `let (_, x) = parser(input)?`
+/// which silently drops it.
+pub fn parse_all<'a, P>(parser: P, input: &'a str) -> Result<P::Output, ParseError>
+where
+    P: Parser<&'a str, Error = Error<'a>>,
+{
+    all_consuming(terminated(parser, multispace0))
+        .parse(input)
+        .map(|(_, output)| output)
+        .diagnose(input)
+}
+
+/// [`parse_all`] for parsers still written against `nom` 7.
+#[cfg(feature = "nom7")]
+pub fn parse_all_nom7<'a, O, P>(parser: P, input: &'a str) -> Result<O, ParseError>
+where
+    P: nom7::Parser<&'a str, O, nom7::error::Error<&'a str>>,
+{
+    use nom7::{character::complete::multispace0, combinator::all_consuming, sequence::terminated};
+
+    all_consuming(terminated(parser, multispace0))(input)
+        .map(|(_, output)| output)
+        .diagnose(input)
+}
+
+/// [`parse_all_nom7`] for parsers that track positions with `nom_locate`.
+#[cfg(feature = "nom_locate")]
+pub fn parse_all_located<'a, O, P>(parser: P, input: &'a str) -> Result<O, ParseError>
+where
+    P: nom7::Parser<
+            nom_locate::LocatedSpan<&'a str>,
+            O,
+            nom7::error::Error<nom_locate::LocatedSpan<&'a str>>,
+        >,
+{
+    use nom7::{character::complete::multispace0, combinator::all_consuming, sequence::terminated};
+
+    all_consuming(terminated(parser, multispace0))(nom_locate::LocatedSpan::new(input))
+        .map(|(_, output)| output)
+        .diagnose(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{header, numbers};
+
+    #[test]
+    fn trailing_input_is_an_error() {
+        assert_eq!(parse_all(numbers::<u32>(), "1 2 3\n\n"), Ok(vec![1, 2, 3]));
+
+        let error = parse_all(numbers::<u32>(), "1 2 x").unwrap_err();
+        assert_eq!((error.offset, error.line, error.column), (4, 1, 5));
+        assert_eq!(error.expected, "end of input");
+    }
+
+    #[test]
+    fn points_at_the_offending_line() {
+        let input = "Time: 7 15 30\nDistance: 9 x 200\n";
+        let error = header("Time", numbers::<u32>())
+            .parse(input)
+            .and_then(|(rest, _)| header("Distance", numbers::<u32>()).parse(&rest[1..]))
+            .and_then(|(rest, d)| all_consuming(multispace0).parse(rest).map(|_| d))
+            .diagnose(input)
+            .unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 13));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 13: expected end of input\n  \
+             |\n\
+             2 | Distance: 9 x 200\n  \
+             |             ^"
+        );
+    }
+
+    #[cfg(feature = "nom7")]
+    #[test]
+    fn nom7_parsers_must_consume_everything() {
+        let digits = nom7::character::complete::digit1::<_, nom7::error::Error<&str>>;
+
+        assert_eq!(parse_all_nom7(digits, "123\n\n"), Ok("123"));
+
+        let error = parse_all_nom7(digits, "123 4").unwrap_err();
+        assert_eq!((error.column, error.expected), (5, "end of input"));
+    }
+
+    #[cfg(feature = "nom_locate")]
+    #[test]
+    fn located_parsers_must_consume_everything() {
+        let digits = |input| {
+            nom7::character::complete::digit1::<_, nom7::error::Error<_>>(input)
+                .map(|(rest, span): (_, nom_locate::LocatedSpan<&str>)| (rest, *span.fragment()))
+        };
+
+        assert_eq!(parse_all_located(digits, "123\n"), Ok("123"));
+
+        let error = parse_all_located(digits, "12\n3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
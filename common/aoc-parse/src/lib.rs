@@ -0,0 +1,27 @@
+//! Parsers for the shapes puzzle inputs keep coming in.
+//!
+//! Every combinator is a plain `nom` 8 parser, so they compose with the rest
+//! of `nom`; [`nom`] is re-exported so crates still on an older major can
+//! build on these without pulling in a second copy. [`parse_all`] runs a
+//! parser over a whole input and rejects anything left over.
+//!
+//! Failures are reported as a [`ParseError`] carrying the line, column and
+//! an excerpt of the input. Parsers that are not run through [`parse_all`]
+//! get the same report from [`Diagnose::diagnose`], which also accepts
+//! `nom` 7 errors, over `&str` or `nom_locate` spans, behind the `nom7` and
+//! `nom_locate` features. Those features bring `parse_all_nom7` and
+//! `parse_all_located` along, the same whole-input check for older parsers.
+
+mod combinators;
+mod error;
+
+pub use combinators::{blocks, grid, header, integer, lines, numbers, range, unsigned};
+#[cfg(feature = "nom_locate")]
+pub use error::parse_all_located;
+#[cfg(feature = "nom7")]
+pub use error::parse_all_nom7;
+pub use error::{Diagnose, ParseError, parse_all};
+pub use nom;
+
+/// The error type every combinator in this crate reports.
+pub type Error<'a> = nom::error::Error<&'a str>;
@@ -0,0 +1,78 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+/// Known-correct answers of a year, read from its `answers.toml`:
+///
+/// ```toml
+/// [day01]
+/// part1 = "1180"
+/// part2 = "6892"
+/// ```
+///
+/// Tables are keyed by the day's directory. Answers are strings because
+/// that is what the parts return, so `"-39"` and `""` compare as printed.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+pub struct Answers(BTreeMap<String, DayAnswers>);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Answers {
+    pub fn parse(registry: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(registry)
+    }
+
+    /// Reads the registry at `path`. A year without one simply has no
+    /// known answers yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(registry) => {
+                Self::parse(&registry).with_context(|| format!("parsing {}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    /// The recorded answer for `part` of the day living in `dir`.
+    pub fn get(&self, dir: &str, part: u8) -> Option<&str> {
+        let day = self.0.get(dir)?;
+        let answer = match part {
+            1 => &day.part1,
+            _ => &day.part2,
+        };
+
+        answer.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        let answers = Answers::parse(
+            r#"
+[day01]
+part1 = "1180"
+
+[day09]
+part1 = "1725987467"
+part2 = "-39"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(answers.get("day01", 1), Some("1180"));
+        assert_eq!(answers.get("day01", 2), None);
+        assert_eq!(answers.get("day09", 2), Some("-39"));
+        assert_eq!(answers.get("day02", 1), None);
+        assert!(Answers::parse("[day01]\npart3 = \"1\"").is_err());
+    }
+}
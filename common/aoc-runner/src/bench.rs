@@ -0,0 +1,269 @@
+use crate::Day;
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, env, fmt, fs, io, path::Path, process::Command};
+
+/// The median time of each benchmark of a day, in nanoseconds, keyed by
+/// the benchmark's path in divan's tree, like `part1_benchmark`.
+pub type Medians = BTreeMap<String, f64>;
+
+/// The medians of a year's benchmarks, read from its `bench-baseline.json`:
+///
+/// ```json
+/// {
+///   "day01": {
+///     "part1_benchmark": 312000.0,
+///     "part2_benchmark": 312300.0
+///   }
+/// }
+/// ```
+///
+/// Days are keyed by their directory, like [`Answers`](crate::Answers).
+/// Timings only compare on the same machine, so the file is not committed.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Baseline(BTreeMap<String, Medians>);
+
+impl Baseline {
+    pub fn parse(baseline: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(baseline)
+    }
+
+    /// Reads the baseline at `path`. A year without the file has none.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(baseline) => {
+                Self::parse(&baseline).with_context(|| format!("parsing {}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    /// Writes the baseline to `path`, replacing what was there.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut baseline = serde_json::to_string_pretty(self)?;
+        baseline.push('\n');
+
+        fs::write(path, baseline).with_context(|| format!("writing {}", path.display()))
+    }
+
+    pub fn get(&self, dir: &str) -> Option<&Medians> {
+        self.0.get(dir)
+    }
+
+    /// Replaces the medians of a day. Benchmarks the day no longer has are
+    /// dropped with the rest.
+    pub fn set(&mut self, dir: &str, medians: Medians) {
+        self.0.insert(dir.to_string(), medians);
+    }
+}
+
+/// Runs the divan benchmarks of `day` with `cargo bench` and returns their
+/// medians, none if the day has no benchmarks.
+pub fn bench(root: &Path, day: &Day) -> Result<Medians> {
+    // The runner is usually started by `cargo run`, which says which cargo
+    // it is.
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .current_dir(root)
+        .args(["bench", "--quiet", "--package", day.dir, "--benches"])
+        .args(["--", "--color", "never"])
+        .output()
+        .with_context(|| format!("running the benchmarks of {}", day.dir))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "benchmarking {} failed ({}):\n{}",
+            day.dir,
+            output.status,
+            stderr.trim_end()
+        );
+    }
+
+    parse(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Reads the medians out of divan's table:
+///
+/// ```text
+/// day01_bench         fastest       │ slowest       │ median        │ ...
+/// ├─ part1_benchmark  215 µs        │ 580.5 µs      │ 312 µs        │ ...
+/// ╰─ group                          │               │               │
+///    ╰─ 10            1.2 ms        │ 1.5 ms        │ 1.3 ms        │ ...
+/// ```
+///
+/// Benchmarks inside groups are named by their path, like `group/10`.
+pub fn parse(output: &str) -> Result<Medians> {
+    let mut medians = Medians::new();
+    let mut path = Vec::<String>::new();
+
+    for line in output.lines() {
+        let Some(branch) = line.find("├─ ").or_else(|| line.find("╰─ ")) else {
+            continue;
+        };
+        // Every level of the tree is indented by three columns.
+        let depth = line[..branch].chars().count() / 3;
+        let row = &line[branch + "├─ ".len()..];
+
+        let mut columns = row.split('│');
+        let first = columns.next().unwrap_or_default();
+        let Some(name) = first.split_whitespace().next() else {
+            continue;
+        };
+        path.truncate(depth);
+        path.push(name.to_string());
+
+        let Some(median) = columns.nth(1).map(str::trim).filter(|m| !m.is_empty()) else {
+            // A group, whose benchmarks follow.
+            continue;
+        };
+        let nanos = nanos(median).with_context(|| format!("reading the median of {line:?}"))?;
+        medians.insert(path.join("/"), nanos);
+    }
+
+    Ok(medians)
+}
+
+/// A divan time, like `312.3 µs`, in nanoseconds.
+fn nanos(time: &str) -> Option<f64> {
+    let (value, unit) = time.split_once(' ')?;
+    let scale = match unit {
+        "ps" => 1e-3,
+        "ns" => 1.0,
+        "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+
+    Some(value.parse::<f64>().ok()? * scale)
+}
+
+/// How a median moved since the baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    /// The benchmark is not in the baseline.
+    New,
+    /// Within the noise threshold.
+    Same,
+    /// Took this many times less time.
+    Faster(f64),
+    /// Took this many times more time.
+    Slower(f64),
+}
+
+impl Change {
+    /// Compares `median` with `baseline`, ignoring changes smaller than
+    /// `threshold`, a fraction of the baseline.
+    pub fn new(baseline: Option<f64>, median: f64, threshold: f64) -> Self {
+        let Some(baseline) = baseline else {
+            return Change::New;
+        };
+
+        if median < baseline * (1.0 - threshold) {
+            Change::Faster(baseline / median)
+        } else if median > baseline * (1.0 + threshold) {
+            Change::Slower(median / baseline)
+        } else {
+            Change::Same
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let change = match self {
+            Change::New => "new".to_string(),
+            Change::Same => "same".to_string(),
+            Change::Faster(ratio) => format!("{ratio:.2}x faster"),
+            Change::Slower(ratio) => format!("{ratio:.2}x SLOWER"),
+        };
+
+        f.pad(&change)
+    }
+}
+
+/// A time in nanoseconds, in the unit divan would print it in.
+struct Time(f64);
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (value, unit) = match self.0 {
+            t if t >= 1e9 => (t / 1e9, "s"),
+            t if t >= 1e6 => (t / 1e6, "ms"),
+            t if t >= 1e3 => (t / 1e3, "µs"),
+            t => (t, "ns"),
+        };
+
+        f.pad(&format!("{value:.1} {unit}"))
+    }
+}
+
+/// Prints a row of the table, header first.
+pub(crate) fn print_row(row: Option<(u8, &str, Option<f64>, f64, Change)>) {
+    let Some((day, name, baseline, median, change)) = row else {
+        println!(
+            "{:<4} {:<24} {:>12} {:>12}  change",
+            "day", "benchmark", "baseline", "median"
+        );
+        return;
+    };
+
+    let baseline = baseline.map_or("-".to_string(), |b| Time(b).to_string());
+    println!(
+        "{day:<4} {name:<24} {baseline:>12} {:>12}  {change}",
+        Time(median)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_divan_tables() {
+        let output = "
+running 0 tests
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+day05_bench         fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ part1_benchmark  215 µs        │ 580.5 µs      │ 312 µs        │ 311.3 µs      │ 100     │ 100
+├─ part2_benchmark  1.1 s         │ 1.3 s         │ 1.25 s        │ 1.2 s         │ 100     │ 100
+╰─ scaling                        │               │               │               │         │
+   ├─ 10            750 ns        │ 1 µs          │ 800 ns        │ 810 ns        │ 100     │ 800
+   ╰─ 100           7.5 µs        │ 9 µs          │ 8 µs          │ 8.1 µs        │ 100     │ 100
+";
+
+        let medians = parse(output).unwrap();
+        assert_eq!(
+            medians,
+            Medians::from([
+                ("part1_benchmark".into(), 312_000.0),
+                ("part2_benchmark".into(), 1.25e9),
+                ("scaling/10".into(), 800.0),
+                ("scaling/100".into(), 8_000.0),
+            ])
+        );
+        assert_eq!(Time(312_000.0).to_string(), "312.0 µs");
+        assert!(parse("├─ part1_benchmark  1 µs │ 2 µs │ 2 fortnights │").is_err());
+    }
+
+    #[test]
+    fn compares_with_the_baseline() {
+        assert_eq!(Change::new(None, 100.0, 0.05), Change::New);
+        assert_eq!(Change::new(Some(100.0), 104.0, 0.05), Change::Same);
+        assert_eq!(Change::new(Some(100.0), 50.0, 0.05), Change::Faster(2.0));
+        assert_eq!(Change::new(Some(100.0), 125.0, 0.05), Change::Slower(1.25));
+        assert_eq!(Change::Slower(1.25).to_string(), "1.25x SLOWER");
+
+        let mut baseline = Baseline::default();
+        baseline.set(
+            "day05",
+            Medians::from([("part1_benchmark".into(), 312_000.0)]),
+        );
+        let saved = serde_json::to_string_pretty(&baseline).unwrap();
+        assert_eq!(Baseline::parse(&saved).unwrap(), baseline);
+    }
+}
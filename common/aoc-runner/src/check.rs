@@ -0,0 +1,127 @@
+use crate::{Answers, Day, Outcome, run_part};
+use std::{fmt, path::Path};
+
+/// How an answer compares with the registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// The registry has no answer for this part yet.
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `pad` so the table's width specifiers apply.
+        let status = match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+        };
+
+        f.pad(status)
+    }
+}
+
+/// One row of the regression table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub outcome: Outcome,
+    pub expected: Option<String>,
+}
+
+impl Check {
+    pub fn status(&self) -> Status {
+        match (&self.expected, &self.outcome.result) {
+            (None, _) => Status::Missing,
+            (Some(expected), Ok(actual)) if expected == actual => Status::Pass,
+            _ => Status::Fail,
+        }
+    }
+}
+
+/// Runs both parts of every day in `days` and compares them with `answers`.
+pub fn check<'a, 'd: 'a>(
+    root: &'a Path,
+    days: impl IntoIterator<Item = &'d Day> + 'a,
+    answers: &'a Answers,
+) -> impl Iterator<Item = Check> + 'a {
+    days.into_iter().flat_map(move |day| {
+        [1, 2].map(|part| Check {
+            outcome: run_part(root, day, part),
+            expected: answers.get(day.dir, part).map(str::to_string),
+        })
+    })
+}
+
+/// Prints a row of the table, header first.
+pub(crate) fn print_row(check: Option<&Check>) {
+    let Some(check) = check else {
+        println!(
+            "{:<4} {:<4} {:<8} {:<20} actual",
+            "day", "part", "status", "expected"
+        );
+        return;
+    };
+
+    let actual = match &check.outcome.result {
+        Ok(answer) => answer.clone(),
+        Err(error) => format!("error: {error}"),
+    };
+
+    println!(
+        "{:<4} {:<4} {:<8} {:<20} {actual}",
+        check.outcome.day,
+        check.outcome.part,
+        check.status(),
+        check.expected.as_deref().unwrap_or("-"),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, time::Duration};
+
+    fn outcome(result: Result<&str, &str>) -> Outcome {
+        Outcome {
+            day: 1,
+            part: 1,
+            result: result.map(str::to_string).map_err(str::to_string),
+            elapsed: Duration::ZERO,
+            timings: None,
+        }
+    }
+
+    #[test]
+    fn status() {
+        let row = |expected: Option<&str>, result| Check {
+            outcome: outcome(result),
+            expected: expected.map(str::to_string),
+        };
+
+        assert_eq!(row(Some("42"), Ok("42")).status(), Status::Pass);
+        assert_eq!(row(Some("42"), Ok("41")).status(), Status::Fail);
+        assert_eq!(row(Some("42"), Err("no start tile")).status(), Status::Fail);
+        assert_eq!(row(None, Ok("0")).status(), Status::Missing);
+    }
+
+    #[test]
+    fn checks_every_part() {
+        fn length(input: &'static str) -> anyhow::Result<String> {
+            Ok(input.trim().len().to_string())
+        }
+
+        let root = std::env::temp_dir().join("aoc-runner-check");
+        fs::create_dir_all(root.join("day01")).unwrap();
+        fs::write(root.join("day01/input.txt"), "abc\n").unwrap();
+        let answers = Answers::parse("[day01]\npart1 = \"3\"\n").unwrap();
+        let days = [Day::new(1, "day01", length, length)];
+
+        let statuses = check(&root, &days, &answers)
+            .map(|check| check.status())
+            .collect::<Vec<_>>();
+
+        assert_eq!(statuses, [Status::Pass, Status::Missing]);
+    }
+}
@@ -0,0 +1,124 @@
+use aoc_core::{Rng, Solution};
+use std::time::{Duration, Instant};
+
+/// A part's entry point, as exported by every day crate.
+pub type Part = fn(&'static str) -> anyhow::Result<String>;
+
+/// Parses an input and solves one part of it, timing each phase.
+pub type Phases = fn(&'static str, u8) -> (anyhow::Result<String>, Timings);
+
+/// Makes a random input of about the size asked for, see
+/// [`Solution::generate`].
+pub type Generate = fn(&mut Rng, usize) -> Option<String>;
+
+/// One day of a year, as the runner sees it.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    /// Directory of the day's crate, relative to the year's workspace.
+    pub dir: &'static str,
+    /// Input file of each part, relative to `dir`.
+    pub inputs: [&'static str; 2],
+    pub part1: Part,
+    pub part2: Part,
+    /// Used instead of `part1` and `part2` when the day has them.
+    pub phases: Option<Phases>,
+    pub generate: Generate,
+}
+
+impl Day {
+    /// A day whose parts both read `input.txt`.
+    pub const fn new(day: u8, dir: &'static str, part1: Part, part2: Part) -> Self {
+        Self {
+            day,
+            dir,
+            inputs: ["input.txt", "input.txt"],
+            part1,
+            part2,
+            phases: None,
+            generate: no_generator,
+        }
+    }
+
+    /// For days that keep a separate input file per part.
+    pub const fn with_inputs(self, part1: &'static str, part2: &'static str) -> Self {
+        Self {
+            inputs: [part1, part2],
+            ..self
+        }
+    }
+
+    /// For days that parse once and solve each part from that, so the
+    /// runner can time the two apart.
+    pub const fn with_phases(self, phases: Phases) -> Self {
+        Self {
+            phases: Some(phases),
+            ..self
+        }
+    }
+
+    /// The day implemented by `S`, whose crate lives in `dir`. This is what
+    /// the runners' generated tables are made of.
+    pub const fn solution<S: Solution>(dir: &'static str) -> Self {
+        Self {
+            generate: S::generate,
+            ..Self::new(S::DAY, dir, whole::<S, 1>, whole::<S, 2>).with_phases(phased::<S>)
+        }
+    }
+}
+
+fn no_generator(_: &mut Rng, _: usize) -> Option<String> {
+    None
+}
+
+fn whole<S: Solution, const PART: u8>(input: &'static str) -> anyhow::Result<String> {
+    let input = S::parse(input)?;
+    match PART {
+        1 => S::part1(&input).map(|answer| answer.to_string()),
+        _ => S::part2(&input).map(|answer| answer.to_string()),
+    }
+}
+
+fn phased<S: Solution>(input: &'static str, part: u8) -> (anyhow::Result<String>, Timings) {
+    timed(
+        input,
+        part,
+        S::parse,
+        |input| S::part1(input).map(|answer| answer.to_string()),
+        |input| S::part2(input).map(|answer| answer.to_string()),
+    )
+}
+
+/// How long a day split into [`Phases`] spent parsing, and solving.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+/// Parses `input`, then solves `part` of it.
+pub fn timed<T>(
+    input: &'static str,
+    part: u8,
+    parse: impl FnOnce(&'static str) -> anyhow::Result<T>,
+    part1: impl FnOnce(&T) -> anyhow::Result<String>,
+    part2: impl FnOnce(&T) -> anyhow::Result<String>,
+) -> (anyhow::Result<String>, Timings) {
+    let mut timings = Timings::default();
+
+    let start = Instant::now();
+    let parsed = parse(input);
+    timings.parse = start.elapsed();
+
+    let result = parsed.and_then(|parsed| {
+        let start = Instant::now();
+        let answer = match part {
+            1 => part1(&parsed),
+            _ => part2(&parsed),
+        };
+        timings.solve = start.elapsed();
+        answer
+    });
+
+    (result, timings)
+}
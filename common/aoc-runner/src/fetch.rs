@@ -0,0 +1,74 @@
+use crate::Day;
+use anyhow::{Context, Result};
+use aoc_client::Client;
+use std::{fs, path::Path};
+
+/// Downloads the input of `day` into each of its input files that is
+/// missing or blank, like the template's, and returns the files written.
+pub fn fetch<'d>(client: &Client, year: u16, root: &Path, day: &'d Day) -> Result<Vec<&'d str>> {
+    let dir = root.join(day.dir);
+    let mut missing = day
+        .inputs
+        .iter()
+        .copied()
+        .filter(|input| {
+            fs::read_to_string(dir.join(input)).map_or(true, |input| input.trim().is_empty())
+        })
+        .collect::<Vec<_>>();
+    missing.dedup();
+
+    if !missing.is_empty() {
+        let input = client
+            .input(year, day.day)
+            .with_context(|| format!("fetching {year} day {}", day.day))?;
+        for file in &missing {
+            let path = dir.join(file);
+            fs::write(&path, &input).with_context(|| format!("writing {}", path.display()))?;
+        }
+    }
+
+    Ok(missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_client::StandIn;
+    use std::time::Duration;
+
+    fn part(_: &'static str) -> anyhow::Result<String> {
+        Ok(String::new())
+    }
+
+    #[test]
+    fn writes_missing_inputs_only() {
+        let server = StandIn::builder("s3cr3t")
+            .input(2023, 1, "1abc2\n")
+            .input(2023, 2, "Game 1\n")
+            .start()
+            .unwrap();
+        let root = std::env::temp_dir().join(format!("aoc-runner-fetch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("day1")).unwrap();
+        fs::write(root.join("day1/input1.txt"), "\n").unwrap();
+        fs::create_dir_all(root.join("day2")).unwrap();
+        fs::write(root.join("day2/input.txt"), "mine\n").unwrap();
+        let client = Client::new("s3cr3t", root.join("cache"))
+            .with_base_url(server.url())
+            .with_min_interval(Duration::ZERO);
+
+        let split = Day::new(1, "day1", part, part).with_inputs("input1.txt", "input2.txt");
+        assert_eq!(
+            fetch(&client, 2023, &root, &split).unwrap(),
+            ["input1.txt", "input2.txt"]
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("day1/input2.txt")).unwrap(),
+            "1abc2\n"
+        );
+
+        let present = Day::new(2, "day2", part, part);
+        assert!(fetch(&client, 2023, &root, &present).unwrap().is_empty());
+        assert_eq!(server.requests().len(), 1);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{Day, run::catch};
+use anyhow::{Context, Result};
+use aoc_core::with_step_budget;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Calls to [`aoc_core::step`] each part may make on one fuzzed input, far
+/// more than any real input needs.
+pub const STEP_BUDGET: u64 = 1_000_000;
+
+/// What every fuzz target does with its input: solves both parts of `day`,
+/// each within [`STEP_BUDGET`]. Errors are what malformed input should get;
+/// a panic is left to reach libFuzzer, which saves the input as a crash.
+pub fn fuzz(day: &Day, data: &[u8]) {
+    // Puzzle inputs are text, anything else is not worth a part's time.
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    // Parts borrow their input for `'static`. Leaking every input caps a
+    // campaign at a few million runs, so bound it with `-runs` or
+    // `-max_total_time`.
+    let input: &'static str = input.to_string().leak();
+
+    for part in [day.part1, day.part2] {
+        let _ = with_step_budget(STEP_BUDGET, || part(input));
+    }
+}
+
+/// A saved input that still makes a part panic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crash {
+    pub path: PathBuf,
+    pub part: u8,
+    pub panic: String,
+}
+
+/// Runs every input saved under `dir/<day's dir>/` through its day the way
+/// [`fuzz`] does, and returns those that still panic. These are the crashes
+/// fuzzing found, kept as regression tests.
+pub fn replay(dir: &Path, days: &[Day]) -> Result<Vec<Crash>> {
+    let mut crashes = Vec::new();
+
+    for day in days {
+        let day_dir = dir.join(day.dir);
+        let entries = match fs::read_dir(&day_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e).with_context(|| format!("reading {}", day_dir.display())),
+        };
+
+        let mut paths = entries
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()
+            .with_context(|| format!("reading {}", day_dir.display()))?;
+        paths.sort();
+
+        for path in paths {
+            let data = fs::read(&path).with_context(|| format!("reading {}", path.display()))?;
+            let Ok(input) = String::from_utf8(data) else {
+                continue;
+            };
+            let input: &'static str = input.leak();
+
+            for (part, solver) in [(1, day.part1), (2, day.part2)] {
+                if let Err(panic) = catch(|| with_step_budget(STEP_BUDGET, || solver(input))) {
+                    crashes.push(Crash {
+                        path: path.clone(),
+                        part,
+                        panic,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(crashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn looping(input: &'static str) -> anyhow::Result<String> {
+        loop {
+            aoc_core::step()?;
+            if input.is_empty() {
+                return Ok(String::new());
+            }
+        }
+    }
+
+    fn picky(input: &'static str) -> anyhow::Result<String> {
+        assert!(!input.starts_with('!'), "bang");
+        Ok(input.to_string())
+    }
+
+    #[test]
+    fn replays_saved_crashes() {
+        let dir = std::env::temp_dir().join(format!("aoc-runner-fuzz-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("day01")).unwrap();
+        fs::write(dir.join("day01/fine"), "abc").unwrap();
+        fs::write(dir.join("day01/crash"), "!abc").unwrap();
+        fs::write(dir.join("day01/binary"), [0xff, 0xfe]).unwrap();
+
+        let days = [
+            Day::new(1, "day01", looping, picky),
+            Day::new(2, "day02", picky, picky),
+        ];
+        fuzz(&days[0], b"abc");
+        let crashes = replay(&dir, &days).unwrap();
+        assert_eq!(crashes.len(), 1);
+        assert_eq!(crashes[0].path, dir.join("day01/crash"));
+        assert_eq!(crashes[0].part, 2);
+        assert!(crashes[0].panic.contains("bang"));
+    }
+}
@@ -0,0 +1,294 @@
+#[cfg(feature = "dhat-heap")]
+use crate::Day;
+use crate::{Outcome, Status};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+/// What one part allocated while it ran.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Heap {
+    pub total_bytes: u64,
+    /// The most bytes live at once.
+    pub peak_bytes: u64,
+    pub allocations: u64,
+}
+
+/// Limits for one part. A limit left out is not checked.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Budget {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<u64>,
+}
+
+impl Budget {
+    /// A budget `heap` fits in with a tenth to spare, so small changes in
+    /// allocation patterns do not fail the check.
+    pub fn around(heap: &Heap) -> Self {
+        let spare = |n: u64| Some(n + n.div_ceil(10));
+
+        Self {
+            total_bytes: spare(heap.total_bytes),
+            peak_bytes: spare(heap.peak_bytes),
+            allocations: spare(heap.allocations),
+        }
+    }
+
+    /// The limits `heap` goes over, described for the report.
+    pub fn exceeded(&self, heap: &Heap) -> Vec<String> {
+        [
+            ("total bytes", self.total_bytes, heap.total_bytes),
+            ("peak bytes", self.peak_bytes, heap.peak_bytes),
+            ("allocations", self.allocations, heap.allocations),
+        ]
+        .into_iter()
+        .filter_map(|(name, limit, used)| {
+            let limit = limit?;
+            (used > limit).then(|| format!("{name} {used} > {limit}"))
+        })
+        .collect()
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct DayBudgets {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<Budget>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<Budget>,
+}
+
+/// Heap budgets of a year, read from its `heap-budget.toml`:
+///
+/// ```toml
+/// [day01.part1]
+/// total_bytes = 120000
+/// peak_bytes = 40000
+/// allocations = 900
+/// ```
+///
+/// Tables are keyed by the day's directory, like [`Answers`](crate::Answers).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Budgets(BTreeMap<String, DayBudgets>);
+
+impl Budgets {
+    pub fn parse(budgets: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(budgets)
+    }
+
+    /// Reads the budgets at `path`. A year without the file has none.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(budgets) => {
+                Self::parse(&budgets).with_context(|| format!("parsing {}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    /// Writes the budgets to `path`, replacing what was there.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut budgets = String::from(
+            "# Heap budgets, checked with `cargo run --release -p runner --features dhat-heap -- heap`.\n\
+             # Regenerate with `heap --save` after an intended change.\n\n",
+        );
+        budgets += &toml::to_string(self)?;
+
+        fs::write(path, budgets).with_context(|| format!("writing {}", path.display()))
+    }
+
+    pub fn get(&self, dir: &str, part: u8) -> Option<&Budget> {
+        let day = self.0.get(dir)?;
+        match part {
+            1 => day.part1.as_ref(),
+            _ => day.part2.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, dir: &str, part: u8, budget: Budget) {
+        let day = self.0.entry(dir.to_string()).or_default();
+        match part {
+            1 => day.part1 = Some(budget),
+            _ => day.part2 = Some(budget),
+        }
+    }
+}
+
+/// One row of the heap report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub outcome: Outcome,
+    /// `None` if the part did not get to run.
+    pub heap: Option<Heap>,
+    pub budget: Option<Budget>,
+}
+
+impl Profile {
+    pub fn status(&self) -> Status {
+        match (&self.outcome.result, &self.heap, &self.budget) {
+            (Err(_), _, _) | (_, None, _) => Status::Fail,
+            (_, _, None) => Status::Missing,
+            (Ok(_), Some(heap), Some(budget)) if budget.exceeded(heap).is_empty() => Status::Pass,
+            _ => Status::Fail,
+        }
+    }
+}
+
+/// Runs one part of `day` under dhat and reports what it allocated. Only
+/// the solver is measured, not reading its input.
+#[cfg(feature = "dhat-heap")]
+pub fn profile_part(root: &Path, day: &Day, part: u8) -> (Outcome, Option<Heap>) {
+    let start = std::time::Instant::now();
+    let (solver, input) = crate::run::input(root, day, part);
+    let (result, heap) = match input {
+        Ok(input) => {
+            // Testing mode keeps the stats in memory instead of writing
+            // `dhat-heap.json`, and lets a new profiler start for each part.
+            let profiler = dhat::Profiler::builder().testing().build();
+            let result = crate::run::solve(|| solver(input));
+            let stats = dhat::HeapStats::get();
+            drop(profiler);
+
+            let heap = Heap {
+                total_bytes: stats.total_bytes,
+                peak_bytes: stats.max_bytes as u64,
+                allocations: stats.total_blocks,
+            };
+            (result, Some(heap))
+        }
+        Err(e) => (Err(e), None),
+    };
+
+    let outcome = Outcome {
+        day: day.day,
+        part,
+        result,
+        elapsed: start.elapsed(),
+        timings: None,
+    };
+    (outcome, heap)
+}
+
+/// Profiles both parts of every day in `days` against `budgets`.
+#[cfg(feature = "dhat-heap")]
+pub fn profile<'a, 'd: 'a>(
+    root: &'a Path,
+    days: impl IntoIterator<Item = &'d Day> + 'a,
+    budgets: &'a Budgets,
+) -> impl Iterator<Item = Profile> + 'a {
+    days.into_iter().flat_map(move |day| {
+        [1, 2].map(|part| {
+            let (outcome, heap) = profile_part(root, day, part);
+            Profile {
+                outcome,
+                heap,
+                budget: budgets.get(day.dir, part).copied(),
+            }
+        })
+    })
+}
+
+/// Prints a row of the table, header first.
+#[cfg(feature = "dhat-heap")]
+pub(crate) fn print_row(profile: Option<&Profile>) {
+    let Some(profile) = profile else {
+        println!(
+            "{:<4} {:<4} {:<8} {:>14} {:>14} {:>12}  notes",
+            "day", "part", "status", "total bytes", "peak bytes", "allocations"
+        );
+        return;
+    };
+
+    let notes = match (&profile.outcome.result, &profile.heap, &profile.budget) {
+        (Err(error), _, _) => format!("error: {error}"),
+        (_, Some(heap), Some(budget)) => budget.exceeded(heap).join(", "),
+        (_, Some(_), None) => "no budget".into(),
+        _ => String::new(),
+    };
+    let columns = match &profile.heap {
+        Some(heap) => format!(
+            "{:>14} {:>14} {:>12}",
+            heap.total_bytes, heap.peak_bytes, heap.allocations
+        ),
+        None => format!("{:>14} {:>14} {:>12}", "-", "-", "-"),
+    };
+
+    println!(
+        "{:<4} {:<4} {:<8} {columns}  {notes}",
+        profile.outcome.day,
+        profile.outcome.part,
+        profile.status(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn budgets() {
+        let budgets = Budgets::parse(
+            r#"
+[day01.part1]
+total_bytes = 1000
+peak_bytes = 100
+"#,
+        )
+        .unwrap();
+        let budget = budgets.get("day01", 1).unwrap();
+        assert_eq!(budgets.get("day01", 2), None);
+
+        let heap = |peak_bytes| Heap {
+            total_bytes: 900,
+            peak_bytes,
+            allocations: 1_000_000,
+        };
+        assert!(budget.exceeded(&heap(100)).is_empty());
+        assert_eq!(budget.exceeded(&heap(101)), ["peak bytes 101 > 100"]);
+
+        let mut saved = Budgets::default();
+        saved.set("day01", 2, Budget::around(&heap(101)));
+        let saved = Budgets::parse(&toml::to_string(&saved).unwrap()).unwrap();
+        assert_eq!(saved.get("day01", 2).unwrap().peak_bytes, Some(112));
+
+        let profile = Profile {
+            outcome: Outcome {
+                day: 1,
+                part: 1,
+                result: Ok("42".into()),
+                elapsed: Duration::ZERO,
+                timings: None,
+            },
+            heap: Some(heap(101)),
+            budget: Some(*budget),
+        };
+        assert_eq!(profile.status(), Status::Fail);
+    }
+
+    #[cfg(feature = "dhat-heap")]
+    #[test]
+    fn measures_each_part() {
+        fn allocates(input: &'static str) -> anyhow::Result<String> {
+            let bytes = vec![0u8; 4096];
+            Ok(format!("{}{}", input.trim(), bytes.len()))
+        }
+
+        let root = std::env::temp_dir().join("aoc-runner-heap");
+        fs::create_dir_all(root.join("day01")).unwrap();
+        fs::write(root.join("day01/input.txt"), "abc\n").unwrap();
+
+        let (outcome, heap) = profile_part(&root, &Day::new(1, "day01", allocates, allocates), 1);
+        let heap = heap.unwrap();
+        assert_eq!(outcome.result, Ok("abc4096".to_string()));
+        assert!(heap.peak_bytes >= 4096 && heap.total_bytes >= heap.peak_bytes);
+        assert!(heap.allocations >= 2);
+    }
+}
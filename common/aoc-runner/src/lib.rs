@@ -0,0 +1,456 @@
+//! Runs every day of a year and reports how each part went.
+//!
+//! Each year has a small `runner` binary that hands its days to [`main`].
+//! Its `build.rs` lists them, with `aoc-build`, from the day crates it
+//! depends on: each is a [`Day::solution`], which times parsing and solving
+//! apart. A day that fails, or panics, is reported and the rest still run.
+//! `fetch` downloads the inputs of days that have none yet, and `submit`
+//! sends an answer to the site.
+//!
+//! `bench` runs the days' divan benchmarks and compares their medians with
+//! the year's `bench-baseline.json`.
+//!
+//! `gen` prints a random input for days that can make one, see
+//! [`Solution::generate`](aoc_core::Solution::generate).
+//!
+//! Built with the `dhat-heap` feature, `heap` also measures what each part
+//! allocates and compares it with the year's `heap-budget.toml`.
+//!
+//! Each year's `fuzz` crate has a libFuzzer target per day that hands its
+//! input to [`fuzz`]. Inputs that crashed a day are saved under
+//! `fuzz/regressions/<day>/`, and [`replay`] checks they no longer do.
+//!
+//! Logging is set up from the `--log`, `--log-format` and `--chrome-trace`
+//! flags, see [`aoc_telemetry::Telemetry`].
+
+mod answers;
+mod bench;
+mod check;
+mod day;
+mod fetch;
+mod fuzz;
+mod heap;
+mod run;
+
+use aoc_client::{Client, Verdict};
+use aoc_core::Rng;
+use aoc_telemetry::{Telemetry, init_telemetry};
+use clap::{Parser, Subcommand};
+use std::{path::Path, process::ExitCode};
+
+pub use answers::Answers;
+pub use bench::{Baseline, Change, Medians, bench};
+pub use check::{Check, Status, check};
+pub use day::{Day, Generate, Part, Phases, Timings, timed};
+pub use fetch::fetch;
+pub use fuzz::{Crash, STEP_BUDGET, fuzz, replay};
+pub use heap::{Budget, Budgets, Heap, Profile};
+#[cfg(feature = "dhat-heap")]
+pub use heap::{profile, profile_part};
+pub use run::{Outcome, run, run_part};
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[derive(Debug, Parser)]
+#[command(about = "Run every Advent of Code day of a year")]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Only run these days.
+    days: Vec<u8>,
+
+    #[command(flatten)]
+    telemetry: Telemetry,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Compare every answer with the year's `answers.toml`.
+    Check {
+        /// Only check these days.
+        days: Vec<u8>,
+    },
+
+    /// Run the divan benchmarks of every day and compare their medians with
+    /// the year's `bench-baseline.json`.
+    Bench {
+        /// Only benchmark these days.
+        days: Vec<u8>,
+
+        /// Record the medians as the new baseline of the days benchmarked.
+        #[arg(long)]
+        save: bool,
+
+        /// Changes smaller than this, in percent, are reported as noise.
+        #[arg(long, default_value_t = 5.0)]
+        threshold: f64,
+    },
+
+    /// Measure what every part allocates and compare it with the year's
+    /// `heap-budget.toml`. Needs the `dhat-heap` feature.
+    Heap {
+        /// Only profile these days.
+        days: Vec<u8>,
+
+        /// Record the measurements, with some room to spare, as the new
+        /// budgets of the days profiled.
+        #[arg(long)]
+        save: bool,
+    },
+
+    /// Download the inputs of these days, skipping files already filled in.
+    Fetch {
+        #[arg(required = true)]
+        days: Vec<u8>,
+    },
+
+    /// Print a random input for a day, to try its solvers on more than
+    /// `input.txt`.
+    Gen {
+        day: u8,
+
+        /// How big an input to make, in whatever the day's generator counts.
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+
+        /// Make the same input again, from the seed an earlier run printed.
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+
+    /// Submit an answer, solving the part first if none is given.
+    Submit {
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: Option<String>,
+    },
+}
+
+/// Entry point for a year's runner. `root` is the year's workspace
+/// directory, which the days' input paths and `answers.toml` are relative
+/// to.
+pub fn main(year: u16, root: &Path, days: &[Day]) -> ExitCode {
+    let cli = Cli::parse();
+    let _telemetry = match init_telemetry(&cli.telemetry) {
+        Ok(guard) => guard,
+        Err(e) => {
+            eprintln!("{e:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match cli.command {
+        None => run_days(year, root, select(days, &cli.days)),
+        Some(Command::Check { days: only }) => check_days(root, select(days, &only)),
+        Some(Command::Bench {
+            days: only,
+            save,
+            threshold,
+        }) => bench_days(root, select(days, &only), save, threshold / 100.0),
+        Some(Command::Heap { days: only, save }) => heap_days(root, select(days, &only), save),
+        Some(Command::Fetch { days: only }) => fetch_days(year, root, days, &only),
+        Some(Command::Gen { day, size, seed }) => generate(year, days, day, size, seed),
+        Some(Command::Submit { day, part, answer }) => submit(year, root, days, day, part, answer),
+    }
+}
+
+fn select<'a>(days: &'a [Day], only: &'a [u8]) -> impl Iterator<Item = &'a Day> + 'a {
+    days.iter()
+        .filter(move |day| only.is_empty() || only.contains(&day.day))
+}
+
+fn run_days<'a>(year: u16, root: &Path, days: impl Iterator<Item = &'a Day>) -> ExitCode {
+    let mut failures = 0;
+    for day in days {
+        for part in [1, 2] {
+            let outcome = run_part(root, day, part);
+            match &outcome.result {
+                Ok(answer) => {
+                    let timings = match outcome.timings {
+                        Some(Timings { parse, solve }) => {
+                            format!(": parse {parse:?}, solve {solve:?}")
+                        }
+                        None => String::new(),
+                    };
+                    println!(
+                        "{year} day {:02} part {}: {answer} ({:?}{timings})",
+                        outcome.day, outcome.part, outcome.elapsed
+                    );
+                }
+                Err(error) => {
+                    failures += 1;
+                    println!(
+                        "{year} day {:02} part {}: FAILED: {error}",
+                        outcome.day, outcome.part
+                    );
+                }
+            }
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("{failures} part(s) failed");
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn check_days<'d>(root: &Path, days: impl Iterator<Item = &'d Day>) -> ExitCode {
+    let answers = match Answers::load(&root.join("answers.toml")) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{e:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    check::print_row(None);
+    for row in check(root, days, &answers) {
+        check::print_row(Some(&row));
+        match row.status() {
+            Status::Pass => passed += 1,
+            Status::Fail => failed += 1,
+            Status::Missing => missing += 1,
+        }
+    }
+
+    println!("\n{passed} passed, {failed} failed, {missing} missing");
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench_days<'d>(
+    root: &Path,
+    days: impl Iterator<Item = &'d Day>,
+    save: bool,
+    threshold: f64,
+) -> ExitCode {
+    let path = root.join("bench-baseline.json");
+    let mut baseline = match Baseline::load(&path) {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("{e:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (mut faster, mut slower, mut failures) = (0, 0, 0);
+    let mut measured = Vec::new();
+    bench::print_row(None);
+    for day in days {
+        let medians = match bench(root, day) {
+            Ok(medians) if medians.is_empty() => {
+                eprintln!("{} has no divan benchmarks", day.dir);
+                continue;
+            }
+            Ok(medians) => medians,
+            Err(e) => {
+                failures += 1;
+                eprintln!("{e:#}");
+                continue;
+            }
+        };
+
+        let before = baseline.get(day.dir);
+        for (name, &median) in &medians {
+            let previous = before.and_then(|before| before.get(name)).copied();
+            let change = Change::new(previous, median, threshold);
+            bench::print_row(Some((day.day, name, previous, median, change)));
+            match change {
+                Change::Faster(_) => faster += 1,
+                Change::Slower(_) => slower += 1,
+                Change::New | Change::Same => {}
+            }
+        }
+        measured.push((day.dir, medians));
+    }
+    println!("\n{faster} faster, {slower} slower, {failures} day(s) failed to run");
+
+    if save {
+        for (dir, medians) in measured {
+            baseline.set(dir, medians);
+        }
+        if let Err(e) = baseline.save(&path) {
+            eprintln!("{e:#}");
+            return ExitCode::FAILURE;
+        }
+        println!("saved {}", path.display());
+    }
+
+    if failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(feature = "dhat-heap")]
+fn heap_days<'d>(root: &Path, days: impl Iterator<Item = &'d Day>, save: bool) -> ExitCode {
+    let path = root.join("heap-budget.toml");
+    let mut budgets = match Budgets::load(&path) {
+        Ok(budgets) => budgets,
+        Err(e) => {
+            eprintln!("{e:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let days = days.collect::<Vec<_>>();
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    let mut measured = Vec::new();
+    heap::print_row(None);
+    for row in profile(root, days.iter().copied(), &budgets) {
+        heap::print_row(Some(&row));
+        match row.status() {
+            Status::Pass => passed += 1,
+            Status::Fail => failed += 1,
+            Status::Missing => missing += 1,
+        }
+        if let (Ok(_), Some(heap)) = (&row.outcome.result, row.heap) {
+            let day = days.iter().find(|day| day.day == row.outcome.day);
+            measured.extend(day.map(|day| (day.dir, row.outcome.part, heap)));
+        }
+    }
+    println!("\n{passed} within budget, {failed} failed, {missing} without a budget");
+
+    if save {
+        for (dir, part, heap) in measured {
+            budgets.set(dir, part, Budget::around(&heap));
+        }
+        if let Err(e) = budgets.save(&path) {
+            eprintln!("{e:#}");
+            return ExitCode::FAILURE;
+        }
+        println!("saved {}", path.display());
+        return ExitCode::SUCCESS;
+    }
+
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+fn heap_days<'d>(_: &Path, _: impl Iterator<Item = &'d Day>, _: bool) -> ExitCode {
+    eprintln!("heap profiling needs the runner built with `--features dhat-heap`");
+    ExitCode::FAILURE
+}
+
+fn fetch_days(year: u16, root: &Path, days: &[Day], only: &[u8]) -> ExitCode {
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failures = 0;
+    for &number in only {
+        let Some(day) = days.iter().find(|day| day.day == number) else {
+            failures += 1;
+            eprintln!("{year} day {number:02} is not registered with the runner");
+            continue;
+        };
+
+        match fetch(&client, year, root, day) {
+            Ok(written) if written.is_empty() => {
+                println!("{year} day {number:02}: input already present")
+            }
+            Ok(written) => println!("{year} day {number:02}: wrote {}", written.join(", ")),
+            Err(e) => {
+                failures += 1;
+                eprintln!("{e:#}");
+            }
+        }
+    }
+
+    if failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn generate(year: u16, days: &[Day], day: u8, size: usize, seed: Option<u64>) -> ExitCode {
+    let Some(registered) = days.iter().find(|d| d.day == day) else {
+        eprintln!("{year} day {day:02} is not registered with the runner");
+        return ExitCode::FAILURE;
+    };
+
+    let seed = seed.unwrap_or_else(|| Rng::new().u64(..));
+    let Some(input) = (registered.generate)(&mut Rng::with_seed(seed), size) else {
+        eprintln!("{year} day {day:02} has no input generator");
+        return ExitCode::FAILURE;
+    };
+
+    // The input goes to stdout, ready to redirect into a file.
+    eprintln!("{year} day {day:02}: size {size}, seed {seed}");
+    print!("{input}");
+    ExitCode::SUCCESS
+}
+
+fn submit(
+    year: u16,
+    root: &Path,
+    days: &[Day],
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+) -> ExitCode {
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let Some(registered) = days.iter().find(|d| d.day == day) else {
+                eprintln!("{year} day {day:02} is not registered with the runner");
+                return ExitCode::FAILURE;
+            };
+            match run_part(root, registered, part).result {
+                Ok(answer) => answer,
+                Err(error) => {
+                    eprintln!("{year} day {day:02} part {part}: FAILED: {error}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    };
+
+    match client.submit(year, day, part, &answer) {
+        Ok(reply) => {
+            println!(
+                "{year} day {day:02} part {part}: {answer} is {}",
+                reply.verdict
+            );
+            println!("{}", reply.message);
+            if reply.verdict == Verdict::Correct {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{Day, Part, Timings};
+use std::{
+    cell::RefCell,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// How one part of one day went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub day: u8,
+    pub part: u8,
+    /// The answer, or why there is none.
+    pub result: Result<String, String>,
+    pub elapsed: Duration,
+    /// Where `elapsed` went, for days split into [`Phases`](crate::Phases).
+    pub timings: Option<Timings>,
+}
+
+thread_local! {
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs both parts of `day`, turning errors and panics into failed
+/// [`Outcome`]s.
+pub fn run(root: &Path, day: &Day) -> [Outcome; 2] {
+    [1, 2].map(|part| run_part(root, day, part))
+}
+
+/// Runs a single part, 1 or 2, of `day`.
+pub fn run_part(root: &Path, day: &Day, part: u8) -> Outcome {
+    let start = Instant::now();
+    let (solver, input) = input(root, day, part);
+    let mut timings = None;
+    let result = input.and_then(|input| match day.phases {
+        Some(phases) => solve(|| {
+            let (answer, spent) = phases(input, part);
+            timings = Some(spent);
+            answer
+        }),
+        None => solve(|| solver(input)),
+    });
+
+    Outcome {
+        day: day.day,
+        part,
+        result,
+        elapsed: start.elapsed(),
+        timings,
+    }
+}
+
+/// The solver of `part` and the input it reads.
+pub(crate) fn input(root: &Path, day: &Day, part: u8) -> (Part, Result<&'static str, String>) {
+    let (solver, input): (Part, _) = match part {
+        1 => (day.part1, day.inputs[0]),
+        _ => (day.part2, day.inputs[1]),
+    };
+
+    let path = root.join(day.dir).join(input);
+    let input = match fs::read_to_string(&path) {
+        // Parts borrow their input for `'static`; the runner exits soon
+        // after, so leaking it is simpler than threading a lifetime.
+        Ok(input) => Ok(&*input.leak()),
+        Err(e) => Err(format!("reading {}: {e}", path.display())),
+    };
+
+    (solver, input)
+}
+
+pub(crate) fn solve(part: impl FnOnce() -> anyhow::Result<String>) -> Result<String, String> {
+    match catch(part) {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(e)) => Err(format!("{e:#}")),
+        Err(panic) => Err(panic),
+    }
+}
+
+/// Runs `f`, or describes how it panicked.
+pub(crate) fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    // Record the panic message, location included, instead of letting the
+    // default hook print it in the middle of the report.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        PANIC.with(|p| *p.borrow_mut() = Some(info.to_string()));
+    }));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(hook);
+
+    result.map_err(|_| {
+        PANIC
+            .with(|p| p.borrow_mut().take())
+            .unwrap_or_else(|| "panicked".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{Context, bail};
+
+    fn answer(input: &'static str) -> anyhow::Result<String> {
+        Ok(input.trim().len().to_string())
+    }
+
+    fn fails(_: &'static str) -> anyhow::Result<String> {
+        bail!("no start tile")
+    }
+
+    fn panics(_: &'static str) -> anyhow::Result<String> {
+        panic!("invalid input")
+    }
+
+    #[test]
+    fn failures_do_not_stop_the_run() {
+        let root = std::env::temp_dir().join("aoc-runner-test");
+        fs::create_dir_all(root.join("day01")).unwrap();
+        fs::write(root.join("day01/input.txt"), "abc\n").unwrap();
+
+        let [first, second] = run(&root, &Day::new(1, "day01", answer, fails));
+        assert_eq!(first.result, Ok("3".to_string()));
+        assert_eq!(second.result, Err("no start tile".to_string()));
+
+        let [first, _] = run(&root, &Day::new(1, "day01", panics, answer));
+        assert!(first.result.unwrap_err().contains("invalid input"));
+
+        let [first, _] = run(
+            &root,
+            &Day::new(1, "day01", answer, answer).with_inputs("x", "y"),
+        );
+        assert!(first.result.unwrap_err().starts_with("reading"));
+    }
+
+    struct Sum;
+
+    impl aoc_core::Solution for Sum {
+        const YEAR: u16 = 2015;
+        const DAY: u8 = 1;
+
+        type Input = Vec<u32>;
+
+        fn parse(input: &'static str) -> anyhow::Result<Vec<u32>> {
+            input.split_whitespace().map(|n| Ok(n.parse()?)).collect()
+        }
+
+        fn part1(numbers: &Vec<u32>) -> anyhow::Result<impl std::fmt::Display> {
+            Ok(numbers.iter().sum::<u32>())
+        }
+
+        fn part2(numbers: &Vec<u32>) -> anyhow::Result<impl std::fmt::Display> {
+            numbers.iter().map(|n| 100 / n).max().context("no numbers")
+        }
+
+        fn generate(rng: &mut aoc_core::Rng, size: usize) -> Option<String> {
+            Some((0..size).map(|_| format!("{} ", rng.u32(1..100))).collect())
+        }
+    }
+
+    #[test]
+    fn times_phases_apart() {
+        let root = std::env::temp_dir().join("aoc-runner-phases");
+        fs::create_dir_all(root.join("day01")).unwrap();
+        fs::write(root.join("day01/input.txt"), "1 2 3\n").unwrap();
+
+        let day = Day::solution::<Sum>("day01");
+        let [first, second] = run(&root, &day);
+        assert_eq!(first.result, Ok("6".to_string()));
+        assert_eq!(second.result, Ok("100".to_string()));
+        let timings = first.timings.unwrap();
+        assert!(timings.parse + timings.solve <= first.elapsed);
+
+        fs::write(root.join("day01/input.txt"), "0 1\n").unwrap();
+        let [_, second] = run(&root, &day);
+        assert!(second.result.unwrap_err().contains("divide by zero"));
+        assert_eq!(second.timings, None);
+
+        fs::write(root.join("day01/input.txt"), "1 two\n").unwrap();
+        let [first, _] = run(&root, &day);
+        assert!(first.result.is_err());
+        assert_eq!(first.timings.unwrap().solve, Duration::ZERO);
+    }
+
+    #[test]
+    fn generates_inputs_from_a_seed() {
+        let generate = Day::solution::<Sum>("day01").generate;
+        let input = generate(&mut aoc_core::Rng::with_seed(7), 5).unwrap();
+        assert_eq!(input.split_whitespace().count(), 5);
+        assert_eq!(generate(&mut aoc_core::Rng::with_seed(7), 5), Some(input));
+
+        let generate = Day::new(1, "day01", answer, answer).generate;
+        assert_eq!(generate(&mut aoc_core::Rng::with_seed(7), 5), None);
+    }
+}
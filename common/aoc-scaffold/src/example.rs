@@ -0,0 +1,82 @@
+use crate::{read, write};
+use anyhow::{Result, bail};
+use std::path::Path;
+
+/// What the template's `it_works` tests feed to `process`.
+const EMPTY: &str = r###"r##""##"###;
+
+/// The example of a saved puzzle page: its first `<pre><code>` block, as
+/// plain text.
+pub fn example(html: &str) -> Option<String> {
+    let (_, rest) = html.split_once("<pre><code>")?;
+    let (code, _) = rest.split_once("</code></pre>")?;
+
+    // Examples highlight parts of themselves with `<em>`.
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in code.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+
+    Some(text)
+}
+
+/// Makes `example` the input of the `it_works` test of both parts.
+pub(crate) fn fill(day_dir: &Path, example: &str) -> Result<()> {
+    if example.contains("\"##") {
+        bail!("the example cannot be written as an r##\"\"## string");
+    }
+
+    for part in ["part1.rs", "part2.rs"] {
+        let path = day_dir.join("src").join(part);
+        let source = read(&path)?;
+        if !source.contains(EMPTY) {
+            bail!("{} has no empty example to fill in", path.display());
+        }
+
+        write(
+            &path,
+            source.replacen(EMPTY, &format!("r##\"{example}\"##"), 1),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_the_first_block() {
+        let page = r#"<article class="day-desc"><h2>--- Day 7: Bridge Repair ---</h2>
+<p>For example:</p>
+<pre><code>190: 10 19
+3267: 81 40 27
+<em>x</em> &lt;-&gt; &amp;y
+</code></pre>
+<p>Only three of the above equations can be made true:</p>
+<pre><code>not this one
+</code></pre>
+</article>"#;
+
+        assert_eq!(
+            example(page).as_deref(),
+            Some("190: 10 19\n3267: 81 40 27\nx <-> &y\n")
+        );
+        assert_eq!(example("<p>no example</p>"), None);
+    }
+}
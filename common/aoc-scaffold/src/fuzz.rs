@@ -0,0 +1,85 @@
+use crate::{read, write};
+use anyhow::{Context, Result};
+use std::{fs, path::Path};
+use toml_edit::{ArrayOfTables, DocumentMut, InlineTable, Item, Table, value};
+
+/// Creates the fuzz crate of a new year, with no targets yet. It is its own
+/// workspace, since building it needs nightly and libFuzzer.
+pub(crate) fn create(year_dir: &Path, edition: &str) -> Result<()> {
+    let dir = year_dir.join("fuzz");
+    fs::create_dir_all(dir.join("fuzz_targets"))?;
+
+    write(
+        &dir.join("Cargo.toml"),
+        format!(
+            r#"# One libFuzzer target per day, run from here with
+# `cargo +nightly fuzz run <day> -- -max_total_time=300`. Inputs a day
+# panicked on are kept in `regressions/<day>/`, which the runner's tests
+# replay.
+[package]
+name = "fuzz"
+version = "0.0.0"
+publish = false
+edition = "{edition}"
+
+[package.metadata]
+cargo-fuzz = true
+
+[dependencies]
+aoc-runner = {{ path = "../../common/aoc-runner" }}
+libfuzzer-sys = "0.4"
+
+# Kept out of the year's workspace, which builds on stable.
+[workspace]
+members = ["."]
+"#
+        ),
+    )?;
+    write(
+        &dir.join(".gitignore"),
+        "target\ncorpus\nartifacts\ncoverage\n",
+    )
+}
+
+/// Gives the day crate `name` a fuzz target of the same name.
+pub(crate) fn register(year_dir: &Path, name: &str) -> Result<()> {
+    let dir = year_dir.join("fuzz");
+    let path = dir.join("Cargo.toml");
+    let mut manifest = read(&path)?
+        .parse::<DocumentMut>()
+        .with_context(|| format!("parsing {}", path.display()))?;
+
+    let dependencies = manifest["dependencies"]
+        .as_table_mut()
+        .with_context(|| format!("{} has no [dependencies]", path.display()))?;
+    let mut dependency = InlineTable::new();
+    dependency.insert("path", format!("../{name}").into());
+    dependencies.insert(name, value(dependency));
+    dependencies.sort_values();
+
+    let mut target = Table::new();
+    target["name"] = value(name);
+    target["path"] = value(format!("fuzz_targets/{name}.rs"));
+    target["test"] = value(false);
+    target["doc"] = value(false);
+    target["bench"] = value(false);
+    manifest
+        .entry("bin")
+        .or_insert(Item::ArrayOfTables(ArrayOfTables::new()))
+        .as_array_of_tables_mut()
+        .with_context(|| format!("{} has a [bin] that is not [[bin]]", path.display()))?
+        .push(target);
+
+    write(&path, manifest.to_string())?;
+    write(
+        &dir.join(format!("fuzz_targets/{name}.rs")),
+        format!(
+            r#"#![no_main]
+
+libfuzzer_sys::fuzz_target!(|data: &[u8]| {{
+    aoc_runner::fuzz(&aoc_runner::Day::solution::<{name}::Puzzle>("{name}"), data);
+}});
+"#
+        ),
+    )
+}
@@ -0,0 +1,134 @@
+//! Creates new days, and new years, from the repository's `template/`.
+//!
+//! A day is `{year}/dayNN`: the template with its `{{project-name}}`
+//! placeholders filled in, registered with the year's workspace and runner,
+//! and given a fuzz target. A year that does not exist yet first gets a
+//! workspace sharing the latest year's `[workspace.dependencies]`, and a
+//! runner and fuzz crate with no days.
+
+mod example;
+mod fuzz;
+mod runner;
+mod template;
+mod workspace;
+
+use anyhow::{Context, Result, bail, ensure};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub use example::example;
+
+/// Creates `day` of `year` under `root`, the repository root, and returns
+/// its directory. With `example`, the text of a saved puzzle page, the
+/// page's example becomes the input of both parts' `it_works` tests.
+pub fn new_day(root: &Path, year: u16, day: u8, example: Option<&str>) -> Result<PathBuf> {
+    ensure!((1..=25).contains(&day), "there is no day {day}");
+    let name = format!("day{day:02}");
+
+    let year_dir = workspace::ensure_year(root, year)?;
+    // Older years name their days without padding, like `2023/day5`.
+    for existing in [format!("day{day}"), name.clone()] {
+        let existing = year_dir.join(existing);
+        if existing.exists() {
+            bail!("{} already exists", existing.display());
+        }
+    }
+    let day_dir = year_dir.join(&name);
+
+    let edition = runner::edition(&year_dir)?;
+    template::instantiate(&root.join("template"), &day_dir, &name, year, day, &edition)?;
+    if let Some(html) = example {
+        let example = example::example(html).context("the page has no example block")?;
+        example::fill(&day_dir, &example)?;
+    }
+
+    workspace::register(&year_dir, &name)?;
+    runner::register(&year_dir, &name)?;
+    fuzz::register(&year_dir, &name)?;
+
+    Ok(day_dir)
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))
+}
+
+fn write(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    fs::write(path, contents).with_context(|| format!("writing {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaffolds_days_and_years() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("2025")).unwrap();
+        std::os::unix::fs::symlink(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../template"),
+            root.join("template"),
+        )
+        .unwrap();
+        fs::write(
+            root.join("2025/Cargo.toml"),
+            "[workspace]\nresolver = \"2\"\nmembers = [\n    \"day01\",\n]\n\n\
+             [workspace.dependencies]\nanyhow = \"1.0.100\"\n",
+        )
+        .unwrap();
+        runner::create(&root.join("2025"), 2025, "2024").unwrap();
+        fuzz::create(&root.join("2025"), "2024").unwrap();
+
+        let page = "<p>For example:</p>\n<pre><code>3   4\n4   3\n</code></pre>";
+        let day = new_day(&root, 2025, 4, Some(page)).unwrap();
+        assert!(day.ends_with("2025/day04"));
+        let manifest = read(&day.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"day04\"") && manifest.contains("edition = \"2024\""));
+        assert!(
+            read(&day.join("src/part2.rs"))
+                .unwrap()
+                .contains("r##\"3   4\n4   3\n\"##")
+        );
+        assert!(
+            read(&root.join("2025/Cargo.toml"))
+                .unwrap()
+                .contains("\"day04\"")
+        );
+        assert!(
+            read(&root.join("2025/runner/Cargo.toml"))
+                .unwrap()
+                .contains("day04 = { path = \"../day04\" }")
+        );
+        let fuzz = read(&root.join("2025/fuzz/Cargo.toml")).unwrap();
+        assert!(
+            fuzz.contains("day04 = { path = \"../day04\" }")
+                && fuzz.contains("[[bin]]\nname = \"day04\"\npath = \"fuzz_targets/day04.rs\"")
+        );
+        assert!(
+            read(&root.join("2025/fuzz/fuzz_targets/day04.rs"))
+                .unwrap()
+                .contains("day04::Puzzle")
+        );
+        let lib = read(&day.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("const YEAR: u16 = 2025;") && lib.contains("const DAY: u8 = 4;"));
+        assert!(new_day(&root, 2025, 4, None).is_err());
+
+        new_day(&root, 2026, 1, None).unwrap();
+        assert!(root.join("2026/fuzz/fuzz_targets/day01.rs").exists());
+        let workspace = read(&root.join("2026/Cargo.toml")).unwrap();
+        assert!(workspace.contains("\"day*\"") && workspace.contains("anyhow = \"1.0.100\""));
+        assert!(
+            read(&root.join("2026/runner/Cargo.toml"))
+                .unwrap()
+                .contains("day01 = { path = \"../day01\" }")
+        );
+
+        // A year laid out like 2023, with unpadded day directories.
+        fs::create_dir_all(root.join("2026/day5")).unwrap();
+        assert!(new_day(&root, 2026, 5, None).is_err());
+        assert!(!root.join("2026/day05").exists());
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use std::{fs, path::PathBuf};
+
+#[derive(Debug, Parser)]
+#[command(about = "Scaffolding for Advent of Code days")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Create `{year}/dayNN` from the template and register it with the
+    /// year's workspace and runner, creating the year if needed.
+    NewDay {
+        year: u16,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// A saved puzzle page whose example becomes the `it_works` input.
+        #[arg(long)]
+        example: Option<PathBuf>,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .canonicalize()?;
+
+    match cli.command {
+        Command::NewDay { year, day, example } => {
+            let html = example
+                .map(|page| {
+                    fs::read_to_string(&page).with_context(|| format!("reading {}", page.display()))
+                })
+                .transpose()?;
+
+            let dir = aoc_scaffold::new_day(&root, year, day, html.as_deref())?;
+            println!("created {}", dir.display());
+        }
+    }
+
+    Ok(())
+}
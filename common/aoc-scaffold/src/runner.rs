@@ -0,0 +1,96 @@
+use crate::{read, write};
+use anyhow::{Context, Result};
+use std::{fs, path::Path};
+use toml_edit::{DocumentMut, InlineTable, value};
+
+/// The edition the year's runner, and so its days, are written in.
+pub(crate) fn edition(year_dir: &Path) -> Result<String> {
+    let path = year_dir.join("runner/Cargo.toml");
+    let manifest = read(&path)?
+        .parse::<DocumentMut>()
+        .with_context(|| format!("parsing {}", path.display()))?;
+
+    manifest["package"]["edition"]
+        .as_str()
+        .map(str::to_string)
+        .with_context(|| format!("{} has no edition", path.display()))
+}
+
+/// Creates the runner of a new year, with no days yet. Its `build.rs` lists
+/// the days it depends on.
+pub(crate) fn create(year_dir: &Path, year: u16, edition: &str) -> Result<()> {
+    let dir = year_dir.join("runner");
+    fs::create_dir_all(dir.join("src"))?;
+
+    write(
+        &dir.join("Cargo.toml"),
+        format!(
+            r#"[package]
+name = "runner"
+version = "0.1.0"
+edition = "{edition}"
+
+[dependencies]
+aoc-runner = {{ workspace = true }}
+
+[build-dependencies]
+anyhow = {{ workspace = true }}
+aoc-build = {{ workspace = true }}
+
+[features]
+dhat-heap = ["aoc-runner/dhat-heap"]
+"#
+        ),
+    )?;
+    write(
+        &dir.join("build.rs"),
+        "fn main() -> anyhow::Result<()> {\n    aoc_build::days()\n}\n",
+    )?;
+    write(
+        &dir.join("src/main.rs"),
+        format!(
+            r#"use std::{{path::Path, process::ExitCode}};
+
+const YEAR: u16 = {year};
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+fn main() -> ExitCode {{
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    aoc_runner::main(YEAR, &root, DAYS)
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    /// Inputs fuzzing found a day panicking on, see `fuzz/`.
+    #[test]
+    fn fuzz_regressions() {{
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let crashes = aoc_runner::replay(&root.join("fuzz/regressions"), DAYS).unwrap();
+        assert!(crashes.is_empty(), "{{crashes:#?}}");
+    }}
+}}
+"#
+        ),
+    )
+}
+
+/// Adds the day crate `name` to the runner's dependencies, which is all
+/// its `build.rs` needs to list it.
+pub(crate) fn register(year_dir: &Path, name: &str) -> Result<()> {
+    let path = year_dir.join("runner/Cargo.toml");
+    let mut manifest = read(&path)?
+        .parse::<DocumentMut>()
+        .with_context(|| format!("parsing {}", path.display()))?;
+    let dependencies = manifest["dependencies"]
+        .as_table_mut()
+        .with_context(|| format!("{} has no [dependencies]", path.display()))?;
+    let mut dependency = InlineTable::new();
+    dependency.insert("path", format!("../{name}").into());
+    dependencies.insert(name, value(dependency));
+    dependencies.sort_values();
+
+    write(&path, manifest.to_string())
+}
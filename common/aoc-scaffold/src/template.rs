@@ -0,0 +1,64 @@
+use crate::{read, write};
+use anyhow::{Context, Result};
+use std::{fs, path::Path};
+use toml_edit::{DocumentMut, value};
+
+/// Build output and lock files that may sit in the template after trying
+/// it out, and are not part of it.
+const SKIP: &[&str] = &["target", "Cargo.lock"];
+
+/// Copies `template` to `dest`, naming the crate `name` and writing it in
+/// `edition`.
+///
+/// Files are copied as they are apart from the `{{project-name}}` and
+/// `{{crate_name}}` placeholders, which day crates named `dayNN` both fill
+/// with `name`, and `{{year}}` and `{{day}}` for their `Puzzle`.
+pub(crate) fn instantiate(
+    template: &Path,
+    dest: &Path,
+    name: &str,
+    year: u16,
+    day: u8,
+    edition: &str,
+) -> Result<()> {
+    let placeholders = [
+        ("{{project-name}}", name.to_string()),
+        ("{{crate_name}}", name.to_string()),
+        ("{{year}}", year.to_string()),
+        ("{{day}}", day.to_string()),
+    ];
+    copy(template, dest, &placeholders)?;
+
+    let path = dest.join("Cargo.toml");
+    let mut manifest = read(&path)?
+        .parse::<DocumentMut>()
+        .with_context(|| format!("parsing {}", path.display()))?;
+    manifest["package"]["edition"] = value(edition);
+
+    write(&path, manifest.to_string())
+}
+
+fn copy(from: &Path, to: &Path, placeholders: &[(&str, String)]) -> Result<()> {
+    fs::create_dir_all(to).with_context(|| format!("creating {}", to.display()))?;
+
+    for entry in fs::read_dir(from).with_context(|| format!("reading {}", from.display()))? {
+        let entry = entry?;
+        if SKIP.iter().any(|skip| entry.file_name() == *skip) {
+            continue;
+        }
+
+        let dest = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy(&entry.path(), &dest, placeholders)?;
+        } else {
+            let contents = placeholders
+                .iter()
+                .fold(read(&entry.path())?, |contents, (placeholder, value)| {
+                    contents.replace(placeholder, value)
+                });
+            write(&dest, contents)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use crate::{fuzz, read, runner, write};
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use toml_edit::{Array, DocumentMut, Value, value};
+
+/// The workspace directory of `year`, created if it does not exist yet.
+///
+/// A new year copies the workspace manifest of the latest year, so the
+/// shared `[workspace.dependencies]` carry over, and gets its own runner
+/// and fuzz crate.
+pub(crate) fn ensure_year(root: &Path, year: u16) -> Result<PathBuf> {
+    let year_dir = root.join(year.to_string());
+    if year_dir.join("Cargo.toml").exists() {
+        return Ok(year_dir);
+    }
+
+    let latest =
+        latest_year(root)?.context("no existing year to copy [workspace.dependencies] from")?;
+    let mut manifest = read(&latest.join("Cargo.toml"))?
+        .parse::<DocumentMut>()
+        .with_context(|| format!("parsing {}", latest.join("Cargo.toml").display()))?;
+    manifest["workspace"]["members"] = value(members(["day*", "runner"].map(String::from)));
+
+    fs::create_dir_all(&year_dir)?;
+    write(&year_dir.join("Cargo.toml"), manifest.to_string())?;
+    write(
+        &year_dir.join("answers.toml"),
+        "# Known-correct answers, checked with `cargo run --release -p runner -- check`.\n\
+         # Tables are keyed by day directory; leave a part out until it is solved.\n",
+    )?;
+    let edition = runner::edition(&latest)?;
+    runner::create(&year_dir, year, &edition)?;
+    fuzz::create(&year_dir, &edition)?;
+
+    Ok(year_dir)
+}
+
+/// Adds `name` to the workspace's members, unless a glob already covers it.
+pub(crate) fn register(year_dir: &Path, name: &str) -> Result<()> {
+    let path = year_dir.join("Cargo.toml");
+    let mut manifest = read(&path)?
+        .parse::<DocumentMut>()
+        .with_context(|| format!("parsing {}", path.display()))?;
+    let members = manifest["workspace"]["members"]
+        .as_array_mut()
+        .with_context(|| format!("{} has no workspace members", path.display()))?;
+
+    let covered = members.iter().filter_map(Value::as_str).any(|member| {
+        member == name
+            || member
+                .strip_suffix('*')
+                .is_some_and(|prefix| name.starts_with(prefix))
+    });
+    if !covered {
+        let names = members
+            .iter()
+            .filter_map(Value::as_str)
+            .chain([name])
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        *members = self::members(names);
+        write(&path, manifest.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// The year directory with the highest number that has a workspace.
+fn latest_year(root: &Path) -> Result<Option<PathBuf>> {
+    let mut years = fs::read_dir(root)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("Cargo.toml").exists())
+        .filter_map(|entry| {
+            let year = entry.file_name().to_str()?.parse::<u16>().ok()?;
+            Some((year, entry.path()))
+        })
+        .collect::<Vec<_>>();
+    years.sort();
+
+    Ok(years.pop().map(|(_, path)| path))
+}
+
+/// One member per line, as the existing manifests are written.
+fn members(names: impl IntoIterator<Item = String>) -> Array {
+    let mut array = names.into_iter().collect::<Array>();
+    for member in array.iter_mut() {
+        member.decor_mut().set_prefix("\n    ");
+    }
+    array.set_trailing("\n");
+    array.set_trailing_comma(true);
+
+    array
+}
@@ -0,0 +1,165 @@
+//! The one place `tracing` is set up, for the days' binaries and the
+//! runners alike.
+//!
+//! Events go to stderr, filtered like `RUST_LOG` and in the chosen
+//! [`Format`], so answers printed on stdout stay easy to read or pipe.
+//! Spans can also be written as a Chrome trace, and with the `tracy`
+//! feature they are sent to Tracy as zones.
+
+use anyhow::{Context, Result};
+use std::{fs::File, io, path::PathBuf};
+use tracing::Subscriber;
+use tracing_subscriber::{
+    EnvFilter, Layer, filter::filter_fn, layer::SubscriberExt, util::SubscriberInitExt,
+};
+
+#[cfg(feature = "tracy")]
+pub use tracy_client;
+
+/// How events are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// `tracing_subscriber`'s default, one line per event.
+    #[default]
+    Full,
+    Compact,
+    /// Several lines per event, with source locations.
+    Pretty,
+    /// One JSON object per line.
+    Json,
+}
+
+/// What to record, as command-line flags. The default prints what
+/// `RUST_LOG` asks for, like `tracing_subscriber::fmt::init`.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct Telemetry {
+    /// Events to print, as `RUST_LOG` directives, which this overrides.
+    #[arg(long, global = true, value_name = "DIRECTIVES")]
+    pub log: Option<String>,
+
+    /// How to print events.
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub log_format: Format,
+
+    /// Write every span to this file as a Chrome trace, to be opened in
+    /// Perfetto or `chrome://tracing`.
+    #[arg(long, global = true, value_name = "FILE")]
+    pub chrome_trace: Option<PathBuf>,
+}
+
+/// Keeps telemetry that is written out at exit, like the Chrome trace,
+/// going until it is dropped.
+#[must_use = "the Chrome trace is only complete once this is dropped"]
+pub struct Guard {
+    _chrome: Option<tracing_chrome::FlushGuard>,
+}
+
+/// Installs the global subscriber described by `telemetry`.
+pub fn init_telemetry(telemetry: &Telemetry) -> Result<Guard> {
+    let filter = match &telemetry.log {
+        Some(directives) => EnvFilter::try_new(directives)
+            .with_context(|| format!("bad log directives {directives:?}"))?,
+        None => EnvFilter::from_default_env(),
+    };
+
+    let mut layers = vec![events(telemetry.log_format).with_filter(filter).boxed()];
+
+    let mut chrome = None;
+    if let Some(path) = &telemetry.chrome_trace {
+        let file = File::create(path).with_context(|| format!("creating {}", path.display()))?;
+        let (layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
+            .writer(file)
+            .include_args(true)
+            .build();
+        // Only spans: events in hot loops would swamp the trace, and
+        // leaving them out keeps them free when nothing prints them.
+        layers.push(layer.with_filter(filter_fn(|meta| meta.is_span())).boxed());
+        chrome = Some(guard);
+    }
+
+    #[cfg(feature = "tracy")]
+    layers.push(tracing_tracy::TracyLayer::default().boxed());
+
+    tracing_subscriber::registry()
+        .with(layers)
+        .try_init()
+        .context("installing the tracing subscriber")?;
+
+    Ok(Guard { _chrome: chrome })
+}
+
+fn events<S>(format: Format) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    let layer = tracing_subscriber::fmt::layer().with_writer(io::stderr);
+    match format {
+        Format::Full => layer.boxed(),
+        Format::Compact => layer.compact().boxed(),
+        Format::Pretty => layer.pretty().boxed(),
+        Format::Json => layer.json().boxed(),
+    }
+}
+
+/// Marks a Tracy frame named `$name` until the returned guard is dropped.
+/// Does nothing without the `tracy` feature, or if Tracy is not running.
+#[cfg(feature = "tracy")]
+#[macro_export]
+macro_rules! frame {
+    ($name:literal) => {
+        $crate::tracy_client::Client::running()
+            .map(|client| client.non_continuous_frame($crate::tracy_client::frame_name!($name)))
+    };
+}
+
+/// Marks a Tracy frame named `$name` until the returned guard is dropped.
+/// Does nothing without the `tracy` feature, or if Tracy is not running.
+#[cfg(not(feature = "tracy"))]
+#[macro_export]
+macro_rules! frame {
+    ($name:literal) => {
+        ()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[command(flatten)]
+        telemetry: Telemetry,
+    }
+
+    #[test]
+    fn flags() {
+        let cli = Cli::parse_from(["runner", "--log", "day09=debug", "--log-format", "json"]);
+        assert_eq!(cli.telemetry.log.as_deref(), Some("day09=debug"));
+        assert_eq!(cli.telemetry.log_format, Format::Json);
+
+        let cli = Cli::parse_from(["runner"]);
+        assert_eq!(cli.telemetry.log_format, Format::Full);
+        assert_eq!(cli.telemetry.chrome_trace, None);
+    }
+
+    #[test]
+    fn writes_a_chrome_trace() {
+        let path = std::env::temp_dir().join(format!("aoc-telemetry-{}.json", std::process::id()));
+        let telemetry = Telemetry {
+            log: Some("off".into()),
+            chrome_trace: Some(path.clone()),
+            ..Telemetry::default()
+        };
+
+        let guard = init_telemetry(&telemetry).unwrap();
+        tracing::info_span!("process").in_scope(|| tracing::info!("hot loop"));
+        drop(guard);
+
+        let trace = std::fs::read_to_string(&path).unwrap();
+        assert!(trace.contains("\"process\""));
+        assert!(!trace.contains("hot loop"));
+        assert!(init_telemetry(&Telemetry::default()).is_err());
+    }
+}
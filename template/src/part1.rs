@@ -1,3 +1,5 @@
+use crate::parse;
+use aoc_core::SolveError;
 use anyhow::{Context, Result};
 use nom::{
     branch::alt,
@@ -10,18 +12,23 @@ use nom::{
 };
 use tracing::info;
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(lines))]
+fn process(lines: &[&'static str]) -> Result<String, SolveError> {
     info!("processing input");
 
     Ok("".to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part1(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(lines))]
+pub fn solve_part1(lines: &[&'static str]) -> Result<String> {
     info!("part 1");
+    let _frame = aoc_telemetry::frame!("part 1");
+
+    process(lines).context("process part 1")
+}
 
-    process(input).context("process part 1")
+pub fn part1(input: &'static str) -> Result<String> {
+    solve_part1(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -31,7 +38,7 @@ mod tests {
     #[test_log::test]
     fn it_works() {
         let input = r##""##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "42");
     }
 }
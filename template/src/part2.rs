@@ -1,3 +1,5 @@
+use crate::parse;
+use aoc_core::SolveError;
 use anyhow::{Context, Result};
 use nom::{
     branch::alt,
@@ -10,18 +12,23 @@ use nom::{
 };
 use tracing::info;
 
-#[tracing::instrument(skip(input))]
-fn process(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(lines))]
+fn process(lines: &[&'static str]) -> Result<String, SolveError> {
     info!("processing input");
 
     Ok("".to_string())
 }
 
-#[tracing::instrument(skip(input))]
-pub fn part2(input: &'static str) -> Result<String> {
+#[tracing::instrument(skip(lines))]
+pub fn solve_part2(lines: &[&'static str]) -> Result<String> {
     info!("part 2");
+    let _frame = aoc_telemetry::frame!("part 2");
+
+    process(lines).context("process part 2")
+}
 
-    process(input).context("process part 2")
+pub fn part2(input: &'static str) -> Result<String> {
+    solve_part2(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -31,7 +38,7 @@ mod tests {
     #[test_log::test]
     fn it_works() {
         let input = r##""##;
-        let result = process(input).unwrap();
+        let result = process(&parse(input).unwrap()).unwrap();
         assert_eq!(result, "42");
     }
 }